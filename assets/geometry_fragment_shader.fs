@@ -1,9 +1,11 @@
 #version 330 core
 
 in vec3 color;
+// Replaces vertex color when alpha is one (edges drawn over filled triangles)
+uniform vec4 lineColor;
 
 out vec4 FragColor;
 
 void main() {
-    FragColor = vec4(color, 1.0f);
+    FragColor = vec4(mix(color, lineColor.rgb, lineColor.a), 1.0f);
 }
\ No newline at end of file
@@ -7,14 +7,14 @@ fn main() {
     .b(1.0)
     .mu(1.0)
     .boundary_conditions(1.0, 15.0)
-    .initial_conditions([0_f64,135_f64,1215_f64,15432_f64,212141_f64,43431_f64,6565_f64,3000_f64,655_f64]).build();
+    .initial_conditions([0.0,135.0,1215.0,15432.0,212141.0,43431.0,6565.0,3000.0,655.0]).build();
     
     //Creating window with predetermined configuration
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/1dbar.obj")
         .solve_1d_time_dependent_diffussion(diffussion_params)
         .with_integration_iteration(150).with_time_step(0.001);
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
     
 }
\ No newline at end of file
@@ -8,26 +8,26 @@ fn main() {
     .mu(1.0)
     .boundary_conditions(1.0, 500.0)
     .initial_conditions([
-        0_f64,5_f64,12_f64,
-        22_f64,21_f64,23_f64,
-        45_f64,67_f64,97_f64,
-        112_f64,156_f64,189_f64,
-        188_f64,200_f64,256_f64,
-        378_f64,423_f64,655_f64,
-        712_f64, 777_f64,
-        892_f64,1000_f64,1255_f64,
-        3000_f64,3000_f64,6655_f64,
-        6565_f64,3000_f64,655_f64,
-        400_f64,376_f64,356_f64,
-        400_f64,
+        0.0,5.0,12.0,
+        22.0,21.0,23.0,
+        45.0,67.0,97.0,
+        112.0,156.0,189.0,
+        188.0,200.0,256.0,
+        378.0,423.0,655.0,
+        712.0, 777.0,
+        892.0,1000.0,1255.0,
+        3000.0,3000.0,6655.0,
+        6565.0,3000.0,655.0,
+        400.0,376.0,356.0,
+        400.0,
         ]).build();
     
     //Creating window with predetermined configuration
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/1dbar_irregular.obj")
         .solve_1d_time_dependent_diffussion(diffussion_params)
-        .with_integration_iteration(150).with_time_step(0.00000001).enable_height_multiplier(3_f64);
+        .with_integration_iteration(150).with_time_step(0.00000001).enable_height_multiplier(3.0);
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
     
 }
\ No newline at end of file
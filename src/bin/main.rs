@@ -9,7 +9,7 @@ fn main() {
     // 2D MESH
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/untitled.obj");
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
 
 }
@@ -12,7 +12,7 @@ fn main() {
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/1dbar_many_divisions.obj")
         .solve_1d_diffussion(diffussion_params).with_integration_iteration(150).enable_height_multiplier(25_f64);
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
     
 }
\ No newline at end of file
@@ -213,7 +213,7 @@ fn main() {
         .solve_1d_time_dependent_diffussion(diffussion_params)
         .with_integration_iteration(150).with_time_step(0.000001).enable_height_multiplier(15_f64);
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
     
 }
\ No newline at end of file
@@ -9,7 +9,7 @@ fn main() {
     // 2D MESH
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/sphere.obj").with_mesh_in_3d();
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
 
 }
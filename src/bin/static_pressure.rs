@@ -3,14 +3,14 @@ use dzahui::{DzahuiWindow, DzahuiWindowBuilder, StokesParams};
 /// Simple static pressure example.
 fn main() {
 
-    let naviers_params = StokesParams::static_pressure().hydrostatic_pressure(100_f64).density(1_f64).force_function(
-        Box::new(|_| -10_f64)
+    let naviers_params = StokesParams::static_pressure().hydrostatic_pressure(100.0).density(1.0).force_function(
+        Box::new(|_| -10.0)
     ).build();
 
     let window_builder: DzahuiWindowBuilder = DzahuiWindow::builder("./assets/1dbar.obj")
         .solve_static_pressure(naviers_params).with_integration_iteration(350);
 
-    let window = window_builder.build();
+    let window = window_builder.build().unwrap();
     window.run();
 
 }
\ No newline at end of file
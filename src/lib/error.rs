@@ -28,6 +28,11 @@ use std::{num::{ParseIntError, ParseFloatError}, ffi::NulError, sync::mpsc::Recv
 /// * `Overflow` - Error when a number overflows
 /// * `Receiver` - Error on communication between threads
 /// * `Writing` - Error while writing to file values of equation
+/// * `Convergence` - Error when an iterative method does not converge within the allowed amount of steps
+/// * `Cancelled` - Error when a solve is stopped from outside before finishing. Contains the last solution obtained
+/// * `Config` - Error while reading parameters from a configuration file, either malformed or lacking a required field
+/// * `ContextCreation` - Error when no OpenGL context can be created. Contains every version and profile tried with the reason it failed
+/// * `OutOfDomain` - Error when a point to evaluate a solution at lies outside of the mesh
 /// 
 pub enum Error {
     ExtensionNotAllowed(String, String),
@@ -39,7 +44,7 @@ pub enum Error {
     NotFound(&'static str),
     NullCString(NulError),
     Matrix(&'static str),
-    Parse(&'static str),
+    Parse(String),
     Integration(String),
     Io(std::io::Error),
     MeshParse(String),
@@ -52,6 +57,11 @@ pub enum Error {
     Overflow,
     Receiver(RecvError),
     Writing,
+    Convergence(String),
+    Cancelled(Vec<crate::solvers::Scalar>),
+    Config(String),
+    ContextCreation(String),
+    OutOfDomain,
 }
 
 impl std::fmt::Display for Error {
@@ -84,7 +94,12 @@ impl std::fmt::Display for Error {
             },
             Error::Integration(e) => format!("Error on integration method occurred: {}",e),
             Error::Writing => format!("Error while writing to file values of differential equation"),
-            Error::Receiver(e) => format!("No message received on thread: {}",e)
+            Error::Receiver(e) => format!("No message received on thread: {}",e),
+            Error::Convergence(e) => format!("Method did not converge: {}",e),
+            Error::Cancelled(_) => String::from("Solve was cancelled before finishing"),
+            Error::Config(e) => format!("Invalid configuration: {}",e),
+            Error::ContextCreation(e) => format!("Unable to create OpenGL context. Tried: {}",e),
+            Error::OutOfDomain => String::from("Point lies outside of mesh")
         };
         write!(formatter, "{}", content)
     }
@@ -128,6 +143,12 @@ impl From<NulError> for Error {
     }
 }
 
+impl From<serde_json::Error> for Error {
+    fn from(source: serde_json::Error) -> Self {
+        Error::Config(source.to_string())
+    }
+}
+
 impl From<RecvError> for Error {
     fn from(source: RecvError) -> Self {
         Error::Receiver(source)
@@ -11,6 +11,7 @@ use std::io::{prelude::*, BufReader};
 
 // Communication with file writer
 use std::sync::mpsc::{sync_channel, SyncSender, Receiver};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::thread;
 
 // Print time adequately
@@ -297,10 +298,13 @@ impl DzahuiLogger {
     }
 }
 
+/// Wether a logger has already been set for this process
+static LOGGER_SPAWNED: AtomicBool = AtomicBool::new(false);
+
 /// # General Information
 /// 
-/// Spawns a boxed logger
-/// Must only be called once.
+/// Spawns a boxed logger.
+/// Only the first call sets the logger. Later calls only change the level of logging, so it can be configured before a window is built.
 /// 
 /// # Parameters
 /// 
@@ -308,7 +312,16 @@ impl DzahuiLogger {
 /// * `prefix` - Id of logger
 /// 
 pub fn spawn(log_level: log::LevelFilter, prefix: &'static str) -> Result<(), log::SetLoggerError> {
+    if LOGGER_SPAWNED.swap(true, Ordering::SeqCst) {
+        log::set_max_level(log_level);
+        return Ok(());
+    }
     log::set_boxed_logger(Box::new(DzahuiLogger::new(prefix, true, None))).map(|()| 
         log::set_max_level(log_level)
     )
+}
+
+/// Wether `spawn` has already been called
+pub fn is_spawned() -> bool {
+    LOGGER_SPAWNED.load(Ordering::SeqCst)
 }
\ No newline at end of file
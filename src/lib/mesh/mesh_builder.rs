@@ -3,12 +3,15 @@ use super::Mesh;
 use crate::{simulation::drawable::binder::Binder, Error};
 
 // External dependencies
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::{BufRead, BufReader};
 use cgmath::{Matrix4, Vector3};
 use ndarray::Array1;
+use serde::{Deserialize, Serialize};
 use std::fs::File;
 
+/// Vertices closer than this are taken as the same one when looking for boundary edges of 2D meshes
+const DEFAULT_WELD_TOLERANCE: f64 = 1e-9;
 
 /// # General Information
 ///
@@ -20,13 +23,32 @@ use std::fs::File;
 /// * `Two` - Plane figure. In 2D, one coordinate needs to be constant throught the whole mesh.
 /// * `Three` - 3D Body. No dimensional check-ups are done. Results depend solely on user's mesh.
 ///
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MeshDimension {
     One,
     Two,
     Three,
 }
 
+/// # General Information
+///
+/// Kind of line found inside an .obj once comments and surrounding whitespace are removed.
+///
+/// # Arms
+///
+/// * `Vertex` - Line starting with `v`.
+/// * `Face` - Line starting with `f`.
+/// * `Ignored` - Blank lines, comments and directives not used by dzahui (`vn`, `vt`, `vp`, `o`, `g`, `s`, `mtllib`, `usemtl`).
+/// * `Unsupported` - Any other directive, which is skipped as well. Holds the directive.
+///
+#[derive(Debug, PartialEq, Eq)]
+enum ObjLine {
+    Vertex,
+    Face,
+    Ignored,
+    Unsupported(String),
+}
+
 /// # General Information
 ///
 /// **Needs .obj**.
@@ -35,10 +57,18 @@ pub enum MeshDimension {
 /// # Fields
 ///
 /// * `location` - Path to .obj.
+/// * `nodes` - Ordered nodes of a generated 1D mesh. When present, .obj is not read by `build_mesh_1d`.
+/// * `base_color` - Initial color (RGB) of vertices without a color in .obj. Defaults to blue.
+/// * `height_multiplier` - Makes bar of 1D meshes taller when using `build`. Ignored for other dimensions.
+/// * `weld_tolerance` - Distance under which vertices of 2D meshes are considered the same when classifying edges as boundary or internal.
 ///
 #[derive(Debug)]
-pub(crate) struct MeshBuilder {
+pub struct MeshBuilder {
     location: String,
+    nodes: Option<Vec<f64>>,
+    base_color: [f32; 3],
+    height_multiplier: Option<f64>,
+    weld_tolerance: f64,
 }
 
 impl MeshBuilder {
@@ -49,19 +79,180 @@ impl MeshBuilder {
     {
         Self {
             location: location.as_ref().to_string(),
+            nodes: None,
+            base_color: [0.0, 0.0, 1.0],
+            height_multiplier: None,
+            weld_tolerance: DEFAULT_WELD_TOLERANCE,
+        }
+    }
+
+    /// Makes bar of 1D meshes built via `build` taller.
+    pub fn with_height_multiplier(self, height_multiplier: f64) -> Self {
+        Self {
+            height_multiplier: Some(height_multiplier),
+            ..self
+        }
+    }
+
+    #[allow(dead_code)]
+    /// Changes distance under which two vertices of a 2D mesh are taken as one when looking for boundary edges. Vertices themselves are not merged.
+    pub fn with_weld_tolerance(self, weld_tolerance: f64) -> Self {
+        if !(weld_tolerance > 0.0 && weld_tolerance.is_finite()) {
+            panic!("Weld tolerance should be positive");
+        }
+        Self {
+            weld_tolerance,
+            ..self
+        }
+    }
+
+    /// # General Information
+    ///
+    /// Builds a mesh of a given dimension, calling `build_mesh_1d`, `build_mesh_2d` or `build_mesh_3d`.
+    ///
+    /// # Parameters
+    ///
+    /// `self` - Consumes builder.
+    /// `dimension` - Dimension of mesh to build.
+    ///
+    pub fn build(self, dimension: MeshDimension) -> Result<Mesh, Error> {
+        match dimension {
+            MeshDimension::One => {
+                log::info!("Creating a 1D Mesh");
+                let height_multiplier = self.height_multiplier;
+                self.build_mesh_1d(height_multiplier)
+            }
+            MeshDimension::Two => {
+                log::info!("Creating a 2D Mesh");
+                self.build_mesh_2d()
+            }
+            MeshDimension::Three => {
+                log::info!("Creating a 3D Mesh");
+                self.build_mesh_3d()
+            }
+        }
+    }
+
+    /// Changes initial color (RGB) of vertices. Colors given in .obj are kept, and solutions override it once they're drawn.
+    pub fn with_base_color(self, base_color: [f32; 3]) -> Self {
+        Self {
+            base_color,
+            ..self
+        }
+    }
+
+    /// Base color as it is stored in vertices.
+    fn base_color_f64(&self) -> Vec<f64> {
+        self.base_color.iter().map(|c| *c as f64).collect()
+    }
+
+    /// # General Information
+    ///
+    /// Generates a 1D mesh over [start, end] without an .obj. A uniform parameter t in [0,1] is warped by `mapping` and the result is rescaled
+    /// so that the first and last nodes are `start` and `end`. With identity, nodes are uniform; with something like tanh they cluster where
+    /// the mapping is flat, which is useful for boundary-layer problems.
+    ///
+    /// # Parameters
+    ///
+    /// * `start` - First node
+    /// * `end` - Last node
+    /// * `n` - Amount of nodes. At least two
+    /// * `mapping` - Strictly monotonic function over [0,1]
+    ///
+    pub fn mesh_1d_from_mapping<M: Fn(f64) -> f64>(start: f64, end: f64, n: usize, mapping: M) -> Result<Self, Error> {
+        if n < 2 || start == end {
+            return Err(Error::MeshParse("A mapped 1D mesh needs at least two nodes over an interval of positive length".to_string()));
+        }
+
+        let warped: Vec<f64> = (0..n).map(|i| mapping(i as f64 / (n - 1) as f64)).collect();
+        let increasing = warped.windows(2).all(|pair| pair[1] > pair[0]);
+        let decreasing = warped.windows(2).all(|pair| pair[1] < pair[0]);
+        if !increasing && !decreasing {
+            return Err(Error::MeshParse("Mapping of 1D mesh should be strictly monotonic over [0,1]".to_string()));
+        }
+
+        let (first, last) = (warped[0], warped[n - 1]);
+        let mut nodes: Vec<f64> = warped
+            .iter()
+            .map(|w| start + (end - start) * (w - first) / (last - first))
+            .collect();
+        if start > end {
+            nodes.reverse();
+        }
+
+        Ok(Self {
+            location: String::new(),
+            nodes: Some(nodes),
+            base_color: [0.0, 0.0, 1.0],
+            height_multiplier: None,
+            weld_tolerance: DEFAULT_WELD_TOLERANCE,
+        })
+    }
+
+    /// # General Information
+    ///
+    /// Generates a 1D mesh from its nodes, without an .obj. Nodes are sorted, so they may be given in any order.
+    ///
+    /// # Parameters
+    ///
+    /// * `nodes` - Coordinates of nodes. At least two different ones
+    ///
+    pub(crate) fn mesh_1d_from_nodes(mut nodes: Vec<f64>) -> Result<Self, Error> {
+        nodes.sort_by(f64::total_cmp);
+        if nodes.len() < 2 || nodes.windows(2).any(|pair| pair[0] == pair[1]) {
+            return Err(Error::MeshParse("A 1D mesh needs at least two nodes, none of them repeated".to_string()));
+        }
+
+        Ok(Self {
+            location: String::new(),
+            nodes: Some(nodes),
+            base_color: [0.0, 0.0, 1.0],
+            height_multiplier: None,
+            weld_tolerance: DEFAULT_WELD_TOLERANCE,
+        })
+    }
+
+    /// Removes comments (everything after '#') and surrounding whitespace (including the '\r' left by CRLF line endings) from a line of an obj
+    /// and tells which kind of line it is.
+    /// Auxiliar function used inside build methods and constant coordinate check.
+    /// Unknown directives are not treated as errors: build methods skip them and warn once per directive (see `warn_unsupported`).
+    fn obj_line_classifier(line: &str) -> (ObjLine, &str) {
+        let content = match line.find('#') {
+            Some(comment_start) => &line[..comment_start],
+            None => line,
         }
+        .trim();
+
+        let kind = match content.split_whitespace().next() {
+            Some("v") => ObjLine::Vertex,
+            Some("f") => ObjLine::Face,
+            None | Some("vn") | Some("vt") | Some("vp") | Some("o") | Some("g") | Some("s")
+            | Some("mtllib") | Some("usemtl") => ObjLine::Ignored,
+            Some(directive) => ObjLine::Unsupported(directive.to_string()),
+        };
+
+        (kind, content)
     }
 
-    /// Checks wether a line starting with 'v ' in an obj has the three vertices needed.
+    /// Logs a single warning listing every unsupported directive found while reading an obj and how many lines used it.
+    /// Auxiliar function used inside build methods.
+    fn warn_unsupported(unsupported: &BTreeMap<String, usize>) {
+        if let Some(summary) = unsupported_summary(unsupported) {
+            log::warn!("{}", summary);
+        }
+    }
+
+    /// Checks wether a line starting with 'v ' in an obj has the three vertices needed, optionally followed by a color ('v x y z r g b').
+    /// Returns coordinates and color, which defaults to `base_color` when not given.
     /// Auxiliar function used inside build methods.
     /// Part of the checkup made to a given input file.
-    fn obj_vertex_checker<A>(line: &A) -> Result<Vec<f64>, Error>
+    fn obj_vertex_checker<A>(line: &A, base_color: &[f64]) -> Result<(Vec<f64>, Vec<f64>), Error>
     where
         A: AsRef<str>,
     {
-        let mut line_parts = line.as_ref().split(" ");
+        let mut line_parts = line.as_ref().split_whitespace();
         line_parts.next();
-        let line_parts: Vec<f64> = line_parts
+        let mut line_parts: Vec<f64> = line_parts
             .map(|c| -> Result<f64, Error> {
 
                 c.parse::<f64>().map_err(|e| {
@@ -74,13 +265,17 @@ impl MeshBuilder {
             })
             .collect::<Result<Vec<f64>, _>>()?;
 
-        if line_parts.len() != 3 {
-            return Err(Error::MeshParse(
-                "A vertex line should contain 3 elements only".to_string(),
-            ));
-        }
+        let color = match line_parts.len() {
+            3 => base_color.to_vec(),
+            6 => line_parts.split_off(3),
+            _ => {
+                return Err(Error::MeshParse(
+                    "A vertex line should contain 3 coordinates, optionally followed by 3 color components".to_string(),
+                ))
+            }
+        };
 
-        Ok(line_parts)
+        Ok((line_parts, color))
     }
 
     /// Verifies the amount of face specifications per line is 3 and also that all of them have the correct syntax 'a/b/c'.
@@ -92,7 +287,7 @@ impl MeshBuilder {
     {
         let mut triangle_faces = vec![];
 
-        let mut line_parts = line.as_ref().split(" ");
+        let mut line_parts = line.as_ref().split_whitespace();
         line_parts.next();
         let line_parts: Vec<&str> = line_parts.collect();
 
@@ -128,6 +323,70 @@ impl MeshBuilder {
         Ok(triangle_faces)
     }
 
+    /// Verifies every index refers to an existing vertex (every vertex has 6 entries: 3 for coordinates and 3 for color).
+    /// Auxiliar function used inside build methods once the whole file is parsed.
+    /// Part of the checkup made to a given input file.
+    fn obj_index_checker(vertices: &[f64], indices: &[u32]) -> Result<(), Error> {
+        let vertex_count = vertices.len() / 6;
+
+        if let Some(index) = indices.iter().find(|&&index| index as usize >= vertex_count) {
+            return Err(Error::MeshParse(format!(
+                "Face references vertex {} but only {} vertices exist",
+                index + 1,
+                vertex_count
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// # General Information
+    ///
+    /// Indices of a 1D bar made of `nodes` vertices on the line followed by `nodes` vertices above it. Every element [i, i+1] is drawn as
+    /// two triangles: (i, i+1, i+nodes) and (i+1, i+1+nodes, i+nodes).
+    ///
+    /// # Parameters
+    ///
+    /// * `nodes` - Amount of nodes on the line. At least two are needed to have an element.
+    ///
+    fn bar_indices(nodes: u32) -> Result<Vec<u32>, Error> {
+        if nodes < 2 {
+            return Err(Error::MeshParse(format!(
+                "A 1D mesh needs at least two vertices, but {} were found",
+                nodes
+            )));
+        }
+
+        Ok((0..nodes - 1)
+            .flat_map(|i| [i, i + 1, i + nodes, i + 1, i + 1 + nodes, i + nodes])
+            .collect())
+    }
+
+    /// Model matrix that moves the center of the bounding box of every vertex (every vertex has 6 entries: 3 for coordinates and 3 for color)
+    /// to the origin. Used by every build method so that camera framing is the same regardless of dimension.
+    fn centering_matrix(vertices: &[f64]) -> Matrix4<f32> {
+        // Empty meshes are left as they are
+        if vertices.len() < 6 {
+            return Matrix4::from_translation(Vector3::new(0., 0., 0.));
+        }
+
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+
+        for vertex in vertices.chunks_exact(6) {
+            for i in 0..3 {
+                min[i] = min[i].min(vertex[i]);
+                max[i] = max[i].max(vertex[i]);
+            }
+        }
+
+        Matrix4::from_translation(Vector3::new(
+            -((min[0] + max[0]) / 2.) as f32,
+            -((min[1] + max[1]) / 2.) as f32,
+            -((min[2] + max[2]) / 2.) as f32,
+        ))
+    }
+
     /// # General information
     ///
     /// Returns hashmap with every diferent value per coordinate inside .obj.
@@ -149,16 +408,19 @@ impl MeshBuilder {
         // Every line is treated individually
         BufReader::new(file).lines().map(|line| -> Result<(),Error> {
 
-            let coordinates = line?;
+            let line = line?;
+            let (kind, coordinates) = MeshBuilder::obj_line_classifier(&line);
             
-            if coordinates.starts_with("v ") {
-                // splitting via space
-                let mut coordinates_iter = coordinates.split(" ");
+            if kind == ObjLine::Vertex {
+                // splitting via whitespace
+                let mut coordinates_iter = coordinates.split_whitespace();
                 // skip the 'v'
                 coordinates_iter.next();
 
                 // mapping to tuple for HashMap
                 let coordinates_vec: [(String, f32); 3] = coordinates_iter
+                    // Optional color components are not coordinates
+                    .take(3)
                     .map(|c_str| -> Result<(String, f32),Error> {
 
                         // Necessary for -0.0 and 0.0 equality
@@ -191,52 +453,61 @@ impl MeshBuilder {
 
     /// # General Information
     ///
-    /// Builds a one dimensional mesh.
-    /// Only a line of well defined points is needed. The method will create another paralell line to generate a bar
-    /// copying every important element.
-    /// Colors for mesh are inserted into vertices array, therefore, every vertex has 6 entries: 3 for coordinates and 3 for color (RGB),
-    /// **Faces are not needed in .obj for this method**
+    /// Indices (0 for x, 1 for y and 2 for z) of every axis whose coordinate is the same for all vertices inside .obj, in ascending order.
+    /// Two constant axes mean a 1D mesh, one constant axis a 2D mesh and none a 3D mesh.
     ///
     /// # Parameters
     ///
-    /// `self` - Consumes builder.
+    /// * `&self` - Only the file in self is needed to make the verification.
     ///
-    pub fn build_mesh_1d(self, height_multiplier: Option<f64>) -> Result<Mesh, Error> {
-        // Generate every element needed at a functional scope.
-        let binder = Binder::new();
+    pub fn constant_axes(&self) -> Result<Vec<usize>, Error> {
+        let coordinates = self.check_for_constant_coordinates()?;
+
+        Ok(coordinates
+            .iter()
+            .enumerate()
+            .filter_map(|(axis, set)| if set.len() == 1 { Some(axis) } else { None })
+            .collect())
+    }
+
+    /// # General Information
+    ///
+    /// Reads vertices of a 1D mesh from .obj, removing both constant coordinates and ordering them from smaller to bigger.
+    /// Every vertex has 6 entries: its only coordinate followed by two zeros and an initial color (RGB).
+    ///
+    /// # Parameters
+    ///
+    /// `&self` - Builder holding location of .obj.
+    ///
+    fn read_vertices_1d(&self) -> Result<Vec<f64>, Error> {
         let mut vertices: Vec<f64> = vec![];
-        let mut indices: Vec<u32> = vec![];
-        let max_length: f64;
-        let mut middle_point: [f32; 3] = [0.; 3];
+        let base_color = self.base_color_f64();
         let file = File::open(&self.location)?;
 
-        // Obtain hashmaps of coordinates
-        let [set_x, set_y, set_z] = self.check_for_constant_coordinates()?;
-
-        // Obtain constant coordinates
-        let constant_coordinates: [usize; 2] = if set_x.values().count() == 1
-            && set_y.values().count() == 1
-        {
-            [1, 0]
-        } else if set_y.values().count() == 1 && set_z.values().count() == 1 {
-            [2, 1]
-        } else if set_z.values().count() == 1 && set_x.values().count() == 1 {
-            [2, 0]
-        } else {
-            return Err(Error::MeshParse("Only coordinates over a line paralell to x, y or z axis are accepted. Check .obj file.".to_string()));
+        // Obtain constant coordinates (bigger index first so that removing them does not shift the other one)
+        let constant_coordinates: [usize; 2] = match self.constant_axes()?.as_slice() {
+            [first, second, ..] => [*second, *first],
+            _ => return Err(Error::MeshParse("Only coordinates over a line paralell to x, y or z axis are accepted. Check .obj file.".to_string())),
         };
 
         // Obtain ordered vertices
+        let mut unsupported: BTreeMap<String, usize> = BTreeMap::new();
         let reader = BufReader::new(file).lines();
         reader
             .map(|line| -> Result<(), Error> {
                 // Each line we're interested in starts with 'v '
                 match line {
-                    Ok(content) => {
+                    Ok(line) => {
+                        let (kind, content) = MeshBuilder::obj_line_classifier(&line);
+                        if let ObjLine::Unsupported(directive) = kind {
+                            *unsupported.entry(directive).or_insert(0) += 1;
+                            return Ok(());
+                        }
                         // Whenever there is a v
-                        if content.starts_with("v ") {
+                        if kind == ObjLine::Vertex {
                             // Check line integrity
-                            let mut coordinate = MeshBuilder::obj_vertex_checker(&content)?;
+                            // Colors are ignored: vertices are reordered below and 1D colors are given by solution anyway
+                            let (mut coordinate, _color) = MeshBuilder::obj_vertex_checker(&content, &base_color)?;
 
                             // Remove both coordinates. Since they are ordered above, this can be done as below
                             for coord in constant_coordinates {
@@ -249,7 +520,7 @@ impl MeshBuilder {
                             // Adding coordinate
                             vertices.append(&mut coordinate);
                             // Adding initial color
-                            vertices.append(&mut vec![0.0, 0.0, 1.0]);
+                            vertices.extend_from_slice(&base_color);
                             // Insertion sort skipping zero coordinates to order line (smaller to bigger elements) in case .obj is not.
                             let mut j = vertices.len() as i32 - 6 - 5 - 1;
                             while j >= 0 && vertices[j as usize] > new_value {
@@ -265,8 +536,40 @@ impl MeshBuilder {
                 }
             })
             .collect::<Result<Vec<_>, _>>()?;
+        MeshBuilder::warn_unsupported(&unsupported);
+
+        Ok(vertices)
+    }
+
+    /// # General Information
+    ///
+    /// Builds a one dimensional mesh.
+    /// Only a line of well defined points is needed. The method will create another paralell line to generate a bar
+    /// copying every important element.
+    /// Colors for mesh are inserted into vertices array, therefore, every vertex has 6 entries: 3 for coordinates and 3 for color (RGB),
+    /// **Faces are not needed in .obj for this method**
+    ///
+    /// # Parameters
+    ///
+    /// `self` - Consumes builder.
+    ///
+    pub fn build_mesh_1d(self, height_multiplier: Option<f64>) -> Result<Mesh, Error> {
+        // Generate every element needed at a functional scope.
+        let binder = Binder::new();
+        let max_length: f64;
+
+        // Generated meshes already have their nodes, otherwise they're read from .obj
+        let mut vertices: Vec<f64> = match &self.nodes {
+            Some(nodes) => {
+                let [r, g, b] = self.base_color.map(|c| c as f64);
+                nodes.iter().flat_map(|x| [*x, 0.0, 0.0, r, g, b]).collect()
+            },
+            None => self.read_vertices_1d()?,
+        };
 
         let vertices_len: u32 = vertices.len() as u32;
+        // Indices are obtained first so that meshes with less than two vertices are rejected before anything is divided by their amount
+        let indices = MeshBuilder::bar_indices(vertices_len / 6)?;
         // Obtain max_length easily once vertices are ordered
         max_length = -vertices[0] + vertices[vertices_len as usize - 6];
         // Prom width serves to give height to bar
@@ -285,46 +588,23 @@ impl MeshBuilder {
                 .collect::<Vec<f64>>(),
         );
 
-        // Create indices for drawing
-        // First triangle
-        indices.append(&mut vec![0, 1, vertices_len / 6]);
-        // Last triangle
-        indices.append(&mut vec![
-            (vertices_len - 6) / 6,
-            (vertices_len * 2 - 6) / 6,
-            (vertices_len * 2 - 12) / 6,
-        ]);
-        // Every other (intermediate) triangle
-        for i in 1..(vertices_len) / 6 - 1 {
-            indices.append(&mut vec![
-                i,
-                i + vertices_len / 6,
-                i + vertices_len / 6 - 1,
-                i,
-                i + 1,
-                i + vertices_len / 6,
-            ])
-        }
-
-        // get middle point for camera
-        middle_point[0] = vertices[0] as f32 + max_length as f32 / 2_f32;
-        middle_point[1] = prom_width as f32 / 2_f32;
-
-        // Translate matrix to given point
-        let model_matrix = Matrix4::from_translation(Vector3::new(
-            -middle_point[0] as f32,
-            -middle_point[1] as f32,
-            middle_point[2] as f32,
-        ));
+        // Translate bar's center to origin
+        let model_matrix = MeshBuilder::centering_matrix(&vertices);
 
-        Ok(Mesh {
+        let mesh = Mesh {
+            dimension: MeshDimension::One,
             vertices: Array1::from_vec(vertices),
             indices: Array1::from_vec(indices),
             boundary_indices: None,
             max_length,
             model_matrix,
             binder,
-        })
+            spatial_index: None,
+            highlight: None,
+        };
+        log::info!("Built {}", mesh.stats());
+
+        Ok(mesh)
     }
 
     /// # General Information
@@ -343,23 +623,15 @@ impl MeshBuilder {
         // Generate every element needed at a functional scope.
         let binder = Binder::new();
         let mut vertices: Vec<f64> = vec![];
+        let base_color = self.base_color_f64();
         let mut indices: Vec<u32> = vec![];
         let max_length: f64;
-        let mut middle_point: [f32; 3] = [0.; 3];
         let file = File::open(&self.location)?;
 
-        // Obtain hashmaps of every coordinate with only different coordinates' value.
-        let [set_x, set_y, set_z] = self.check_for_constant_coordinates()?;
-
         // Get constant coordinate
-        let constant_coordinate: usize = if set_x.values().count() == 1 {
-            0
-        } else if set_y.values().count() == 1 {
-            1
-        } else if set_z.values().count() == 1 {
-            2
-        } else {
-            return Err(Error::MeshParse("Only coordinates over a plane paralell to x, y or z plane are accepted. Check .obj file.".to_string()));
+        let constant_coordinate: usize = match self.constant_axes()?.first() {
+            Some(axis) => *axis,
+            None => return Err(Error::MeshParse("Only coordinates over a plane paralell to x, y or z plane are accepted. Check .obj file.".to_string())),
         };
 
         // Generate maximum and minimum value hashmap for x and y to encapsulate mesh in a square (for proper viewing purposes).
@@ -370,20 +642,23 @@ impl MeshBuilder {
             ("y_max", 0.0),
         ]);
 
-        // Primary data structure for boundary vertices algorithm (first we work with edges in the form (a,b))
-        let mut boundary_edges: HashMap<[u32; 2], usize> = HashMap::new();
-
+        let mut unsupported: BTreeMap<String, usize> = BTreeMap::new();
         let reader = BufReader::new(file).lines();
         reader
             .map(|line| -> Result<(), Error> {
                 // Each line we're interested in is either a 'v ' or an 'f '
-                let content = line?;
+                let line = line?;
+                let (kind, content) = MeshBuilder::obj_line_classifier(&line);
+                if let ObjLine::Unsupported(directive) = kind {
+                    *unsupported.entry(directive).or_insert(0) += 1;
+                    return Ok(());
+                }
                 
                 // Whenever there is a v
-                if content.starts_with("v ") {
+                if kind == ObjLine::Vertex {
 
                     // Check line integrity
-                    let mut coordinate = MeshBuilder::obj_vertex_checker(&content)?;
+                    let (mut coordinate, mut color) = MeshBuilder::obj_vertex_checker(&content, &base_color)?;
 
                     // Remotion of the constant coordinate
                     coordinate.remove(constant_coordinate);
@@ -408,53 +683,23 @@ impl MeshBuilder {
                     }
 
                     vertices.append(&mut coordinate);
-                    // Adding initial color: blue
-                    vertices.append(&mut vec![0.0, 0.0, 1.0]);
+                    // Adding initial color: from file or blue
+                    vertices.append(&mut color);
                 }
                 // Whenever there is an f
-                else if content.starts_with("f ") {
-                    // Splitting via single space
+                else if kind == ObjLine::Face {
+                    // Splitting via whitespace
                     let mut triangle = MeshBuilder::obj_face_checker(&content)?;
 
-                    // filling boundary edges hashmap to obtain boundary vertices
-                    // three possible combinations. Find better way to insert them
-                    if let Some(counter) =
-                        boundary_edges.get_mut(&[triangle[0], triangle[1]])
-                    {
-                        *counter += 1;
-                    } else if let Some(counter) = boundary_edges.get_mut(&[triangle[1],triangle[0]]) {
-                        
-                        *counter += 1;
-                    } else {
-                        boundary_edges.insert([triangle[0], triangle[1]], 1);
-                    }
-                    if let Some(counter) =
-                        boundary_edges.get_mut(&[triangle[0], triangle[2]])
-                    {
-                        *counter += 1;
-                    } else if let Some(counter) = boundary_edges.get_mut(&[triangle[2],triangle[0]]) {
-
-                        *counter += 1;
-                    } else {
-                        boundary_edges.insert([triangle[0], triangle[2]], 1);
-                    }
-                    if let Some(counter) =
-                        boundary_edges.get_mut(&[triangle[2], triangle[1]])
-                    {
-                        *counter += 1;
-                    } else if let Some(counter) = boundary_edges.get_mut(&[triangle[1],triangle[2]]) {
-
-                        *counter += 1;
-                    }else {
-                        boundary_edges.insert([triangle[2], triangle[1]], 1);
-                    }
-
                     // Push into triangles vector of u32
                     indices.append(&mut triangle);
                 }
                 Ok(())
             })
             .collect::<Result<Vec<_>, _>>()?;
+        MeshBuilder::warn_unsupported(&unsupported);
+
+        MeshBuilder::obj_index_checker(&vertices, &indices)?;
 
         // Obtaining max and min from hashmap
         let x_min = max_min.get("x_min").ok_or(Error::Infallible)?;
@@ -465,38 +710,83 @@ impl MeshBuilder {
         // Finally obtaining max length
         max_length = if len_x > len_y { len_x } else { len_y };
 
-        // Obtaining middle point
-        middle_point[0] = *x_min as f32 + max_length as f32 / 2.0;
-        middle_point[1] = *y_min as f32 + max_length as f32 / 2.0;
+        // Coincident vertices share an id, so that an edge between two triangles is recognized even when each one uses its own copy of vertices
+        let welded = MeshBuilder::weld_vertices(&vertices, self.weld_tolerance);
 
-        // reducing boundary edges to vertices with a filter based on wether they are at the boundary or not.
-        let boundary_indices: Vec<u32> = HashSet::<u32>::from_iter(
-            boundary_edges
-                .into_iter()
-                .filter(|(_duple, counter)| if *counter != 1 { false } else { true })
-                .collect::<HashMap<[u32; 2], usize>>()
-                .into_keys()
-                .flatten(),
-        )
-        .into_iter().collect();
+        // Counting how many triangles share every edge (in the form (a,b) with a <= b)
+        let mut boundary_edges: HashMap<[u32; 2], usize> = HashMap::new();
+        for triangle in indices.chunks_exact(3) {
+            for (start, end) in [(0, 1), (1, 2), (2, 0)] {
+                let (a, b) = (welded[triangle[start] as usize], welded[triangle[end] as usize]);
+                *boundary_edges.entry([a.min(b), a.max(b)]).or_insert(0) += 1;
+            }
+        }
 
-        let boundary_indices = merge_sort(boundary_indices)?;
+        // An edge adjacent to a single triangle is at the boundary, and so is every copy of its vertices
+        let boundary_welded: HashSet<u32> = boundary_edges
+            .into_iter()
+            .filter(|(_edge, counter)| *counter == 1)
+            .flat_map(|(edge, _counter)| edge)
+            .collect();
+        let boundary_indices: Vec<u32> = (0..welded.len() as u32)
+            .filter(|id| boundary_welded.contains(&welded[*id as usize]))
+            .collect();
 
         // Model matrix for viewing purposes
-        let model_matrix = Matrix4::from_translation(Vector3::new(
-            middle_point[0] as f32,
-            middle_point[1] as f32,
-            middle_point[2] as f32,
-        ));
+        let model_matrix = MeshBuilder::centering_matrix(&vertices);
 
-        Ok(Mesh {
+        let mesh = Mesh {
+            dimension: MeshDimension::Two,
             vertices: Array1::from_vec(vertices),
             indices: Array1::from_vec(indices),
             boundary_indices: Some(boundary_indices),
             max_length,
             model_matrix,
             binder,
-        })
+            spatial_index: None,
+            highlight: None,
+        };
+        log::info!("Built {}", mesh.stats());
+
+        Ok(mesh)
+    }
+
+    /// # General Information
+    ///
+    /// Id of every vertex once coincident ones are welded: vertices closer than `tolerance` to an earlier one receive its id, the rest keep their own.
+    /// Vertices are bucketed in a grid of cells as big as `tolerance`, so only neighbouring cells are compared.
+    ///
+    /// # Parameters
+    ///
+    /// * `vertices` - Vertices in sextuples (coordinate and color).
+    /// * `tolerance` - Maximum distance between welded vertices. Should be positive.
+    ///
+    fn weld_vertices(vertices: &[f64], tolerance: f64) -> Vec<u32> {
+        let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+        let mut welded: Vec<u32> = Vec::with_capacity(vertices.len() / 6);
+
+        for (id, vertex) in vertices.chunks_exact(6).enumerate() {
+            let cell = [0, 1, 2].map(|axis| (vertex[axis] / tolerance).floor() as i64);
+            let distance = |other: u32| -> f64 {
+                let other = &vertices[6 * other as usize..6 * other as usize + 3];
+                (0..3).map(|axis| (vertex[axis] - other[axis]).powi(2)).sum::<f64>().sqrt()
+            };
+
+            let mut neighbours = (-1..=1).flat_map(|i| (-1..=1).flat_map(move |j| (-1..=1).map(move |k| [cell[0] + i, cell[1] + j, cell[2] + k])));
+            let existing = neighbours.find_map(|neighbour| {
+                cells.get(&neighbour).and_then(|ids| ids.iter().copied().find(|other| distance(*other) <= tolerance))
+            });
+
+            match existing {
+                Some(other) => welded.push(other),
+                None => {
+                    cells.entry(cell).or_default().push(id as u32);
+                    welded.push(id as u32);
+                }
+            }
+        }
+
+        welded
     }
 
     /// # General Information
@@ -512,9 +802,9 @@ impl MeshBuilder {
     pub fn build_mesh_3d(self) -> Result<Mesh, Error> {
         let binder = Binder::new();
         let mut vertices: Vec<f64> = vec![];
+        let base_color = self.base_color_f64();
         let mut indices: Vec<u32> = vec![];
         let max_length: f64;
-        let mut middle_point: [f32; 3] = [0.; 3];
         let file = File::open(&self.location)?;
 
         let mut max_min = HashMap::from([
@@ -526,16 +816,22 @@ impl MeshBuilder {
             ("z_max", 0.0),
         ]);
 
+        let mut unsupported: BTreeMap<String, usize> = BTreeMap::new();
         let reader = BufReader::new(file).lines();
         reader
             .map(|line| -> Result<(), Error> {
                 // Each line we're interested in is either a 'v ' or an 'f '
                 
-                let content = line?;
+                let line = line?;
+                let (kind, content) = MeshBuilder::obj_line_classifier(&line);
+                if let ObjLine::Unsupported(directive) = kind {
+                    *unsupported.entry(directive).or_insert(0) += 1;
+                    return Ok(());
+                }
                 // Whenever there is a v
-                if content.starts_with("v ") {
+                if kind == ObjLine::Vertex {
                     // Check line integrity
-                    let mut coordinate = MeshBuilder::obj_vertex_checker(&content)?;
+                    let (mut coordinate, mut color) = MeshBuilder::obj_vertex_checker(&content, &base_color)?;
 
                     // Check for min and max
                     let x_min = max_min.get_mut("x_min").ok_or(Error::Infallible)?;
@@ -564,11 +860,11 @@ impl MeshBuilder {
                     }
 
                     vertices.append(&mut coordinate);
-                    vertices.append(&mut vec![0.0, 0.0, 1.0]);
+                    vertices.append(&mut color);
                 }
                 // Whenever there is an f
-                else if content.starts_with("f ") {
-                    // Splitting via single space
+                else if kind == ObjLine::Face {
+                    // Splitting via whitespace
                     let mut triangle = MeshBuilder::obj_face_checker(&content)?;
                     // Push into triangles vector of u32
                     indices.append(&mut triangle);
@@ -579,6 +875,9 @@ impl MeshBuilder {
                 Ok(())
             })
             .collect::<Result<Vec<_>, _>>()?;
+        MeshBuilder::warn_unsupported(&unsupported);
+
+        MeshBuilder::obj_index_checker(&vertices, &indices)?;
 
         let x_min = max_min.get("x_min").ok_or(Error::Infallible)?;
         let y_min = max_min.get("y_min").ok_or(Error::Infallible)?;
@@ -587,8 +886,6 @@ impl MeshBuilder {
         let len_y = max_min.get("y_max").ok_or(Error::Infallible)? - y_min;
         let len_z = max_min.get("z_max").ok_or(Error::Infallible)? - z_min;
 
-        log::info!("{:?}",middle_point);
-
         max_length = if len_x >= len_y && len_x >= len_z {
             len_x
         } else if len_y >= len_x && len_y >= len_z {
@@ -597,85 +894,54 @@ impl MeshBuilder {
             len_z
         };
 
-        middle_point[0] = *x_min as f32 + (max_length as f32 / 2.0);
-        middle_point[1] = *y_min as f32 + (max_length as f32 / 2.0);
-        middle_point[2] = *z_min as f32 + (max_length as f32 / 2.0);
-
-        // Translate matrix to given point
-        let model_matrix = Matrix4::from_translation(Vector3::new(
-            middle_point[0] as f32,
-            middle_point[1] as f32,
-            middle_point[2] as f32,
-        ));
+        // Translate mesh's center to origin
+        let model_matrix = MeshBuilder::centering_matrix(&vertices);
 
-        Ok(Mesh {
+        let mesh = Mesh {
+            dimension: MeshDimension::Three,
             vertices: Array1::from_vec(vertices),
             indices: Array1::from_vec(indices),
             boundary_indices: None,
             max_length,
             model_matrix,
             binder,
-        })
-    }
-}
-
+            spatial_index: None,
+            highlight: None,
+        };
+        log::info!("Built {}", mesh.stats());
 
-fn merge_sort(mut vec_arr: Vec<u32>) -> Result<Vec<u32>,Error> {
-    
-    if vec_arr.len() == 1 {
-        return Ok(vec_arr);
+        Ok(mesh)
     }
-    let r = vec_arr.len();
-    let l: usize = 0;
-    let mid = r / 2;
-
-    let mut left_array = Vec::new();
-    let mut right_array = Vec::new();
-    vec_arr[l..mid].clone_into(&mut left_array);
-    vec_arr[mid..=r-1].clone_into(&mut right_array);
-    
-    let left_array = merge_sort(left_array)?;
-    let right_array = merge_sort(right_array)?;
-
-    
-    merge(&mut vec_arr, left_array, right_array)?;
-
-    Ok(vec_arr)
 }
 
-fn merge(arr: &mut Vec<u32>, left: Vec<u32>, right: Vec<u32>) -> Result<(),Error> {
-
-    let mut i_left: usize = 0;
-    let mut i_right: usize = 0;
-    let mut k: usize = 0;
-
-    while i_left < left.len() && i_right < right.len() {
-        
-        if left[i_left] <= right[i_right] {
-            arr[k] = left[i_left];
-            i_left += 1
-        } else {
-            arr[k] = right[i_right];
-            i_right += 1
-        }
-
-        k += 1
-
+/// Summary of unsupported directives in the form "Skipped unsupported obj directives: 'l' (2 lines), 'p' (1 line)". None if there were none.
+fn unsupported_summary(unsupported: &BTreeMap<String, usize>) -> Option<String> {
+    if unsupported.is_empty() {
+        return None;
     }
+    let directives: Vec<String> = unsupported
+        .iter()
+        .map(|(directive, lines)| format!("'{}' ({} line{})", directive, lines, if *lines == 1 { "" } else { "s" }))
+        .collect();
 
-    while i_left < left.len() {
-        arr[k] = left[i_left];
-        i_left += 1;
-        k += 1;
-    }
+    Some(format!("Skipped unsupported obj directives: {}", directives.join(", ")))
+}
 
-    while i_right < right.len() {
-        arr[k] = right[i_right];
-        i_right += 1;
-        k += 1;
+#[cfg(test)]
+mod test {
+    use super::{unsupported_summary, MeshBuilder, ObjLine};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn unsupported_directives_are_summarized_once() {
+        let mut unsupported: BTreeMap<String, usize> = BTreeMap::new();
+        for line in ["l 1 2", "vn 0 0 1", "p 3", "l 2 3 # comment", "v 0 0 0"] {
+            if let (ObjLine::Unsupported(directive), _) = MeshBuilder::obj_line_classifier(line) {
+                *unsupported.entry(directive).or_insert(0) += 1;
+            }
+        }
 
+        assert!(unsupported_summary(&unsupported) == Some("Skipped unsupported obj directives: 'l' (2 lines), 'p' (1 line)".to_string()));
+        assert!(unsupported_summary(&BTreeMap::new()).is_none());
     }
-
-    Ok(())
-
-}
\ No newline at end of file
+}
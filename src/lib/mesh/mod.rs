@@ -1,17 +1,29 @@
 // Module declaration
 pub(crate) mod mesh_builder;
+pub(crate) mod spatial_index;
 
 // External dependencies
-use cgmath::Matrix4;
+use cgmath::{InnerSpace, Matrix4, Point3, Transform, Vector3};
 use ndarray::Array1;
 use num::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
 // Internal dependencies
 use crate::{
-    simulation::drawable::binder::{Binder, Bindable, Drawable},
+    solvers::{
+        basis::functions::Function1D,
+        fem::{basis::single_variable::linear_basis::LinearBasis, diffusion_solver::element_gradients},
+        from_scalar, to_scalar,
+    },
+    simulation::drawable::{
+        binder::{Binder, Bindable, Drawable},
+        color_bar::gradient_color,
+    },
     Error,
 };
-use mesh_builder::MeshBuilder;
+use mesh_builder::{MeshBuilder, MeshDimension};
+use spatial_index::SpatialIndex;
 
 /// # General Information
 ///
@@ -19,21 +31,117 @@ use mesh_builder::MeshBuilder;
 ///
 /// # Fields
 ///
+/// * `dimension` - Dimension of mesh (which build method created it).
 /// * `max_length` - Maximum length of figure. Used to center camera arround objective.
 /// * `model_matrix` - Translates and rotates object to final world position.
 /// * `binder` - vao, vbo and ebo variables bound to mesh drawable in GPU.
 /// * `indices` - Indices that map to vertices. Normally used in triads. Specified in gl configuration.
 /// * `vertices` -  Vertices in 3d space. Normally used in sextuples (coordinate and color). Specified in gl configuration.
+/// * `spatial_index` - Grid over vertex positions used for picking. Built on first use.
+/// * `highlight` - Vertex drawn with a highlight color (normally the one picked last), along with the color it would otherwise have.
 ///
 #[allow(dead_code)]
 #[derive(Debug)]
-pub(crate) struct Mesh {
+pub struct Mesh {
+    pub(crate) dimension: MeshDimension,
     pub(crate) max_length: f64,
     pub(crate) model_matrix: Matrix4<f32>,
     pub(crate) boundary_indices: Option<Vec<u32>>,
     binder: Binder,
     pub(crate) indices: Array1<u32>,
     pub(crate) vertices: Array1<f64>,
+    spatial_index: Option<SpatialIndex>,
+    highlight: Option<Highlight>,
+}
+
+/// # General Information
+///
+/// A vertex drawn with a color different to the one of the solution, to show it has been selected.
+///
+/// # Fields
+///
+/// * `vertex` - Index of vertex (position of its sextuple).
+/// * `color` - Highlight color (RGB).
+/// * `previous_color` - Color vertex would have without highlight. Restored when highlight moves elsewhere.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Highlight {
+    vertex: usize,
+    color: [f64; 3],
+    previous_color: [f64; 3],
+}
+
+/// # General Information
+///
+/// Shape quality of triangles of a 2D mesh. Poorly-shaped triangles degrade accuracy and conditioning of FEM.
+///
+/// # Fields
+///
+/// * `min_aspect_ratio` - Smallest aspect ratio. Aspect ratio is circumradius over twice the inradius: 1 for an equilateral triangle, growing without bound for slivers.
+/// * `max_aspect_ratio` - Largest aspect ratio.
+/// * `mean_aspect_ratio` - Mean aspect ratio over every triangle.
+/// * `min_angle` - Smallest interior angle of any triangle, in degrees.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QualityReport {
+    pub min_aspect_ratio: f64,
+    pub max_aspect_ratio: f64,
+    pub mean_aspect_ratio: f64,
+    pub min_angle: f64,
+}
+
+/// # General Information
+///
+/// Quick summary of a freshly built mesh, logged so that users can confirm a file was parsed as expected.
+///
+/// # Fields
+///
+/// * `dimension` - Dimension mesh was built with.
+/// * `vertex_count` - Amount of vertices read. For 1D meshes these are the nodes of the line, not the duplicated ones used to draw a bar.
+/// * `triangle_count` - Amount of triangles drawn.
+/// * `min` - Smallest coordinates of any vertex (before model matrix is applied).
+/// * `max` - Biggest coordinates of any vertex (before model matrix is applied).
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct MeshStats {
+    pub(crate) dimension: MeshDimension,
+    pub(crate) vertex_count: usize,
+    pub(crate) triangle_count: usize,
+    pub(crate) min: [f64; 3],
+    pub(crate) max: [f64; 3],
+}
+
+/// # General Information
+///
+/// Everything needed to build a mesh again without reading its file, as written to simulation checkpoints. GPU buffers are created anew.
+///
+/// # Fields
+///
+/// * `dimension` - Dimension of mesh.
+/// * `max_length` - Maximum length of figure.
+/// * `model_matrix` - Model matrix, by columns.
+/// * `boundary_indices` - Indices of boundary vertices, when known.
+/// * `indices` - Indices that map to vertices.
+/// * `vertices` - Vertices in sextuples (coordinate and color). Highlighted vertices keep the color they would otherwise have.
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct MeshSnapshot {
+    pub(crate) dimension: MeshDimension,
+    pub(crate) max_length: f64,
+    pub(crate) model_matrix: [[f32; 4]; 4],
+    pub(crate) boundary_indices: Option<Vec<u32>>,
+    pub(crate) indices: Vec<u32>,
+    pub(crate) vertices: Vec<f64>,
+}
+
+impl std::fmt::Display for MeshStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} mesh with {} vertices and {} triangles, bounded by {:?} and {:?}",
+            self.dimension, self.vertex_count, self.triangle_count, self.min, self.max
+        )
+    }
 }
 
 impl Mesh {
@@ -50,6 +158,642 @@ impl Mesh {
         MeshBuilder::new(location)
     }
 
+    /// Vertex and triangle counts, bounding box and dimension of mesh. Only the nodes of 1D meshes are counted and bounded.
+    pub(crate) fn stats(&self) -> MeshStats {
+        let vertices = match self.dimension {
+            MeshDimension::One => self.vertices.slice(ndarray::s![..self.vertices.len() / 2]),
+            _ => self.vertices.view(),
+        };
+
+        let mut min = [f64::INFINITY; 3];
+        let mut max = [f64::NEG_INFINITY; 3];
+        for vertex in vertices.exact_chunks(6) {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(vertex[axis]);
+                max[axis] = max[axis].max(vertex[axis]);
+            }
+        }
+
+        MeshStats {
+            dimension: self.dimension,
+            vertex_count: vertices.len() / 6,
+            triangle_count: self.indices.len() / 3,
+            min,
+            max,
+        }
+    }
+
+    /// Copy of mesh that can be written to a file. Highlight is not kept.
+    pub(crate) fn snapshot(&self) -> MeshSnapshot {
+        let mut vertices = self.vertices.to_vec();
+        if let Some(highlight) = &self.highlight {
+            vertices[6 * highlight.vertex + 3..6 * highlight.vertex + 6].copy_from_slice(&highlight.previous_color);
+        }
+
+        MeshSnapshot {
+            dimension: self.dimension,
+            max_length: self.max_length,
+            model_matrix: self.model_matrix.into(),
+            boundary_indices: self.boundary_indices.clone(),
+            indices: self.indices.to_vec(),
+            vertices,
+        }
+    }
+
+    /// Mesh equal to the one a snapshot was taken from. Vertices need to be sent to GPU again.
+    pub(crate) fn from_snapshot(snapshot: MeshSnapshot) -> Self {
+        Mesh {
+            dimension: snapshot.dimension,
+            max_length: snapshot.max_length,
+            model_matrix: snapshot.model_matrix.into(),
+            boundary_indices: snapshot.boundary_indices,
+            binder: Binder::new(),
+            indices: Array1::from_vec(snapshot.indices),
+            vertices: Array1::from_vec(snapshot.vertices),
+            spatial_index: None,
+            highlight: None,
+        }
+    }
+
+    /// Opposite corners (smallest and biggest coordinates) of box containing every vertex once model matrix is applied.
+    pub(crate) fn world_bounds(&self) -> (Point3<f32>, Point3<f32>) {
+        let mut min = Point3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = Point3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for vertex in self.vertices.exact_chunks(6) {
+            let point = self.model_matrix.transform_point(Point3::new(vertex[0] as f32, vertex[1] as f32, vertex[2] as f32));
+            min = Point3::new(min.x.min(point.x), min.y.min(point.y), min.z.min(point.z));
+            max = Point3::new(max.x.max(point.x), max.y.max(point.y), max.z.max(point.z));
+        }
+
+        (min, max)
+    }
+
+    /// # General Information
+    ///
+    /// Spatial index over vertex positions, built the first time it's asked for. Vertex positions never change after creation (only colors do), so it's never rebuilt.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Mesh whose vertices are indexed
+    /// * `points_per_cell` - Desired amount of vertices per cell. Only used when index is built
+    ///
+    pub(crate) fn spatial_index(&mut self, points_per_cell: usize) -> &SpatialIndex {
+        self.spatial_index.get_or_insert_with(|| SpatialIndex::new(&self.vertices, points_per_cell))
+    }
+
+    /// Color (last three entries of its sextuple) of a vertex.
+    fn vertex_color(&self, vertex: usize) -> [f64; 3] {
+        [self.vertices[6 * vertex + 3], self.vertices[6 * vertex + 4], self.vertices[6 * vertex + 5]]
+    }
+
+    /// Changes color (last three entries of its sextuple) of a vertex.
+    fn set_vertex_color(&mut self, vertex: usize, color: [f64; 3]) {
+        for (offset, channel) in color.iter().enumerate() {
+            self.vertices[6 * vertex + 3 + offset] = *channel;
+        }
+    }
+
+    /// Colors of every vertex. Highlighted vertex gives the color it would have without highlight.
+    pub(crate) fn colors(&self) -> Vec<[f64; 3]> {
+        let mut colors: Vec<[f64; 3]> = (0..self.vertices.len() / 6).map(|vertex| self.vertex_color(vertex)).collect();
+        if let Some(highlight) = &self.highlight {
+            colors[highlight.vertex] = highlight.previous_color;
+        }
+        colors
+    }
+
+    /// Changes color of every vertex, as obtained with `colors`. Highlight is kept. Errors when there's not a color per vertex.
+    pub(crate) fn set_colors(&mut self, colors: &[[f64; 3]]) -> Result<(), Error> {
+        if 6 * colors.len() != self.vertices.len() {
+            return Err(Error::WrongDims);
+        }
+
+        for (vertex, color) in colors.iter().enumerate() {
+            self.set_vertex_color(vertex, *color);
+        }
+        self.keep_highlight();
+
+        Ok(())
+    }
+
+    /// # General Information
+    ///
+    /// Colors vertices on the boundary (as found when mesh was built) with one color and every other vertex with another, to check boundary detection.
+    /// Highlight is kept.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Mesh whose vertices are colored
+    /// * `boundary_color` - Color (RGB) of boundary vertices
+    /// * `interior_color` - Color (RGB) of interior vertices
+    ///
+    pub(crate) fn color_boundary(&mut self, boundary_color: [f64; 3], interior_color: [f64; 3]) -> Result<(), Error> {
+        let boundary: HashSet<usize> = match &self.boundary_indices {
+            Some(boundary_indices) => boundary_indices.iter().map(|index| *index as usize).collect(),
+            None => return Err(Error::BoundaryError("Mesh has no boundary indices".to_string())),
+        };
+
+        for vertex in 0..self.vertices.len() / 6 {
+            let color = if boundary.contains(&vertex) { boundary_color } else { interior_color };
+            self.set_vertex_color(vertex, color);
+        }
+        self.keep_highlight();
+
+        Ok(())
+    }
+
+    /// Colors just written become the ones highlighted vertex would have without highlight, and highlight color is drawn again over them.
+    fn keep_highlight(&mut self) {
+        if let Some(highlight) = self.highlight {
+            self.highlight = Some(Highlight {
+                previous_color: self.vertex_color(highlight.vertex),
+                ..highlight
+            });
+            self.set_vertex_color(highlight.vertex, highlight.color);
+        }
+    }
+
+    /// # General Information
+    ///
+    /// Draws a vertex with a highlight color. Vertex highlighted before gets its color back. Highlight is kept when solution colors are updated.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Mesh whose vertex is highlighted
+    /// * `vertex` - Index of vertex (position of its sextuple). None only removes current highlight
+    /// * `color` - Highlight color (RGB)
+    ///
+    pub(crate) fn highlight_vertex(&mut self, vertex: Option<usize>, color: [f64; 3]) -> Result<(), Error> {
+        if let Some(vertex) = vertex {
+            if 6 * vertex + 6 > self.vertices.len() {
+                return Err(Error::WrongDims);
+            }
+        }
+
+        if let Some(previous) = self.highlight.take() {
+            self.set_vertex_color(previous.vertex, previous.previous_color);
+        }
+
+        if let Some(vertex) = vertex {
+            self.highlight = Some(Highlight {
+                vertex,
+                color,
+                previous_color: self.vertex_color(vertex),
+            });
+            self.set_vertex_color(vertex, color);
+        }
+
+        Ok(())
+    }
+
+    /// # General Information
+    ///
+    /// Coordinates of every node as a point, without colors. Useful to hand mesh to other libraries.
+    /// In 1D, the extra vertices generated to draw the bar are left out, so there's one point per node of the line.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Mesh to read nodes from
+    ///
+    pub fn node_coordinates(&self) -> Vec<[f64; 3]> {
+        let points = self.vertices.iter().copied().collect::<Vec<f64>>();
+        let nodes = match self.dimension {
+            MeshDimension::One => points.len() / 12,
+            _ => points.len() / 6,
+        };
+
+        points.chunks_exact(6).take(nodes).map(|vertex| [vertex[0], vertex[1], vertex[2]]).collect()
+    }
+
+    /// Coordinates of a vertex (first three entries of its sextuple).
+    fn position(&self, index: u32) -> Result<Vector3<f64>, Error> {
+        let start = 6 * index as usize;
+        if start + 3 > self.vertices.len() {
+            return Err(Error::WrongDims);
+        }
+        Ok(Vector3::new(self.vertices[start], self.vertices[start + 1], self.vertices[start + 2]))
+    }
+
+    /// # General Information
+    ///
+    /// Measure of mesh: total length in 1D (sum of intervals), total area in 2D (sum of triangle areas) and enclosed volume in 3D.
+    /// Volume is obtained as the sum of signed volumes of tetrahedra formed by every face and the origin, which only makes sense for
+    /// closed surfaces with consistently oriented faces; therefore, an error is returned whenever an edge is not shared by exactly two faces.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Mesh to measure
+    ///
+    pub fn measure(&self) -> Result<f64, Error> {
+        match self.dimension {
+            MeshDimension::One => {
+                let nodes = self.filter_for_solving_1d();
+                Ok(nodes.windows(2).into_iter().map(|interval| (interval[1] - interval[0]).abs()).sum())
+            }
+            MeshDimension::Two => self
+                .indices
+                .exact_chunks(3)
+                .into_iter()
+                .map(|triangle| -> Result<f64, Error> {
+                    let a = self.position(triangle[0])?;
+                    let b = self.position(triangle[1])?;
+                    let c = self.position(triangle[2])?;
+                    Ok((b - a).cross(c - a).magnitude() / 2.0)
+                })
+                .sum(),
+            MeshDimension::Three => {
+                // Every edge of a closed surface belongs to exactly two faces
+                let mut edges: HashMap<[u32; 2], usize> = HashMap::new();
+                for triangle in self.indices.exact_chunks(3) {
+                    for (start, end) in [(0, 1), (1, 2), (2, 0)] {
+                        let edge = [triangle[start].min(triangle[end]), triangle[start].max(triangle[end])];
+                        *edges.entry(edge).or_insert(0) += 1;
+                    }
+                }
+                if edges.is_empty() || edges.values().any(|count| *count != 2) {
+                    return Err(Error::custom("Volume can only be measured on meshes whose faces form a closed surface"));
+                }
+
+                let signed_volume: f64 = self
+                    .indices
+                    .exact_chunks(3)
+                    .into_iter()
+                    .map(|triangle| -> Result<f64, Error> {
+                        let a = self.position(triangle[0])?;
+                        let b = self.position(triangle[1])?;
+                        let c = self.position(triangle[2])?;
+                        Ok(a.dot(b.cross(c)) / 6.0)
+                    })
+                    .sum::<Result<f64, Error>>()?;
+
+                Ok(signed_volume.abs())
+            }
+        }
+    }
+
+    /// # General Information
+    ///
+    /// Size of every element: length of every interval in 1D and, otherwise, longest edge of every triangle (its diameter).
+    /// Smallest and biggest sizes bound stable time steps of explicit schemes (CFL-like conditions).
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Mesh to inspect
+    ///
+    pub fn element_sizes(&self) -> Result<Vec<f64>, Error> {
+        match self.dimension {
+            MeshDimension::One => Ok(self.filter_for_solving_1d().windows(2).into_iter().map(|interval| (interval[1] - interval[0]).abs()).collect()),
+            _ => self
+                .indices
+                .exact_chunks(3)
+                .into_iter()
+                .map(|triangle| -> Result<f64, Error> {
+                    let a = self.position(triangle[0])?;
+                    let b = self.position(triangle[1])?;
+                    let c = self.position(triangle[2])?;
+                    Ok((b - a).magnitude().max((c - b).magnitude()).max((a - c).magnitude()))
+                })
+                .collect(),
+        }
+    }
+
+    /// Smallest element size (see `element_sizes`). Errors on meshes without elements.
+    pub fn min_element_size(&self) -> Result<f64, Error> {
+        self.element_sizes()?.into_iter().reduce(f64::min).ok_or_else(|| Error::custom("Mesh has no elements"))
+    }
+
+    /// Biggest element size (see `element_sizes`). Errors on meshes without elements.
+    pub fn max_element_size(&self) -> Result<f64, Error> {
+        self.element_sizes()?.into_iter().reduce(f64::max).ok_or_else(|| Error::custom("Mesh has no elements"))
+    }
+
+    /// # General Information
+    ///
+    /// Aspect ratios (min, max and mean) and minimum angle over every triangle of a 2D mesh. Helps decide wether a mesh should be remeshed.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Mesh to inspect. Should be 2D and have at least one triangle.
+    ///
+    pub fn quality_report_2d(&self) -> Result<QualityReport, Error> {
+        if self.dimension != MeshDimension::Two || self.indices.is_empty() {
+            return Err(Error::custom("Quality report is only available for 2D meshes with triangles"));
+        }
+
+        let mut report = QualityReport {
+            min_aspect_ratio: f64::INFINITY,
+            max_aspect_ratio: 0.0,
+            mean_aspect_ratio: 0.0,
+            min_angle: 180.0,
+        };
+
+        for triangle in self.indices.exact_chunks(3) {
+            let a = self.position(triangle[0])?;
+            let b = self.position(triangle[1])?;
+            let c = self.position(triangle[2])?;
+
+            let lengths = [(b - c).magnitude(), (c - a).magnitude(), (a - b).magnitude()];
+            let area = (b - a).cross(c - a).magnitude() / 2.0;
+            let semiperimeter = lengths.iter().sum::<f64>() / 2.0;
+
+            // R / 2r = (abc / 4A) / (2A / s)
+            let aspect_ratio = lengths.iter().product::<f64>() * semiperimeter / (8.0 * area * area);
+
+            for (vertex, others) in [(a, (b, c)), (b, (c, a)), (c, (a, b))] {
+                let angle = (others.0 - vertex).angle(others.1 - vertex).0.to_degrees();
+                report.min_angle = report.min_angle.min(angle);
+            }
+
+            report.min_aspect_ratio = report.min_aspect_ratio.min(aspect_ratio);
+            report.max_aspect_ratio = report.max_aspect_ratio.max(aspect_ratio);
+            report.mean_aspect_ratio += aspect_ratio;
+        }
+        report.mean_aspect_ratio /= (self.indices.len() / 3) as f64;
+
+        Ok(report)
+    }
+
+    /// # General Information
+    ///
+    /// Carries a solution of a coarser 1D mesh over to the nodes of this one. Every node is located inside an element of the coarse mesh
+    /// and the solution is evaluated there through the linear basis, that is, it's linearly interpolated between the element's nodal values.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Mesh (normally a refinement of `coarse_mesh`) whose nodes receive values
+    /// * `coarse_solution` - Value of solution at every node of `coarse_mesh`
+    /// * `coarse_mesh` - Mesh where solution was obtained. Should cover every node of `self`
+    ///
+    pub fn interpolate_solution_onto(&self, coarse_solution: &[f64], coarse_mesh: &Mesh) -> Result<Vec<f64>, Error> {
+        if self.dimension != MeshDimension::One || coarse_mesh.dimension != MeshDimension::One {
+            return Err(Error::custom("Solutions can only be interpolated between 1D meshes"));
+        }
+
+        let coarse_nodes: Vec<f64> = coarse_mesh.filter_for_solving_1d().to_vec();
+        if coarse_solution.len() != coarse_nodes.len() {
+            return Err(Error::WrongDims);
+        }
+        let basis = LinearBasis::new(&coarse_nodes.iter().map(|node| to_scalar(*node)).collect())?;
+        let (first, last) = (coarse_nodes[0], coarse_nodes[coarse_nodes.len() - 1]);
+
+        self.filter_for_solving_1d()
+            .iter()
+            .map(|x| -> Result<f64, Error> {
+                if *x < first || *x > last {
+                    return Err(Error::custom(format!("Node {} lies outside of coarse mesh [{}, {}]", x, first, last)));
+                }
+                // Pieces of basis functions do not include their right end, so last node is taken as is
+                if *x == last {
+                    return Ok(coarse_solution[coarse_solution.len() - 1]);
+                }
+                // Element whose left node is the last one not after x
+                let element = coarse_nodes
+                    .partition_point(|node| node <= x)
+                    .saturating_sub(1)
+                    .min(coarse_nodes.len() - 2);
+
+                Ok(coarse_solution[element] * from_scalar(basis.basis[element].evaluate(to_scalar(*x)))
+                    + coarse_solution[element + 1] * from_scalar(basis.basis[element + 1].evaluate(to_scalar(*x))))
+            })
+            .collect()
+    }
+
+    /// # General Information
+    ///
+    /// Value of a 2D solution at any point of mesh. Triangle containing the point is found and the nodal values of its vertices are
+    /// weighted by the barycentric coordinates of the point, that is, solution is taken as linear over every triangle.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - 2D mesh where solution was obtained
+    /// * `solution` - Value of solution at every vertex of mesh
+    /// * `point` - Point (in mesh coordinates, before model matrix is applied) to evaluate solution at
+    ///
+    pub fn evaluate_2d(&self, solution: &[f64], point: [f64; 2]) -> Result<f64, Error> {
+        if self.dimension != MeshDimension::Two {
+            return Err(Error::custom("Only 2D meshes can evaluate a 2D solution"));
+        }
+        if solution.len() != self.vertices.len() / 6 {
+            return Err(Error::WrongDims);
+        }
+
+        for triangle in self.indices.exact_chunks(3) {
+            let a = self.position(triangle[0])?;
+            let b = self.position(triangle[1])?;
+            let c = self.position(triangle[2])?;
+
+            let determinant = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+            if determinant == 0.0 {
+                continue;
+            }
+            // Weights of b and c, so that point = a + weight_b (b - a) + weight_c (c - a)
+            let weight_b = ((point[0] - a.x) * (c.y - a.y) - (c.x - a.x) * (point[1] - a.y)) / determinant;
+            let weight_c = ((b.x - a.x) * (point[1] - a.y) - (point[0] - a.x) * (b.y - a.y)) / determinant;
+            let weight_a = 1.0 - weight_b - weight_c;
+
+            // Points on edges belong to every triangle sharing them, up to rounding
+            let tolerance = 1e-12;
+            if weight_a >= -tolerance && weight_b >= -tolerance && weight_c >= -tolerance {
+                return Ok(weight_a * solution[triangle[0] as usize]
+                    + weight_b * solution[triangle[1] as usize]
+                    + weight_c * solution[triangle[2] as usize]);
+            }
+        }
+
+        Err(Error::OutOfDomain)
+    }
+
+    /// # General Information
+    ///
+    /// Coarser copy of a 2D mesh for quick previews, obtained by vertex clustering: a grid is laid over the mesh and every internal vertex inside
+    /// a cell is merged into a single one at their centroid. Boundary vertices are never merged, so the outline of mesh is kept. Triangles left
+    /// with a repeated vertex disappear. Grid size is chosen so that the amount of triangles is as close as possible to the fraction requested.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - 2D mesh to coarsen
+    /// * `target_fraction` - Fraction of triangles to keep. Should be in (0, 1]
+    ///
+    pub fn decimate(&self, target_fraction: f64) -> Result<Mesh, Error> {
+        if self.dimension != MeshDimension::Two {
+            return Err(Error::custom("Only 2D meshes can be decimated"));
+        }
+        if !(target_fraction > 0.0 && target_fraction <= 1.0) {
+            return Err(Error::custom(format!("Fraction of triangles to keep should be in (0, 1], got {}", target_fraction)));
+        }
+
+        let target = target_fraction * (self.indices.len() / 3) as f64;
+        let boundary: HashSet<u32> = self.boundary_indices.iter().flatten().copied().collect();
+        let distance_to_target = |indices: &[u32]| ((indices.len() / 3) as f64 - target).abs();
+
+        // Finer grids merge less vertices. A grid with twice as many cells per axis as vertices per axis hardly merges any
+        let max_cells = 2 * ((self.vertices.len() / 6) as f64).sqrt().ceil() as usize + 1;
+        let (vertices, indices, boundary_indices) = (1..=max_cells)
+            .map(|cells| self.cluster_vertices(cells, &boundary))
+            .min_by(|a, b| distance_to_target(&a.1).total_cmp(&distance_to_target(&b.1)))
+            .ok_or(Error::Infallible)?;
+
+        let mut mesh = Mesh {
+            dimension: MeshDimension::Two,
+            max_length: self.max_length,
+            model_matrix: self.model_matrix,
+            boundary_indices: Some(boundary_indices),
+            binder: Binder::new(),
+            indices: Array1::from_vec(indices),
+            vertices: Array1::from_vec(vertices),
+            spatial_index: None,
+            highlight: None,
+        };
+        let stats = mesh.stats();
+        mesh.max_length = (stats.max[0] - stats.min[0]).max(stats.max[1] - stats.min[1]);
+
+        Ok(mesh)
+    }
+
+    /// # General Information
+    ///
+    /// Merges internal vertices sharing a cell of a grid with `cells` cells per axis. Returns vertices, indices of triangles and boundary indices
+    /// of the result. Vertices no longer used by any triangle are dropped.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - 2D mesh to coarsen
+    /// * `cells` - Amount of cells per axis
+    /// * `boundary` - Indices of boundary vertices, which are never merged
+    ///
+    fn cluster_vertices(&self, cells: usize, boundary: &HashSet<u32>) -> (Vec<f64>, Vec<u32>, Vec<u32>) {
+        let stats = self.stats();
+        let size = [0, 1].map(|axis| (stats.max[axis] - stats.min[axis]) / cells as f64);
+        let cell_of = |vertex: &[f64]| -> [usize; 2] {
+            [0, 1].map(|axis| {
+                if size[axis] > 0.0 {
+                    (((vertex[axis] - stats.min[axis]) / size[axis]) as usize).min(cells - 1)
+                } else {
+                    0
+                }
+            })
+        };
+
+        // Every vertex is sent to a group: its own on the boundary, the one of its cell otherwise. Groups keep the sum of positions, amount of vertices,
+        // color of first vertex and wether they are on the boundary
+        let mut groups: Vec<([f64; 3], usize, [f64; 3], bool)> = vec![];
+        let mut cell_groups: HashMap<[usize; 2], usize> = HashMap::new();
+        let mut group_of: Vec<usize> = Vec::with_capacity(self.vertices.len() / 6);
+
+        for (id, vertex) in self.vertices.exact_chunks(6).into_iter().enumerate() {
+            let vertex = vertex.to_vec();
+            let on_boundary = boundary.contains(&(id as u32));
+            let new_group = ([0.0; 3], 0, [vertex[3], vertex[4], vertex[5]], on_boundary);
+            let group = if on_boundary {
+                groups.push(new_group);
+                groups.len() - 1
+            } else {
+                *cell_groups.entry(cell_of(&vertex)).or_insert_with(|| {
+                    groups.push(new_group);
+                    groups.len() - 1
+                })
+            };
+
+            let (sum, amount, _, _) = &mut groups[group];
+            for axis in 0..3 {
+                sum[axis] += vertex[axis];
+            }
+            *amount += 1;
+            group_of.push(group);
+        }
+
+        // Triangles between three different groups survive, once
+        let mut seen: HashSet<[usize; 3]> = HashSet::new();
+        let mut triangles: Vec<[usize; 3]> = vec![];
+        for triangle in self.indices.exact_chunks(3) {
+            let triangle = [0, 1, 2].map(|corner| group_of[triangle[corner] as usize]);
+            let mut key = triangle;
+            key.sort_unstable();
+            if key[0] != key[1] && key[1] != key[2] && seen.insert(key) {
+                triangles.push(triangle);
+            }
+        }
+
+        // Only groups with triangles become vertices
+        let mut used = vec![false; groups.len()];
+        triangles.iter().flatten().for_each(|group| used[*group] = true);
+        let mut new_index: Vec<u32> = vec![0; groups.len()];
+        let mut vertices: Vec<f64> = vec![];
+        let mut boundary_indices: Vec<u32> = vec![];
+        for (group, (sum, amount, color, on_boundary)) in groups.iter().enumerate().filter(|(group, _)| used[*group]) {
+            new_index[group] = (vertices.len() / 6) as u32;
+            if *on_boundary {
+                boundary_indices.push(new_index[group]);
+            }
+            vertices.extend(sum.map(|coordinate| coordinate / *amount as f64));
+            vertices.extend(color);
+        }
+        let indices = triangles.iter().flatten().map(|group| new_index[*group]).collect();
+
+        (vertices, indices, boundary_indices)
+    }
+
+    /// # General Information
+    ///
+    /// Error indicator of every element of a 1D mesh from the jump of the gradient of a solution across its nodes. For element e with length h,
+    /// it is "sqrt(h (J_left² + J_right²) / 2)", where J are the jumps of the derivative at its ends (zero at the ends of mesh). Every jump is
+    /// shared by two elements, hence the half.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - 1D mesh where solution was obtained
+    /// * `solution` - Value of solution at every node of mesh
+    ///
+    pub(crate) fn gradient_jump_indicators(&self, solution: &[f64]) -> Result<Vec<f64>, Error> {
+        if self.dimension != MeshDimension::One {
+            return Err(Error::custom("Gradient jumps can only be obtained on 1D meshes"));
+        }
+
+        let nodes = self.filter_for_solving_1d().to_vec();
+        let gradients = element_gradients(
+            &nodes.iter().map(|node| to_scalar(*node)).collect(),
+            &solution.iter().map(|value| to_scalar(*value)).collect::<Vec<_>>(),
+        )?;
+        // Jump at every node. Ends of mesh have no neighbor to jump from
+        let jumps: Vec<f64> = std::iter::once(0.0)
+            .chain(gradients.windows(2).map(|pair| from_scalar(pair[1] - pair[0])))
+            .chain(std::iter::once(0.0))
+            .collect();
+
+        Ok((0..gradients.len())
+            .map(|element| ((nodes[element + 1] - nodes[element]) * (jumps[element].powi(2) + jumps[element + 1].powi(2)) / 2.0).sqrt())
+            .collect())
+    }
+
+    /// # General Information
+    ///
+    /// Refinement of a 1D mesh driven by a solution: every element accepted by `marker` is split in two at its midpoint, while the rest are
+    /// kept as they are. Marker receives the index of the element and its gradient-jump error indicator (see `gradient_jump_indicators`),
+    /// so that refinement can follow the largest errors.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - 1D mesh to refine
+    /// * `solution` - Value of solution at every node of mesh
+    /// * `marker` - Tells wether an element, given its index and error indicator, should be refined
+    ///
+    pub fn refine_where<M: Fn(usize, f64) -> bool>(&self, solution: &[f64], marker: M) -> Result<Mesh, Error> {
+        let indicators = self.gradient_jump_indicators(solution)?;
+        let nodes = self.filter_for_solving_1d();
+
+        let mut refined_nodes: Vec<f64> = nodes.to_vec();
+        for (element, indicator) in indicators.iter().enumerate() {
+            if marker(element, *indicator) {
+                refined_nodes.push((nodes[element] + nodes[element + 1]) / 2.0);
+            }
+        }
+
+        MeshBuilder::mesh_1d_from_nodes(refined_nodes)?
+            .with_base_color(self.vertex_color(0).map(|channel| channel as f32))
+            .build_mesh_1d(None)
+    }
+
     /// Filtering vertices to give to 1d solver. Temporal function. To be changed for better solution.
     pub(crate) fn filter_for_solving_1d(&self) -> Array1<f64> {
         // size of vertex is 6. There are double the vertices in 1d since a new pair is generated to draw a bar, therefore len is divided by 12.
@@ -69,24 +813,59 @@ impl Mesh {
 
     /// Improvable solution to move gradient updating out of dzahui window. Probably will be changed in the future.
     /// Obtains max and min of solution (normallly some sort of rate of change), divides every element by the difference and then multiplies them by
-    /// pi/2 so that, when calculating their sine and cosine, there's a mapping between max velocity <-> red and min velocity <-> blue
-    pub(crate) fn update_gradient_1d(&mut self, velocity_norm: Vec<f64>) {
-        let sol_max = velocity_norm
-            .iter()
-            .copied()
-            .fold(f64::NEG_INFINITY, f64::max);
+    /// pi/2 so that, when calculating their sine and cosine, there's a mapping between max velocity <-> red and min velocity <-> blue.
+    /// When `fixed_range` is given it is used instead of the solution's min and max, and values outside of it are clamped.
+    /// Returns the range used, so that it can be shown on a legend.
+    /// Solutions with less than two values (like the one of `NoSolver`) leave colors untouched, and constant solutions are drawn with the color of the minimum.
+    pub(crate) fn update_gradient_1d(&mut self, velocity_norm: Vec<f64>, fixed_range: Option<(f64, f64)>) -> (f64, f64) {
+        if velocity_norm.len() < 2 {
+            return match (fixed_range, velocity_norm.first()) {
+                (Some(range), _) => range,
+                (None, Some(value)) => (*value, *value),
+                (None, None) => (0.0, 0.0),
+            };
+        }
 
-        let sol_min = velocity_norm.iter().copied().fold(f64::INFINITY, f64::min);
+        let (sol_min, sol_max) = match fixed_range {
+            Some(range) => range,
+            None => (
+                velocity_norm.iter().copied().fold(f64::INFINITY, f64::min),
+                velocity_norm
+                    .iter()
+                    .copied()
+                    .fold(f64::NEG_INFINITY, f64::max),
+            ),
+        };
         let vertices_len = self.vertices.len();
         
-        for i in 0..(vertices_len / 12) {
-            let norm_sol =
-                (velocity_norm[i] - sol_min) / (sol_max - sol_min) * (std::f64::consts::PI / 2.);
-            self.vertices[6 * i + 3] = norm_sol.sin();
-            self.vertices[6 * i + 5] = norm_sol.cos();
-            self.vertices[6 * i + 3 + vertices_len / 2] = norm_sol.sin();
-            self.vertices[6 * i + 5 + vertices_len / 2] = norm_sol.cos();
+        let range = sol_max - sol_min;
+        
+        for (i, value) in velocity_norm.iter().take(vertices_len / 12).enumerate() {
+            let value = value.clamp(sol_min, sol_max);
+            let normalized = if range > 0.0 { (value - sol_min) / range } else { 0.0 };
+            let [r, g, b] = gradient_color(normalized);
+            for offset in [0, vertices_len / 2] {
+                self.vertices[6 * i + 3 + offset] = r;
+                self.vertices[6 * i + 4 + offset] = g;
+                self.vertices[6 * i + 5 + offset] = b;
+            }
         }
+
+        // Solution color of highlighted vertex is kept to be restored later
+        self.keep_highlight();
+
+        (sol_min, sol_max)
+    }
+}
+
+/// Two meshes are equal when they come from equal files: same dimension, vertices, indices and length.
+/// Binder (GPU state) and model matrix (derived from vertices) are not compared.
+impl PartialEq for Mesh {
+    fn eq(&self, other: &Self) -> bool {
+        self.dimension == other.dimension
+            && self.max_length == other.max_length
+            && self.vertices == other.vertices
+            && self.indices == other.indices
     }
 }
 
@@ -112,6 +891,10 @@ impl Drawable for Mesh {
         ))
     }
 
+    fn get_vertex_count(&self) -> Result<usize, Error> {
+        Ok(self.vertices.len() / 6)
+    }
+
     fn get_max_length(&self) -> Result<f32, Error> {
         let max_len = self.max_length.to_f32();
 
@@ -130,8 +913,27 @@ impl Drawable for Mesh {
 
 #[cfg(test)]
 mod test {
-    use super::Mesh;
-    use ndarray::Array1;
+    use super::{mesh_builder::{MeshBuilder, MeshDimension}, Mesh};
+    use crate::{simulation::drawable::binder::{Drawable, DrawMode}, Error};
+    use cgmath::Vector4;
+    use ndarray::{s, Array1};
+
+    /// Center of bounding box of mesh once model matrix is applied.
+    fn transformed_center(mesh: &Mesh) -> [f32; 3] {
+        let mut min = [f32::INFINITY; 3];
+        let mut max = [f32::NEG_INFINITY; 3];
+
+        for vertex in mesh.vertices.as_slice().unwrap().chunks_exact(6) {
+            let transformed = mesh.get_model_matrix()
+                * Vector4::new(vertex[0] as f32, vertex[1] as f32, vertex[2] as f32, 1.0);
+            for (i, coordinate) in [transformed.x, transformed.y, transformed.z].into_iter().enumerate() {
+                min[i] = min[i].min(coordinate);
+                max[i] = max[i].max(coordinate);
+            }
+        }
+
+        [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0, (min[2] + max[2]) / 2.0]
+    }
 
     #[test]
     fn parse_coordinates() {
@@ -156,4 +958,495 @@ mod test {
         assert!(new_mesh.max_length >= 1.90);
         assert!(new_mesh.max_length <= 2.10);
     }
+
+    #[test]
+    fn face_out_of_range_is_rejected() {
+        let new_mesh = Mesh::builder("./assets/out_of_range_face.obj").build_mesh_2d();
+        assert!(matches!(new_mesh, Err(Error::MeshParse(ref message)) if message.contains("vertex 4")));
+
+        let new_mesh = Mesh::builder("./assets/out_of_range_face.obj").build_mesh_3d();
+        assert!(matches!(new_mesh, Err(Error::MeshParse(_))));
+    }
+
+    #[test]
+    fn vertex_only_mesh_is_drawn_as_points() {
+        let new_mesh = Mesh::builder("./assets/point_cloud.obj")
+            .build_mesh_3d()
+            .unwrap();
+        assert!(new_mesh.indices.is_empty());
+        assert!(new_mesh.get_vertex_count().unwrap() == 4);
+        assert!(new_mesh.get_draw_mode().unwrap() == DrawMode::Points);
+
+        let new_mesh = Mesh::builder("./assets/test.obj")
+            .build_mesh_3d()
+            .unwrap();
+        assert!(new_mesh.get_draw_mode().unwrap() == DrawMode::Triangles);
+    }
+
+    #[test]
+    fn mesh_1d_is_centered_at_origin() {
+        let new_mesh = Mesh::builder("./assets/1dbar_irregular_small.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        assert!(transformed_center(&new_mesh).iter().all(|c| c.abs() < 1e-5));
+    }
+
+    #[test]
+    fn mesh_2d_is_centered_at_origin() {
+        let new_mesh = Mesh::builder("./assets/test.obj")
+            .build_mesh_2d()
+            .unwrap();
+        assert!(transformed_center(&new_mesh).iter().all(|c| c.abs() < 1e-5));
+    }
+
+    #[test]
+    fn mesh_3d_is_centered_at_origin() {
+        let new_mesh = Mesh::builder("./assets/offset_tetrahedron.obj")
+            .build_mesh_3d()
+            .unwrap();
+        assert!(transformed_center(&new_mesh).iter().all(|c| c.abs() < 1e-5));
+    }
+
+    #[test]
+    fn comments_and_directives_are_skipped() {
+        let clean = Mesh::builder("./assets/1dbar_irregular_small.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        let sprinkled = Mesh::builder("./assets/1dbar_sprinkled.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        assert!(clean.vertices == sprinkled.vertices);
+        assert!(clean.indices == sprinkled.indices);
+
+        let clean = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        let sprinkled = Mesh::builder("./assets/test_sprinkled.obj")
+            .build_mesh_2d()
+            .unwrap();
+        assert!(clean.vertices == sprinkled.vertices);
+        assert!(clean.indices == sprinkled.indices);
+        assert!(clean.boundary_indices == sprinkled.boundary_indices);
+
+        let clean = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        let sprinkled = Mesh::builder("./assets/test_sprinkled.obj")
+            .build_mesh_3d()
+            .unwrap();
+        assert!(clean.vertices == sprinkled.vertices);
+        assert!(clean.indices == sprinkled.indices);
+    }
+
+    #[test]
+    fn constant_axes_give_dimension() {
+        let axes = Mesh::builder("./assets/1dbar_irregular_small.obj").constant_axes().unwrap();
+        assert!(axes == vec![1, 2]);
+
+        let axes = Mesh::builder("./assets/test.obj").constant_axes().unwrap();
+        assert!(axes == vec![2]);
+
+        let axes = Mesh::builder("./assets/offset_tetrahedron.obj").constant_axes().unwrap();
+        assert!(axes.is_empty());
+    }
+
+    #[test]
+    fn crlf_line_endings_are_accepted() {
+        let lf = Mesh::builder("./assets/1dbar_irregular_small.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        let crlf = Mesh::builder("./assets/1dbar_crlf.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        assert!(lf.vertices == crlf.vertices);
+        assert!(lf.indices == crlf.indices);
+
+        let lf = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        let crlf = Mesh::builder("./assets/test_crlf.obj").build_mesh_2d().unwrap();
+        assert!(lf.vertices == crlf.vertices);
+        assert!(lf.indices == crlf.indices);
+
+        let lf = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        let crlf = Mesh::builder("./assets/test_crlf.obj").build_mesh_3d().unwrap();
+        assert!(lf.vertices == crlf.vertices);
+        assert!(lf.indices == crlf.indices);
+    }
+
+    #[test]
+    fn same_file_gives_equal_meshes() {
+        let first = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        let second = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        assert!(first == second);
+
+        let other_file = Mesh::builder("./assets/trapezoid.obj").build_mesh_2d().unwrap();
+        assert!(first != other_file);
+
+        let other_dimension = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        assert!(first != other_dimension);
+    }
+
+    #[test]
+    fn highlight_moves_and_restores_colors() {
+        let mut mesh = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        let original = mesh.vertices.clone();
+        let highlight = [1.0, 1.0, 0.0];
+
+        mesh.highlight_vertex(Some(2), highlight).unwrap();
+        assert!(mesh.vertex_color(2) == highlight);
+
+        // Previously selected vertex gets its color back
+        mesh.highlight_vertex(Some(0), highlight).unwrap();
+        assert!(mesh.vertex_color(0) == highlight);
+        assert!(mesh.vertices.slice(s![12..18]) == original.slice(s![12..18]));
+
+        mesh.highlight_vertex(None, highlight).unwrap();
+        assert!(mesh.vertices == original);
+        assert!(mesh.highlight_vertex(Some(original.len()), highlight).is_err());
+
+        // Highlight survives solution colors, and solution color is restored afterwards
+        let mut bar = Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap();
+        let nodes = bar.vertices.len() / 12;
+        let solution: Vec<f64> = (0..nodes).map(|i| i as f64).collect();
+        bar.highlight_vertex(Some(1), highlight).unwrap();
+        bar.update_gradient_1d(solution, None);
+        assert!(bar.vertex_color(1) == highlight);
+        bar.highlight_vertex(None, highlight).unwrap();
+        assert!(bar.vertex_color(1) == bar.vertex_color(1 + nodes));
+    }
+
+    #[test]
+    fn build_dispatches_on_dimension() {
+        let dispatched = Mesh::builder("./assets/test.obj").build(MeshDimension::Two).unwrap();
+        let direct = Mesh::builder("./assets/test.obj").build_mesh_2d().unwrap();
+        assert!(dispatched == direct);
+
+        let bar = Mesh::builder("./assets/1dbar.obj").with_height_multiplier(2.0).build(MeshDimension::One).unwrap();
+        assert!(bar == Mesh::builder("./assets/1dbar.obj").build_mesh_1d(Some(2.0)).unwrap());
+        assert!(bar != Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap());
+    }
+
+    #[test]
+    fn duplicated_seam_vertex_is_welded() {
+        // Center of square appears twice, each copy used by two of the four triangles
+        let seam = Mesh::builder("./assets/seam_square.obj").build_mesh_2d().unwrap();
+        assert!(seam.boundary_indices == Some(vec![0, 1, 2, 3]));
+        // Vertices themselves are kept
+        assert!(seam.vertices.len() == 36);
+
+        let square = Mesh::builder("./assets/unit_square.obj").with_weld_tolerance(1e-6).build_mesh_2d().unwrap();
+        assert!(square.boundary_indices == Some(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn linear_field_is_interpolated_exactly() {
+        let square = Mesh::builder("./assets/seam_square.obj").build_mesh_2d().unwrap();
+        let field = |x: f64, y: f64| 2.0 * x - 3.0 * y + 1.0;
+        let solution: Vec<f64> = square.vertices.exact_chunks(6).into_iter().map(|v| field(v[0], v[1])).collect();
+
+        for point in [[0.5, 0.2], [0.9, 0.6], [0.25, 0.75], [0.1, 0.1], [1.0, 1.0], [0.5, 0.5]] {
+            let value = square.evaluate_2d(&solution, point).unwrap();
+            assert!((value - field(point[0], point[1])).abs() < 1e-12);
+        }
+
+        assert!(matches!(square.evaluate_2d(&solution, [1.5, 0.5]), Err(Error::OutOfDomain)));
+        assert!(matches!(square.evaluate_2d(&solution[1..], [0.5, 0.5]), Err(Error::WrongDims)));
+    }
+
+    #[test]
+    fn decimation_halves_triangles_and_keeps_outline() {
+        let dense = Mesh::builder("./assets/big_mesh.obj").build_mesh_2d().unwrap();
+        let coarse = dense.decimate(0.5).unwrap();
+
+        let (dense_stats, coarse_stats) = (dense.stats(), coarse.stats());
+        let ratio = coarse_stats.triangle_count as f64 / dense_stats.triangle_count as f64;
+        assert!(ratio > 0.35 && ratio < 0.65);
+        for axis in 0..3 {
+            assert!((coarse_stats.min[axis] - dense_stats.min[axis]).abs() < 1e-9);
+            assert!((coarse_stats.max[axis] - dense_stats.max[axis]).abs() < 1e-9);
+        }
+
+        // Result is a valid mesh: indices in range, no repeated vertex in a triangle and boundary kept whole
+        let vertex_count = coarse.vertices.len() / 6;
+        assert!(coarse.indices.iter().all(|index| (*index as usize) < vertex_count));
+        assert!(coarse.indices.exact_chunks(3).into_iter().all(|t| t[0] != t[1] && t[1] != t[2] && t[0] != t[2]));
+        assert!(coarse.boundary_indices.as_ref().unwrap().len() == dense.boundary_indices.as_ref().unwrap().len());
+        assert!((coarse.measure().unwrap() - dense.measure().unwrap()).abs() < 1e-6 * dense.measure().unwrap());
+        assert!(dense.decimate(1.0).unwrap().indices.len() == dense.indices.len());
+
+        assert!(dense.decimate(0.0).is_err());
+        assert!(Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap().decimate(0.5).is_err());
+    }
+
+    #[test]
+    fn stats_match_obj() {
+        let square = Mesh::builder("./assets/unit_square.obj").build_mesh_2d().unwrap();
+        let stats = square.stats();
+        assert!(stats.dimension == MeshDimension::Two);
+        assert!(stats.vertex_count == 4);
+        assert!(stats.triangle_count == 2);
+        assert!(stats.min == [0.0, 0.0, 0.0] && stats.max == [1.0, 1.0, 0.0]);
+
+        // Bar height is not part of the stats
+        let bar = Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap();
+        let stats = bar.stats();
+        assert!(stats.vertex_count == 11);
+        assert!(stats.triangle_count == 20);
+        assert!(stats.min == [0.0, 0.0, 0.0] && stats.max == [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn measure_per_dimension() {
+        let bar = Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap();
+        assert!((bar.measure().unwrap() - 1.0).abs() < 1e-9);
+
+        let square = Mesh::builder("./assets/unit_square.obj").build_mesh_2d().unwrap();
+        assert!((square.measure().unwrap() - 1.0).abs() < 1e-9);
+
+        let trapezoid = Mesh::builder("./assets/trapezoid.obj").build_mesh_2d().unwrap();
+        assert!((trapezoid.measure().unwrap() - 1.5).abs() < 1e-9);
+
+        let tetrahedron = Mesh::builder("./assets/offset_tetrahedron.obj").build_mesh_3d().unwrap();
+        assert!((tetrahedron.measure().unwrap() - 1.0 / 3.0).abs() < 1e-9);
+
+        // A single triangle does not enclose any volume
+        let open_surface = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        assert!(open_surface.measure().is_err());
+    }
+
+    #[test]
+    fn vertex_colors_are_read() {
+        let colored = Mesh::builder("./assets/colored_triangle.obj").build_mesh_3d().unwrap();
+        assert!(
+            colored.vertices
+                == Array1::from_vec(vec![
+                    -1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+                    0.0, 0.0, 1.0
+                ])
+        );
+
+        // In 2D the constant coordinate is removed but colors are kept
+        let colored = Mesh::builder("./assets/colored_triangle.obj").build_mesh_2d().unwrap();
+        let colors: Vec<f64> = colored
+            .vertices
+            .exact_chunks(6)
+            .into_iter()
+            .flat_map(|vertex| vertex.to_vec().split_off(3))
+            .collect();
+        assert!(colors == vec![1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn fixed_color_range_is_frame_independent() {
+        let mut mesh = Mesh::builder("./assets/1dbar_irregular_small.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        let nodes = mesh.vertices.len() / 12;
+        let color_of =
+            |mesh: &Mesh, node: usize| mesh.vertices.slice(s![6 * node + 3..6 * node + 6]).to_vec();
+
+        // Node 0 keeps its value while the rest of the solution changes its min and max
+        let mut first_frame = vec![0.5; nodes];
+        first_frame[1] = 0.2;
+        first_frame[2] = 0.9;
+        let mut second_frame = vec![0.5; nodes];
+        second_frame[1] = 0.4;
+        second_frame[2] = 3.0;
+
+        mesh.update_gradient_1d(first_frame.clone(), Some((0.0, 1.0)));
+        let first_color = color_of(&mesh, 0);
+        mesh.update_gradient_1d(second_frame.clone(), Some((0.0, 1.0)));
+        let second_color = color_of(&mesh, 0);
+        assert!(first_color == second_color);
+
+        // Out of range values are clamped to the maximum color
+        let clamped = color_of(&mesh, 2);
+        assert!((clamped[0] - 1.0).abs() < 1e-12 && clamped[2].abs() < 1e-12);
+
+        // Without a fixed range colors depend on every frame's min and max
+        mesh.update_gradient_1d(first_frame, None);
+        let first_color = color_of(&mesh, 0);
+        mesh.update_gradient_1d(second_frame, None);
+        assert!(first_color != color_of(&mesh, 0));
+    }
+
+    #[test]
+    fn degenerate_solutions_do_not_write_nan() {
+        let mut mesh = Mesh::builder("./assets/1dbar_irregular_small.obj")
+            .build_mesh_1d(None)
+            .unwrap();
+        let nodes = mesh.vertices.len() / 12;
+        let original = mesh.vertices.clone();
+
+        // Empty and single-element solutions are a no-op
+        assert!(mesh.update_gradient_1d(vec![], None) == (0.0, 0.0));
+        assert!(mesh.vertices == original);
+        assert!(mesh.update_gradient_1d(vec![2.0], None) == (2.0, 2.0));
+        assert!(mesh.vertices == original);
+
+        // A constant field is drawn with the color of the minimum
+        assert!(mesh.update_gradient_1d(vec![1.5; nodes], None) == (1.5, 1.5));
+        assert!(mesh.vertices.iter().all(|x| !x.is_nan()));
+        let color = mesh.vertices.slice(s![3..6]).to_vec();
+        assert!(color == vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn sliver_triangle_lowers_quality() {
+        let square = Mesh::builder("./assets/unit_square.obj").build_mesh_2d().unwrap();
+        let report = square.quality_report_2d().unwrap();
+        assert!((report.min_angle - 45.0).abs() < 1e-9);
+        assert!(report.max_aspect_ratio < 1.3);
+
+        let sliver = Mesh::builder("./assets/sliver.obj").build_mesh_2d().unwrap();
+        let report = sliver.quality_report_2d().unwrap();
+        assert!(report.min_angle < 2.0);
+        assert!(report.max_aspect_ratio > 20.0);
+        assert!(report.min_aspect_ratio < 1.3);
+        assert!(report.mean_aspect_ratio > report.min_aspect_ratio && report.mean_aspect_ratio < report.max_aspect_ratio);
+
+        let bar = Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap();
+        assert!(bar.quality_report_2d().is_err());
+    }
+
+    #[test]
+    fn minimal_1d_meshes() {
+        let two_nodes = Mesh::builder("./assets/1dbar_two_nodes.obj").build_mesh_1d(None).unwrap();
+        assert!(two_nodes.indices == Array1::from_vec(vec![0, 1, 2, 1, 3, 2]));
+        assert!(two_nodes.max_length == 2.0);
+        assert!(two_nodes.vertices.iter().all(|x| x.is_finite()));
+
+        let single_node = Mesh::builder("./assets/1dbar_single_node.obj").build_mesh_1d(None);
+        assert!(matches!(single_node, Err(Error::MeshParse(_))));
+
+        let empty = Mesh::builder("./assets/empty.obj").build_mesh_1d(None);
+        assert!(empty.is_err());
+    }
+
+    #[test]
+    fn mapped_1d_meshes() {
+        let nodes = |mesh: &Mesh| mesh.filter_for_solving_1d().to_vec();
+
+        let uniform = MeshBuilder::mesh_1d_from_mapping(0.0, 2.0, 5, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        assert!(nodes(&uniform) == vec![0.0, 0.5, 1.0, 1.5, 2.0]);
+        assert!(uniform.max_length == 2.0);
+
+        // tanh clusters nodes at both ends
+        let clustered = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 11, |t| (3.0 * (2.0 * t - 1.0)).tanh())
+            .unwrap()
+            .build_mesh_1d(None)
+            .unwrap();
+        let clustered = nodes(&clustered);
+        let widths: Vec<f64> = clustered.windows(2).map(|pair| pair[1] - pair[0]).collect();
+        assert!(clustered[0] == 0.0 && (clustered[10] - 1.0).abs() < 1e-12);
+        assert!(widths[0] < 0.05 && widths[9] < 0.05 && widths[5] > 0.2);
+
+        assert!(matches!(MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 5, |t| (t - 0.5).powi(2)), Err(Error::MeshParse(_))));
+        assert!(MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 1, |t| t).is_err());
+    }
+
+    #[test]
+    fn base_color_fills_color_slots() {
+        let colors = |mesh: &Mesh| -> Vec<Vec<f64>> {
+            mesh.vertices.exact_chunks(6).into_iter().map(|vertex| vertex.to_vec().split_off(3)).collect()
+        };
+        let red = vec![1.0, 0.0, 0.0];
+
+        let mesh = Mesh::builder("./assets/test.obj").with_base_color([1.0, 0.0, 0.0]).build_mesh_3d().unwrap();
+        assert!(colors(&mesh).iter().all(|color| *color == red));
+
+        let mesh = Mesh::builder("./assets/1dbar.obj").with_base_color([1.0, 0.0, 0.0]).build_mesh_1d(None).unwrap();
+        assert!(colors(&mesh).iter().all(|color| *color == red));
+
+        // Colors from file are kept
+        let mesh = Mesh::builder("./assets/colored_triangle.obj").with_base_color([1.0, 1.0, 1.0]).build_mesh_3d().unwrap();
+        assert!(colors(&mesh)[1] == vec![0.0, 1.0, 0.0]);
+
+        // Default is blue
+        let mesh = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        assert!(colors(&mesh).iter().all(|color| *color == vec![0.0, 0.0, 1.0]));
+    }
+
+    #[test]
+    fn linear_solution_is_interpolated_exactly() {
+        let coarse = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 3, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        let refined = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 9, |t| t.powi(2)).unwrap().build_mesh_1d(None).unwrap();
+        let linear = |x: f64| 2.0 * x + 1.0;
+
+        let coarse_solution: Vec<f64> = coarse.filter_for_solving_1d().iter().map(|x| linear(*x)).collect();
+        let refined_solution = refined.interpolate_solution_onto(&coarse_solution, &coarse).unwrap();
+
+        assert!(refined
+            .filter_for_solving_1d()
+            .iter()
+            .zip(refined_solution.iter())
+            .all(|(x, u)| (u - linear(*x)).abs() < 1e-12));
+
+        assert!(matches!(refined.interpolate_solution_onto(&coarse_solution[..2], &coarse), Err(Error::WrongDims)));
+        let wider = MeshBuilder::mesh_1d_from_mapping(0.0, 2.0, 5, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        assert!(wider.interpolate_solution_onto(&coarse_solution, &coarse).is_err());
+    }
+
+    #[test]
+    fn refine_marked_element_only() {
+        let mesh = MeshBuilder::mesh_1d_from_mapping(0.0, 4.0, 5, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        // Kink at x = 2, so only the two elements around it have error
+        let solution = [0.0, 1.0, 2.0, 2.0, 2.0];
+        let indicators = mesh.gradient_jump_indicators(&solution).unwrap();
+        assert!(indicators[0] == 0.0 && indicators[3] == 0.0);
+        assert!(indicators[1] > 0.0 && indicators[2] > 0.0);
+
+        let refined = mesh.refine_where(&solution, |element, _| element == 2).unwrap();
+        assert!(refined.filter_for_solving_1d().to_vec() == vec![0.0, 1.0, 2.0, 2.5, 3.0, 4.0]);
+
+        let by_error = mesh.refine_where(&solution, |_, indicator| indicator > 0.0).unwrap();
+        assert!(by_error.filter_for_solving_1d().to_vec() == vec![0.0, 1.0, 1.5, 2.0, 2.5, 3.0, 4.0]);
+
+        assert!(matches!(mesh.refine_where(&solution[..4], |_, _| true), Err(Error::WrongDims)));
+    }
+
+    #[test]
+    fn node_coordinates_match_obj() {
+        let mesh = Mesh::builder("./assets/simple_triangle.obj").build_mesh_2d().unwrap();
+        assert!(mesh.node_coordinates() == vec![[-0.5, -0.5, 0.0], [0.5, -0.5, 0.0], [0.0, 0.5, 0.0]]);
+
+        let bar = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 3, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        let nodes: Vec<f64> = bar.node_coordinates().iter().map(|point| point[0]).collect();
+        assert!(nodes == bar.filter_for_solving_1d().to_vec());
+    }
+
+    #[test]
+    fn boundary_vertices_are_colored_apart() {
+        let (boundary_color, interior_color) = ([1.0, 0.0, 0.0], [0.0, 0.0, 1.0]);
+        let mut mesh = Mesh::builder("./assets/big_mesh.obj").build_mesh_2d().unwrap();
+        let base_colors = mesh.colors();
+        let boundary = mesh.boundary_indices.clone().unwrap();
+
+        mesh.color_boundary(boundary_color, interior_color).unwrap();
+        let colors = mesh.colors();
+        assert!(colors.iter().filter(|color| **color == boundary_color).count() == boundary.len());
+        assert!(boundary.iter().all(|vertex| colors[*vertex as usize] == boundary_color));
+        assert!(colors.iter().filter(|color| **color == interior_color).count() == colors.len() - boundary.len());
+
+        // Highlight survives and colors can be put back
+        mesh.highlight_vertex(Some(boundary[0] as usize), [1.0, 1.0, 1.0]).unwrap();
+        mesh.set_colors(&base_colors).unwrap();
+        assert!(mesh.colors() == base_colors);
+        assert!(mesh.vertex_color(boundary[0] as usize) == [1.0, 1.0, 1.0]);
+
+        let mut bar = Mesh::builder("./assets/1dbar.obj").build_mesh_1d(None).unwrap();
+        assert!(matches!(bar.color_boundary(boundary_color, interior_color), Err(Error::BoundaryError(_))));
+    }
+
+    #[test]
+    fn element_sizes_of_graded_mesh() {
+        let graded = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 5, |t| t * t).unwrap().build_mesh_1d(None).unwrap();
+        let nodes = graded.filter_for_solving_1d();
+        let sizes = graded.element_sizes().unwrap();
+
+        assert!(sizes.len() == 4);
+        assert!(sizes.iter().zip(nodes.windows(2)).all(|(size, interval)| (size - (interval[1] - interval[0])).abs() < 1e-12));
+        // Intervals of t² grow as (2i + 1) / 16
+        assert!((graded.min_element_size().unwrap() - 1.0 / 16.0).abs() < 1e-12);
+        assert!((graded.max_element_size().unwrap() - 7.0 / 16.0).abs() < 1e-12);
+
+        // Unit square split in two triangles by its diagonal
+        let square = Mesh::builder("./assets/unit_square.obj").build_mesh_2d().unwrap();
+        assert!(square.element_sizes().unwrap().iter().all(|size| (size - 2_f64.sqrt()).abs() < 1e-12));
+    }
 }
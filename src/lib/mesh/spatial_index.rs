@@ -0,0 +1,158 @@
+// External dependencies
+use cgmath::{InnerSpace, Point3, Vector3};
+use ndarray::Array1;
+
+/// # General Information
+///
+/// Uniform grid over vertex positions of a mesh. Vertices are bucketed by the cell they fall in, so that a query only tests vertices of cells
+/// that may intersect a region instead of every vertex.
+///
+/// # Fields
+///
+/// * `points` - Position of every vertex (first three entries of its sextuple)
+/// * `min` - Corner of grid with smallest coordinates
+/// * `cell_size` - Length of a cell along every axis. Zero on axes where every vertex has the same coordinate
+/// * `cells_per_axis` - Amount of cells along every axis
+/// * `cells` - Indices of vertices inside every cell. Cell (i, j, k) is stored at `i + cells_per_axis[0] * (j + cells_per_axis[1] * k)`
+///
+#[derive(Debug)]
+pub(crate) struct SpatialIndex {
+    points: Vec<Point3<f32>>,
+    min: Point3<f32>,
+    cell_size: Vector3<f32>,
+    cells_per_axis: [usize; 3],
+    cells: Vec<Vec<usize>>,
+}
+
+impl SpatialIndex {
+    /// # General Information
+    ///
+    /// Buckets vertices into a grid with (roughly) `points_per_cell` vertices per cell if they were evenly spread.
+    ///
+    /// # Parameters
+    ///
+    /// * `vertices` - Vertices of a mesh in sextuples (coordinate and color)
+    /// * `points_per_cell` - Desired amount of vertices per cell. Smaller values give more, smaller cells
+    ///
+    pub(crate) fn new(vertices: &Array1<f64>, points_per_cell: usize) -> Self {
+        let points: Vec<Point3<f32>> = vertices
+            .exact_chunks(6)
+            .into_iter()
+            .map(|vertex| Point3::new(vertex[0] as f32, vertex[1] as f32, vertex[2] as f32))
+            .collect();
+
+        let mut min = Point3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = Point3::new(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+        for point in &points {
+            min = Point3::new(min.x.min(point.x), min.y.min(point.y), min.z.min(point.z));
+            max = Point3::new(max.x.max(point.x), max.y.max(point.y), max.z.max(point.z));
+        }
+        if points.is_empty() {
+            min = Point3::new(0.0, 0.0, 0.0);
+            max = min;
+        }
+
+        // Same amount of cells along every axis with some extent
+        let extent = max - min;
+        let extents = [extent.x, extent.y, extent.z];
+        let spread_axes = extents.iter().filter(|e| **e > 0.0).count().max(1) as f64;
+        let cells_per_spread_axis = ((points.len() as f64 / points_per_cell.max(1) as f64).powf(1.0 / spread_axes).ceil() as usize).max(1);
+        let cells_per_axis = extents.map(|e| if e > 0.0 { cells_per_spread_axis } else { 1 });
+        let cell_size = Vector3::new(
+            extent.x / cells_per_axis[0] as f32,
+            extent.y / cells_per_axis[1] as f32,
+            extent.z / cells_per_axis[2] as f32,
+        );
+
+        let mut index = Self {
+            points: vec![],
+            min,
+            cell_size,
+            cells_per_axis,
+            cells: vec![vec![]; cells_per_axis.iter().product()],
+        };
+        for (id, point) in points.iter().enumerate() {
+            let cell = index.cell_of(point);
+            index.cells[cell].push(id);
+        }
+        index.points = points;
+
+        index
+    }
+
+    /// Position of cell containing a point along every axis. Points on the far side of grid belong to last cell.
+    fn cell_of(&self, point: &Point3<f32>) -> usize {
+        let offset = point - self.min;
+        let coordinate = |offset: f32, size: f32, amount: usize| {
+            if size > 0.0 {
+                ((offset / size) as usize).min(amount - 1)
+            } else {
+                0
+            }
+        };
+        let i = coordinate(offset.x, self.cell_size.x, self.cells_per_axis[0]);
+        let j = coordinate(offset.y, self.cell_size.y, self.cells_per_axis[1]);
+        let k = coordinate(offset.z, self.cell_size.z, self.cells_per_axis[2]);
+
+        i + self.cells_per_axis[0] * (j + self.cells_per_axis[1] * k)
+    }
+
+    /// Position of a vertex.
+    pub(crate) fn point(&self, id: usize) -> Point3<f32> {
+        self.points[id]
+    }
+
+    /// # General Information
+    ///
+    /// Indices of vertices inside cells whose bounding sphere is accepted by `may_intersect`, in increasing order.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Index to query
+    /// * `may_intersect` - Receives center and radius of the bounding sphere of a cell. Should return true whenever the region queried could touch it
+    ///
+    pub(crate) fn candidates<F: Fn(Point3<f32>, f32) -> bool>(&self, may_intersect: F) -> Vec<usize> {
+        let radius = (self.cell_size / 2.0).magnitude();
+
+        let mut candidates: Vec<usize> = vec![];
+        for k in 0..self.cells_per_axis[2] {
+            for j in 0..self.cells_per_axis[1] {
+                for i in 0..self.cells_per_axis[0] {
+                    let cell = &self.cells[i + self.cells_per_axis[0] * (j + self.cells_per_axis[1] * k)];
+                    if cell.is_empty() {
+                        continue;
+                    }
+                    let center = self.min + Vector3::new(
+                        self.cell_size.x * (i as f32 + 0.5),
+                        self.cell_size.y * (j as f32 + 0.5),
+                        self.cell_size.z * (k as f32 + 0.5),
+                    );
+                    if may_intersect(center, radius) {
+                        candidates.extend(cell);
+                    }
+                }
+            }
+        }
+        candidates.sort_unstable();
+
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SpatialIndex;
+    use ndarray::Array1;
+
+    #[test]
+    fn every_vertex_is_in_a_single_cell() {
+        let vertices: Vec<f64> = (0..100).flat_map(|i| [(i % 10) as f64, (i / 10) as f64, 0.0, 0.0, 0.0, 1.0]).collect();
+        let index = SpatialIndex::new(&Array1::from_vec(vertices), 4);
+
+        // Flat grid only splits the two axes with extent
+        assert!(index.cells_per_axis == [5, 5, 1]);
+        assert!(index.candidates(|_, _| true) == (0..100).collect::<Vec<usize>>());
+        assert!(index.candidates(|_, _| false).is_empty());
+        assert!(index.cells.iter().map(|cell| cell.len()).sum::<usize>() == 100);
+    }
+}
@@ -8,7 +8,11 @@ pub(crate) mod writer;
 
 // Re-exports
 pub use self::error::Error;
+pub use self::mesh::{mesh_builder::{MeshBuilder, MeshDimension}, Mesh, QualityReport};
 pub use self::simulation::dzahui_window::{DzahuiWindow, DzahuiWindowBuilder};
-pub use self::solvers::euler::EulerSolver;
-pub use self::solvers::diffusion_solver::DiffussionParams;
+pub use self::simulation::camera::{Camera, CameraBuilder};
+pub use self::simulation::render_mode::CullMode;
+pub use self::solvers::euler::{EulerSolver, SystemEulerSolver};
+pub use self::solvers::Scalar;
+pub use self::solvers::diffusion_solver::{DiffussionParams, PoissonParams1D};
 pub use self::solvers::stokes_solver::StokesParams;
@@ -0,0 +1,165 @@
+// Internal dependencies
+use crate::solvers::BoundaryCondition;
+use crate::Error;
+
+// External dependencies
+use std::collections::{HashMap, HashSet};
+
+/// Conditions a boundary vertex goes through when clicked repeatedly. After the last one, vertex is left without condition.
+const CONDITION_CYCLE: [BoundaryCondition; 3] = [
+    BoundaryCondition::Dirichlet(0.0),
+    BoundaryCondition::Dirichlet(1.0),
+    BoundaryCondition::Neumann(0.0),
+];
+
+/// # General Information
+///
+/// State of the interactive boundary-condition editor. While active, clicking a boundary vertex cycles its condition. Conditions are kept
+/// by vertex index for the next time a solver is built.
+///
+/// # Fields
+///
+/// * `active` - Wether clicks edit boundary conditions
+/// * `boundary` - Indices of vertices on the boundary of mesh. Only these accept a condition
+/// * `conditions` - Condition assigned to every edited boundary vertex
+/// * `nodes_1d` - Amount of nodes when mesh is a 1D bar. Both vertices drawn at an end of the bar then stand for the same node
+///
+#[derive(Debug, Default)]
+pub(crate) struct BoundaryEditor {
+    pub(crate) active: bool,
+    boundary: HashSet<usize>,
+    pub(crate) conditions: HashMap<usize, BoundaryCondition>,
+    nodes_1d: Option<usize>,
+}
+
+impl BoundaryEditor {
+    /// Creates an inactive editor with no conditions over the boundary vertices of a mesh. Meshes without boundary indices accept none.
+    pub(crate) fn new(boundary_indices: Option<&Vec<u32>>) -> Self {
+        Self {
+            active: false,
+            boundary: boundary_indices.into_iter().flatten().map(|index| *index as usize).collect(),
+            conditions: HashMap::new(),
+            nodes_1d: None,
+        }
+    }
+
+    /// Creates an inactive editor over the ends of a 1D bar with `nodes` nodes. Bar is drawn with a second copy of every node above
+    /// the first one, so both vertices at each end accept a condition.
+    pub(crate) fn new_1d(nodes: usize) -> Self {
+        Self {
+            active: false,
+            boundary: HashSet::from([0, nodes - 1, nodes, 2 * nodes - 1]),
+            conditions: HashMap::new(),
+            nodes_1d: Some(nodes),
+        }
+    }
+
+    /// Conditions edited on the left and right ends of a 1D bar. Ends not edited (and every end of a mesh which is not 1D) give None.
+    pub(crate) fn conditions_1d(&self) -> [Option<BoundaryCondition>; 2] {
+        match self.nodes_1d {
+            Some(nodes) => [self.conditions.get(&0).copied(), self.conditions.get(&(nodes - 1)).copied()],
+            None => [None, None],
+        }
+    }
+
+    /// Activates or deactivates editing. Returns wether editor is now active.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.active = !self.active;
+        self.active
+    }
+
+    /// # General Information
+    ///
+    /// Assigns a condition to a boundary vertex, replacing the one it had. None removes its condition.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Editor holding conditions
+    /// * `vertex` - Index of vertex. Should be on the boundary
+    /// * `condition` - Condition to assign
+    ///
+    pub(crate) fn assign(&mut self, vertex: usize, condition: Option<BoundaryCondition>) -> Result<(), Error> {
+        if !self.boundary.contains(&vertex) {
+            return Err(Error::BoundaryError(format!("Vertex {} is not on the boundary of mesh", vertex)));
+        }
+        // Upper copy of a 1D node is stored as the node itself
+        let vertex = match self.nodes_1d {
+            Some(nodes) => vertex % nodes,
+            None => vertex,
+        };
+
+        match condition {
+            Some(condition) => self.conditions.insert(vertex, condition),
+            None => self.conditions.remove(&vertex),
+        };
+
+        Ok(())
+    }
+
+    /// Gives a boundary vertex the condition following the one it has in `CONDITION_CYCLE` (the first one when it has none) and returns it.
+    pub(crate) fn cycle(&mut self, vertex: usize) -> Result<Option<BoundaryCondition>, Error> {
+        let stored = match self.nodes_1d {
+            Some(nodes) => vertex % nodes,
+            None => vertex,
+        };
+        let next = match self.conditions.get(&stored) {
+            None => CONDITION_CYCLE.first().copied(),
+            Some(current) => CONDITION_CYCLE
+                .iter()
+                .position(|condition| condition == current)
+                .and_then(|position| CONDITION_CYCLE.get(position + 1))
+                .copied(),
+        };
+        self.assign(vertex, next)?;
+
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BoundaryEditor;
+    use crate::solvers::{diffusion_solver::DiffussionParams, BoundaryCondition, Solver};
+    use crate::Error;
+
+    #[test]
+    fn only_boundary_vertices_take_conditions() {
+        let boundary = vec![0, 1, 2];
+        let mut editor = BoundaryEditor::new(Some(&boundary));
+
+        editor.assign(1, Some(BoundaryCondition::Dirichlet(2.5))).unwrap();
+        assert!(editor.conditions.get(&1) == Some(&BoundaryCondition::Dirichlet(2.5)));
+        assert!(matches!(editor.assign(7, Some(BoundaryCondition::Dirichlet(1.0))), Err(Error::BoundaryError(_))));
+        assert!(editor.conditions.len() == 1);
+
+        // Clicking cycles through conditions and back to none
+        assert!(editor.cycle(0).unwrap() == Some(BoundaryCondition::Dirichlet(0.0)));
+        assert!(editor.cycle(0).unwrap() == Some(BoundaryCondition::Dirichlet(1.0)));
+        assert!(editor.cycle(0).unwrap() == Some(BoundaryCondition::Neumann(0.0)));
+        assert!(editor.cycle(0).unwrap().is_none());
+        assert!(!editor.conditions.contains_key(&0));
+        assert!(editor.cycle(7).is_err());
+    }
+
+    #[test]
+    fn edited_ends_change_rebuilt_solver() {
+        let params = DiffussionParams::time_independent().b(0.0).mu(1.0).boundary_conditions(0.0, 0.0).build();
+        let mut equation = Solver::DiffussionSolverTimeIndependent(params);
+        let mesh = vec![0_f64, 0.25, 0.5, 0.75, 1_f64];
+        let mut editor = BoundaryEditor::new_1d(mesh.len());
+
+        // Upper vertex of left end (5) and lower vertex of right end (4), cycled to Dirichlet(1.0) and Dirichlet(0.0)
+        editor.cycle(5).unwrap();
+        editor.cycle(5).unwrap();
+        editor.cycle(4).unwrap();
+        assert!(editor.conditions_1d() == [Some(BoundaryCondition::Dirichlet(1.0)), Some(BoundaryCondition::Dirichlet(0.0))]);
+        assert!(editor.cycle(2).is_err());
+
+        equation.set_boundary_conditions(editor.conditions_1d()).unwrap();
+        let solution = equation.rebuild_solver(mesh, 20).unwrap().solve(0.0).unwrap();
+        assert!((solution[0] - 1.0).abs() < 1e-12 && solution[4].abs() < 1e-12);
+        assert!((solution[2] - 0.5).abs() < 1e-10);
+
+        assert!(BoundaryEditor::new(None).conditions_1d() == [None, None]);
+    }
+}
@@ -1,8 +1,13 @@
 // External dependencies
-use cgmath::{InnerSpace, Matrix4, Point2, Point3, Transform, Vector3, Vector4};
-use ndarray::{Array1, ArrayView1, Axis};
+use cgmath::{EuclideanSpace, InnerSpace, Matrix4, Point2, Point3, Transform, Vector3, Vector4};
+use ndarray::Array1;
 
-use crate::Error;
+use crate::{mesh::spatial_index::SpatialIndex, Error};
+
+/// Narrowest angle (in degrees) a cone can have
+const MIN_ANGLE: f32 = 0.5;
+/// Widest angle (in degrees) a cone can have
+const MAX_ANGLE: f32 = 45.0;
 
 /// # General Information
 /// 
@@ -26,6 +31,65 @@ pub(crate) struct Cone {
     angle: f32,
 }
 
+/// # General Information
+///
+/// Picking ray under a viewport position, in view coordinates. Viewport coordinates are mapped to normalized device coordinates on near and far planes
+/// and unprojected; the ray starts on the near plane and points towards the far one.
+///
+/// # Parameters
+///
+/// * `mouse_coordinates` - viewport coordinates of ray.
+/// * `projection_matrix` - camera projection matrix to find reverse transformation.
+/// * `window_width` - original window width needed to normalize viewport coordinates
+/// * `window_height` - original window height needed to normalize viewport coordinates
+///
+pub(crate) fn screen_ray(
+    mouse_coordinates: &Point2<f32>,
+    projection_matrix: &Matrix4<f32>,
+    window_width: u32,
+    window_height: u32,
+) -> Result<(Point3<f32>, Vector3<f32>), Error> {
+    // Ray from position of mouse
+    let near_ndc_coordinates = Vector4::new(
+        (mouse_coordinates.x - (window_width as f32) / 2.0) / ((window_width as f32) / 2.0), // map between -1 and 1
+        -(mouse_coordinates.y - (window_height as f32) / 2.0) / ((window_height as f32) / 2.0),
+        -1.0, // near plane
+        1.0,
+    );
+    let far_ndc_coordinates = Vector4::new(
+        (mouse_coordinates.x - (window_width as f32) / 2.0) / ((window_width as f32) / 2.0), // map between -1 and 1
+        -(mouse_coordinates.y - (window_height as f32) / 2.0) / ((window_height as f32) / 2.0),
+        1.0, // far plane
+        1.0,
+    );
+
+    let inverse_projection_matrix: Matrix4<f32> = projection_matrix
+        .inverse_transform().ok_or(Error::Matrix("Could not create inverse transformation matrix for vertex selector"))?;
+    let near_view_coordinates = inverse_projection_matrix * near_ndc_coordinates;
+    let far_view_coordinates = inverse_projection_matrix * far_ndc_coordinates;
+
+    // need to divide by w (god knows why)
+    let near_view_coordinates = Vector3::new(
+        near_view_coordinates.x,
+        near_view_coordinates.y,
+        near_view_coordinates.z,
+    ) / near_view_coordinates.w;
+    let far_view_coordinates = Vector3::new(
+        far_view_coordinates.x,
+        far_view_coordinates.y,
+        far_view_coordinates.z,
+    ) / far_view_coordinates.w;
+
+    let anchorage_point: Point3<f32> = Point3::new(
+        near_view_coordinates.x,
+        near_view_coordinates.y,
+        near_view_coordinates.z,
+    );
+    let direction: Vector3<f32> = (far_view_coordinates - near_view_coordinates).normalize();
+
+    Ok((anchorage_point, direction))
+}
+
 impl Cone {
     /// Function to create new instance. Normalizes direction vector.
     pub(crate) fn new(anchorage_point: Point3<f32>, direction: Vector3<f32>, angle: f32) -> Cone {
@@ -37,6 +101,16 @@ impl Cone {
         }
     }
 
+    /// Angle (in degrees) the cone is open.
+    pub(crate) fn angle(&self) -> f32 {
+        self.angle
+    }
+
+    /// Changes how much the cone is open. Angle (in degrees) is kept between 0.5 and 45.
+    pub(crate) fn set_angle(&mut self, angle: f32) {
+        self.angle = angle.clamp(MIN_ANGLE, MAX_ANGLE);
+    }
+
     /// # General Information
     ///
     /// Change cone given mouse input.
@@ -56,43 +130,8 @@ impl Cone {
         window_width: u32,
         window_height: u32,
     ) -> Result<(),Error> {
-        // Create cone from position of mouse
-        let near_ndc_coordinates = Vector4::new(
-            (mouse_coordinates.x - (window_width as f32) / 2.0) / ((window_width as f32) / 2.0), // map between -1 and 1
-            -(mouse_coordinates.y - (window_height as f32) / 2.0) / ((window_height as f32) / 2.0),
-            -1.0, // near plane
-            1.0,
-        );
-        let far_ndc_coordinates = Vector4::new(
-            (mouse_coordinates.x - (window_width as f32) / 2.0) / ((window_width as f32) / 2.0), // map between -1 and 1
-            -(mouse_coordinates.y - (window_height as f32) / 2.0) / ((window_height as f32) / 2.0),
-            1.0, // far plane
-            1.0,
-        );
-
-        let inverse_projection_matrix: Matrix4<f32> = projection_matrix
-            .inverse_transform().ok_or(Error::Matrix("Could not create inverse transformation matrix for vertex selector"))?;
-        let near_view_coordinates = inverse_projection_matrix * near_ndc_coordinates;
-        let far_view_coordinates = inverse_projection_matrix * far_ndc_coordinates;
-
-        // need to divide by w (god knows why)
-        let near_view_coordinates = Vector3::new(
-            near_view_coordinates.x,
-            near_view_coordinates.y,
-            near_view_coordinates.z,
-        ) / near_view_coordinates.w;
-        let far_view_coordinates = Vector3::new(
-            far_view_coordinates.x,
-            far_view_coordinates.y,
-            far_view_coordinates.z,
-        ) / far_view_coordinates.w;
-
-        let anchorage_point: Point3<f32> = Point3::new(
-            near_view_coordinates.x,
-            near_view_coordinates.y,
-            near_view_coordinates.z,
-        );
-        let direction: Vector3<f32> = (far_view_coordinates - near_view_coordinates).normalize();
+        let (anchorage_point, direction) =
+            screen_ray(mouse_coordinates, projection_matrix, window_width, window_height)?;
 
         self.anchorage_point = anchorage_point;
         self.direction = direction;
@@ -100,6 +139,70 @@ impl Cone {
         Ok(())
     }
 
+    /// # General Information
+    ///
+    /// Segments to visualize the vertex selector in world coordinates: the picking ray, going from camera through the point under the mouse,
+    /// and the cone's axis, going from its anchorage point along its direction. Both segments are `length` long.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Cone, normally after being changed from mouse position
+    /// * `view_matrix` - Camera view matrix. Its inverse takes the cone (which lives in view coordinates) to world coordinates
+    /// * `length` - Length of both segments
+    ///
+    pub(crate) fn debug_segments(&self, view_matrix: &Matrix4<f32>, length: f32) -> Result<[[Point3<f32>; 2]; 2], Error> {
+        let inverse_view_matrix: Matrix4<f32> = view_matrix
+            .inverse_transform().ok_or(Error::Matrix("Could not create inverse view matrix for vertex selector"))?;
+
+        // Camera sits at the origin of view coordinates
+        let camera = inverse_view_matrix.transform_point(Point3::new(0.0, 0.0, 0.0));
+        let anchorage_point = inverse_view_matrix.transform_point(self.anchorage_point);
+        let ray_direction = (anchorage_point - camera).normalize();
+        let axis_direction = inverse_view_matrix.transform_vector(self.direction).normalize();
+
+        Ok([
+            [camera, camera + ray_direction * length],
+            [anchorage_point, anchorage_point + axis_direction * length],
+        ])
+    }
+
+    /// # General Information
+    ///
+    /// Outline of the cone in world coordinates, to be drawn as a single line strip: two opposite generators meeting at the anchorage point
+    /// followed by the circle where the cone ends, `length` away from its anchorage point.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Cone, normally after being changed from mouse position
+    /// * `view_matrix` - Camera view matrix. Its inverse takes the cone (which lives in view coordinates) to world coordinates
+    /// * `length` - Distance along the axis at which the cone ends
+    /// * `segments` - Amount of segments approximating the circle. Should be even so that generators are opposite
+    ///
+    pub(crate) fn outline(&self, view_matrix: &Matrix4<f32>, length: f32, segments: usize) -> Result<Vec<Point3<f32>>, Error> {
+        let inverse_view_matrix: Matrix4<f32> = view_matrix
+            .inverse_transform().ok_or(Error::Matrix("Could not create inverse view matrix for vertex selector"))?;
+
+        // Two directions perpendicular to axis span the circle
+        let helper = if self.direction.x.abs() < 0.9 { Vector3::unit_x() } else { Vector3::unit_y() };
+        let first = self.direction.cross(helper).normalize();
+        let second = self.direction.cross(first);
+
+        let center = self.anchorage_point + self.direction * length;
+        let radius = length * self.angle.to_radians().tan();
+        let circle: Vec<Point3<f32>> = (0..=segments)
+            .map(|i| {
+                let theta = 2.0 * std::f32::consts::PI * (i as f32) / (segments as f32);
+                center + (first * theta.cos() + second * theta.sin()) * radius
+            })
+            .collect();
+
+        Ok([circle[segments / 2], self.anchorage_point]
+            .into_iter()
+            .chain(circle)
+            .map(|point| inverse_view_matrix.transform_point(point))
+            .collect())
+    }
+
     #[allow(dead_code)]
     /// Matrix to translate vertex to a given location (normally determined by a mesh instance).
     fn get_translation_matrix(arr: &Array1<f32>) -> Matrix4<f32> {
@@ -108,15 +211,99 @@ impl Cone {
     }
 
     /// Obtain center coordinates as viewed from camera
-    fn get_view_center(arr: &ArrayView1<f64>, view_matrix: &Matrix4<f32>) -> Vector3<f32> {
-        let vec_arr = Vector4::new(arr[0] as f32, arr[1] as f32, arr[2] as f32, 1.0);
+    fn get_view_center(point: &Point3<f32>, view_matrix: &Matrix4<f32>) -> Vector3<f32> {
+        let vec_arr = Vector4::new(point.x, point.y, point.z, 1.0);
         let view_center = view_matrix * vec_arr;
         Vector3::new(view_center.x, view_center.y, view_center.z)
     }
 
+    /// Wether a point (in view coordinates) is inside the cone and no farther than 100 units from its anchorage point along z.
+    fn contains(&self, view_center: &Vector3<f32>) -> bool {
+        let x = view_center.x;
+        let y = view_center.y;
+        let z = view_center.z;
+        // filters
+        let mut is_z_in_range = z < self.anchorage_point.z;
+        is_z_in_range = is_z_in_range && z > self.anchorage_point.z - 100.0;
+
+        // obtaining values for circle center of cone
+        // first obtain t from equation f(t) = p + tv
+        // z direction can never be zero
+        let curve_value_from_z = (z - self.anchorage_point.z) / self.direction.z;
+        // then obtain x and y from t
+        // this generates circle center
+        let c_x = self.anchorage_point.x + curve_value_from_z * self.direction.x;
+        let c_y = self.anchorage_point.y + curve_value_from_z * self.direction.y;
+        // obtain radius of circunference via angle and distance to anchorage point
+        let c_r = ((c_x - self.anchorage_point.x).powf(2.0)
+            + (c_y - self.anchorage_point.y).powf(2.0)
+            + (z - self.anchorage_point.z).powf(2.0))
+        .sqrt()
+            * self.angle.to_radians().tan();
+
+        // check inequalities for circle
+        let circle_ineq = (c_x - x).powf(2.0) + (c_y - y).powf(2.0) <= c_r.powf(2.0);
+
+        is_z_in_range && circle_ineq
+    }
+
+    /// # General Information
+    ///
+    /// Conservative test of wether a sphere (in view coordinates) may hold a point accepted by `contains`. False only when it surely does not.
+    /// A point accepted lies in a ball of radius `t tan(angle)` around the axis point `t` away from anchorage point, so it is never farther than
+    /// `asin(tan(angle))` from the axis line. A sphere is then kept when its angular distance to that line, reduced by its angular radius, is within that bound.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Cone to test against
+    /// * `center` - Center of sphere in view coordinates
+    /// * `radius` - Radius of sphere
+    ///
+    fn may_contain_sphere(&self, center: &Vector3<f32>, radius: f32) -> bool {
+        // Small slack so that rounding never discards a point `contains` accepts
+        let slack = 1e-3;
+
+        if center.z - radius >= self.anchorage_point.z + slack || center.z + radius <= self.anchorage_point.z - 100.0 - slack {
+            return false;
+        }
+
+        let to_center = center - self.anchorage_point.to_vec();
+        let distance = to_center.magnitude();
+        if distance <= radius {
+            return true;
+        }
+
+        let spread = self.angle.to_radians().tan().min(1.0).asin();
+        let angular_radius = (radius / distance).asin();
+        let angle_to_axis = (to_center.dot(self.direction).abs() / distance).min(1.0).acos();
+
+        angle_to_axis <= spread + angular_radius + slack
+    }
+
+    /// Closest vertex (along z in view coordinates) to anchorage point among the ones inside cone, with its distance.
+    fn nearest<I: Iterator<Item = (usize, Point3<f32>)>>(&self, points: I, view_matrix: &Matrix4<f32>) -> Option<(f32, usize)> {
+        points
+            .filter_map(|(id, point)| {
+                let view_center = Cone::get_view_center(&point, view_matrix);
+                if self.contains(&view_center) {
+                    Some(((view_center.z - self.anchorage_point.z).abs(), id))
+                } else {
+                    None
+                }
+            })
+            .reduce(|(past_distance, past_id), (new_distance, new_id)| {
+                if new_distance < past_distance {
+                    (new_distance, new_id)
+                } else {
+                    (past_distance, past_id)
+                }
+            })
+    }
+
     /// # General Information
     ///
-    /// Determine closest intersection given some vertices (sextuples of points) and current cone status. Only one vertex is returned with id.
+    /// Determine closest intersection given some vertices (sextuples of points) and current cone status. Only one vertex is returned with its id
+    /// (position of its sextuple). Every vertex is tested.
     ///
     /// # Parameters
     ///
@@ -129,56 +316,127 @@ impl Cone {
         vertices: &Array1<f64>,
         view_matrix: &Matrix4<f32>,
     ) -> Option<(f32, usize)> {
-        // Filter objects to only those that are partially or completelly inside cone
-        let dim_1 = vertices.len() / 3;
-        let reshaped_vertices = vertices.to_shared().reshape((dim_1, 3));
-        let filtered_objects: Vec<ArrayView1<f64>> = reshaped_vertices
-            .axis_iter(Axis(0))
-            .filter(|vertex| {
-                let view_center = Cone::get_view_center(vertex, view_matrix);
-                let x = view_center.x;
-                let y = view_center.y;
-                let z = view_center.z;
-                // filters
-                let mut is_z_in_range = z < self.anchorage_point.z;
-                is_z_in_range = is_z_in_range && z > self.anchorage_point.z - 100.0;
-
-                // obtaining values for circle center of cone
-                // first obtain t from equation f(t) = p + tv
-                // z direction can never be zero
-                let curve_value_from_z = (z - self.anchorage_point.z) / self.direction.z;
-                // then obtain x and y from t
-                // this generates circle center
-                let c_x = self.anchorage_point.x + curve_value_from_z * self.direction.x;
-                let c_y = self.anchorage_point.y + curve_value_from_z * self.direction.y;
-                // obtain radius of circunference via angle and distance to anchorage point
-                let c_r = ((c_x - self.anchorage_point.x).powf(2.0)
-                    + (c_y - self.anchorage_point.y).powf(2.0)
-                    + (z - self.anchorage_point.z).powf(2.0))
-                .sqrt()
-                    * self.angle.to_radians().tan();
-
-                // check inequalities for circle
-                let circle_ineq = (c_x - x).powf(2.0) + (c_y - y).powf(2.0) <= c_r.powf(2.0);
-
-                is_z_in_range && circle_ineq
-            })
+        let points = vertices
+            .exact_chunks(6)
+            .into_iter()
+            .map(|vertex| Point3::new(vertex[0] as f32, vertex[1] as f32, vertex[2] as f32))
+            .enumerate();
+
+        self.nearest(points, view_matrix)
+    }
+
+    /// # General Information
+    ///
+    /// Same as `obtain_nearest_intersection`, but only vertices in cells of `index` that may intersect the cone are tested. Result is the same.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - To determine cone location.
+    /// * `index` - Spatial index over vertices of a mesh
+    /// * `view_matrix` - Camera view matrix needed to see where a vertex is (in view space). Should not scale, so that cell radii are kept
+    ///
+    pub(crate) fn obtain_nearest_intersection_indexed(
+        &self,
+        index: &SpatialIndex,
+        view_matrix: &Matrix4<f32>,
+    ) -> Option<(f32, usize)> {
+        let candidates = index.candidates(|center, radius| {
+            self.may_contain_sphere(&Cone::get_view_center(&center, view_matrix), radius)
+        });
+
+        self.nearest(candidates.into_iter().map(|id| (id, index.point(id))), view_matrix)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{screen_ray, Cone};
+    use crate::mesh::spatial_index::SpatialIndex;
+    use ndarray::Array1;
+    use crate::simulation::camera::Camera;
+    use cgmath::{InnerSpace, Matrix4, Point2, Point3, SquareMatrix, Transform, Vector3, Vector4};
+
+    #[test]
+    fn picking_ray_starts_at_camera_and_goes_through_mouse() {
+        let (width, height) = (800, 600);
+        let camera = Camera::builder().build(2.0, height, width);
+        let mouse = Point2::new(600.0, 150.0);
+
+        let mut cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0), 3.0);
+        cone.change_from_mouse_position(&mouse, &camera.projection_matrix, width, height).unwrap();
+        let [ray, _axis] = cone.debug_segments(&camera.view_matrix, 10.0).unwrap();
+
+        // Ray starts at camera
+        assert!((ray[0] - camera.camera_position).magnitude() < 1e-3);
+
+        // Mouse unprojected on near plane (y grows downwards on viewport) lies on ray
+        let ndc = Vector4::new(0.5, 0.5, -1.0, 1.0);
+        let inverse = (camera.projection_matrix * camera.view_matrix).inverse_transform().unwrap();
+        let world = inverse * ndc;
+        let world = Point3::new(world.x, world.y, world.z) / world.w;
+        let ray_direction = (ray[1] - ray[0]).normalize();
+        let to_mouse = (world - ray[0]).normalize();
+        assert!((ray_direction - to_mouse).magnitude() < 1e-3);
+
+        // Screen ray (view coordinates) of the center of the screen goes straight forward
+        let (_, direction) = screen_ray(&Point2::new(400.0, 300.0), &camera.projection_matrix, width, height).unwrap();
+        assert!((direction - Vector3::new(0.0, 0.0, -1.0)).magnitude() < 1e-3);
+    }
+
+    #[test]
+    fn angle_changes_candidates() {
+        let mut cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, -1.0), 3.0);
+        // About 5.7 degrees away from the axis
+        let vertices = Array1::from_vec(vec![1.0, 0.0, -10.0, 0.0, 0.0, 1.0]);
+        let view_matrix = Matrix4::identity();
+
+        assert!(cone.obtain_nearest_intersection(&vertices, &view_matrix).is_none());
+
+        cone.set_angle(10.0);
+        assert!(cone.angle() == 10.0);
+        assert!(cone.obtain_nearest_intersection(&vertices, &view_matrix).is_some());
+
+        cone.set_angle(90.0);
+        assert!(cone.angle() == 45.0);
+
+        // Outline starts and ends on the circle, goes through anchorage point and ends 'length' away from it
+        let outline = cone.outline(&view_matrix, 10.0, 8).unwrap();
+        assert!(outline.len() == 11);
+        assert!(outline[1] == Point3::new(0.0, 0.0, 0.0));
+        assert!(outline.iter().skip(2).all(|point| (point.z + 10.0).abs() < 1e-4));
+        assert!(outline.iter().skip(2).all(|point| ((point.x.powi(2) + point.y.powi(2)).sqrt() - 10.0).abs() < 1e-3));
+    }
+
+    #[test]
+    fn indexed_picking_matches_brute_force() {
+        // Pseudo-random vertices in a 10 x 10 x 10 box
+        let mut seed: u64 = 12345;
+        let mut random = move || {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) as f64 / (1u64 << 31) as f64
+        };
+        let vertices: Vec<f64> = (0..2000)
+            .flat_map(|_| [10.0 * random() - 5.0, 10.0 * random() - 5.0, 10.0 * random() - 5.0, 0.0, 0.0, 1.0])
             .collect();
+        let vertices = Array1::from_vec(vertices);
+        let index = SpatialIndex::new(&vertices, 8);
 
-        // Obtain sphere closest to anchorage point
-        filtered_objects
-            .iter()
-            .enumerate()
-            .map(|(id, vertex)| {
-                let view_center_z = Cone::get_view_center(vertex, view_matrix).z;
-                ((view_center_z - self.anchorage_point.z).abs(), id)
-            })
-            .reduce(|(past_distance, past_id), (new_distance, new_id)| {
-                if new_distance < past_distance {
-                    (new_distance, new_id)
-                } else {
-                    (past_distance, past_id)
-                }
-            })
+        let (width, height) = (800, 600);
+        let camera = Camera::builder().build(10.0, height, width);
+        let mut cone = Cone::new(Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 0.0, 1.0), 3.0);
+
+        let mut found = 0;
+        for i in 0..50 {
+            let mouse = Point2::new(300.0 + 4.0 * i as f32, 200.0 + 4.0 * ((7 * i) % 50) as f32);
+            cone.change_from_mouse_position(&mouse, &camera.projection_matrix, width, height).unwrap();
+            cone.set_angle(0.5 + (i % 10) as f32);
+
+            let brute_force = cone.obtain_nearest_intersection(&vertices, &camera.view_matrix);
+            let indexed = cone.obtain_nearest_intersection_indexed(&index, &camera.view_matrix);
+            assert!(brute_force == indexed);
+            found += brute_force.is_some() as usize;
+        }
+        // Comparison is meaningful: picks actually hit vertices
+        assert!(found > 0);
     }
 }
@@ -4,6 +4,10 @@ pub mod cone;
 // External dependencies
 use cgmath::{self, Deg, Matrix4, Point3, Vector3};
 
+/// Time (in seconds) for camera to cover most of the motion given by mouse. Motion is released exponentially with this constant,
+/// so the result depends only on elapsed time and not on how many frames are drawn.
+const CAMERA_SMOOTHING_TIME: f32 = 0.05;
+
 
 /// # General Information
 ///
@@ -21,8 +25,11 @@ use cgmath::{self, Deg, Matrix4, Point3, Vector3};
 /// * `theta` - y axis - position angle to move camera.
 /// * `phi` - xz plane - position angle to move camera.
 /// * `radius` - how far away camera is from object.
+/// * `pending_motion` - Mouse motion (x,y) not yet applied to camera. Released every frame according to delta time.
+/// * `fov` - Field of view (vertical, in degrees) of projection matrix.
+/// * `aspect_ratio` - Width over height of viewport used in projection matrix.
 ///
-#[derive(Debug)]pub(crate) struct Camera {
+#[derive(Debug)]pub struct Camera {
     pub(crate) camera_position: Point3<f32>,
     camera_target: Point3<f32>,
     pub(crate) view_matrix: Matrix4<f32>,
@@ -33,6 +40,9 @@ use cgmath::{self, Deg, Matrix4, Point3, Vector3};
     pub(crate) theta: f32,
     pub(crate) phi: f32,
     pub(crate) radius: f32,
+    pub(crate) pending_motion: (f32, f32),
+    fov: f32,
+    aspect_ratio: f32,
 }
 
 /// # General Information
@@ -48,6 +58,7 @@ use cgmath::{self, Deg, Matrix4, Point3, Vector3};
 /// * `fov` - Field of view of projection matrix.
 /// * `camera_sensitivity` - Speed at which camera moves arround target (in a sphere).
 /// * `camera_target` - Point at which camera is looking.
+/// * `near_far` - Distances from camera to near and far planes of projection.
 ///
 #[derive(Default, Debug)]
 pub struct CameraBuilder {
@@ -57,6 +68,7 @@ pub struct CameraBuilder {
     fov: Option<f32>,
     camera_sensitivity: Option<f32>,
     camera_target: Option<Point3<f32>>,
+    near_far: Option<(f32, f32)>,
 }
 
 impl CameraBuilder {
@@ -69,6 +81,7 @@ impl CameraBuilder {
             fov: None,
             camera_sensitivity: None,
             camera_target: None,
+            near_far: None,
         }
     }
     /// Changes distance (radius) to object centered
@@ -107,6 +120,16 @@ impl CameraBuilder {
             ..self
         }
     }
+    /// Changes near and far planes of projection. Both have to be positive and near has to be smaller than far
+    pub fn with_near_far(self, near: f32, far: f32) -> Self {
+        if near <= 0.0 || near >= far {
+            panic!("Near and far planes should satisfy 0 < near < far!");
+        }
+        CameraBuilder {
+            near_far: Some((near, far)),
+            ..self
+        }
+    }
     /// # General Information
     ///
     /// Builds a Camera from parameters given.
@@ -158,13 +181,11 @@ impl CameraBuilder {
 
         // After obtaining values from builder:
         // The easier values to obtain from mesh are near and far
-        // near is obtained from max_length ( or radius )
-        let mut near = radius - 50.0;
-        if near <= 0.0 {
-            near = 0.1;
-        }
-        // far is always 100 away from near
-        let far = near + 100.0;
+        let (near, far) = if let Some(near_far) = self.near_far {
+            near_far
+        } else {
+            default_near_far(radius, mesh_length)
+        };
         // Aspect ratio is obtained from height and width of viewport
         let aspect_ratio: f32 = width as f32 / height as f32;
         // Camera position is given by theta and phi (since it's a sphere)
@@ -192,19 +213,202 @@ impl CameraBuilder {
             view_matrix,
             active_view_change,
             camera_sensitivity,
+            pending_motion: (0.0, 0.0),
+            fov,
+            aspect_ratio,
         }
     }
 }
 
+/// Near and far planes such that a mesh of length `mesh_length` centered at target is never clipped.
+/// Mesh is contained in a sphere of diameter `mesh_length` around target, so it always lies between `radius - mesh_length` and `radius + mesh_length`.
+/// Near is kept away from zero to not waste depth precision.
+fn default_near_far(radius: f32, mesh_length: f32) -> (f32, f32) {
+    let near = (radius - mesh_length).max(radius * 0.01).max(0.1);
+    let far = radius + mesh_length;
+    (near, far)
+}
+
 impl Camera {
     /// Create a camera builder.
     pub fn builder() -> CameraBuilder {
         CameraBuilder::new()
     }
 
+    /// Copy of current view matrix. Together with `projection_matrix` it allows drawing external overlays aligned with the scene.
+    pub fn view_matrix(&self) -> Matrix4<f32> {
+        self.view_matrix
+    }
+
+    /// Copy of current projection matrix.
+    pub fn projection_matrix(&self) -> Matrix4<f32> {
+        self.projection_matrix
+    }
+
     /// Change view matrix associated to camera.
     pub(crate) fn modify_view_matrix(&mut self) {
         self.view_matrix =
             Matrix4::look_at_rh(self.camera_position, self.camera_target, self.up_vector);
     }
+
+    /// Places camera on sphere of radius `radius` centered at `camera_target` according to `theta` and `phi`.
+    pub(crate) fn update_position(&mut self) {
+        self.camera_position = Point3::new(
+            self.theta.to_radians().sin() * self.phi.to_radians().sin(),
+            self.theta.to_radians().cos(),
+            self.theta.to_radians().sin() * self.phi.to_radians().cos(),
+        ) * self.radius
+            + Vector3::new(self.camera_target.x, self.camera_target.y, self.camera_target.z);
+    }
+
+    /// # General Information
+    ///
+    /// Points camera to `target` from `distance` away, keeping current angles (theta and phi). View matrix is rebuilt.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Camera to move
+    /// * `target` - New point camera looks at. Orbiting continues around it.
+    /// * `distance` - New distance from camera to target
+    ///
+    pub fn look_at(&mut self, target: Point3<f32>, distance: f32) {
+        self.camera_target = target;
+        self.radius = distance;
+        self.update_position();
+        self.modify_view_matrix();
+    }
+
+    /// # General Information
+    ///
+    /// Looks at the center of the box with opposite corners `min` and `max` from a distance at which the sphere containing the box fits
+    /// inside field of view (on both directions). Projection matrix is rebuilt so that near and far planes do not clip the box.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Camera to move
+    /// * `min` - Corner of box with smallest coordinates
+    /// * `max` - Corner of box with biggest coordinates
+    ///
+    pub fn frame_bounds(&mut self, min: Point3<f32>, max: Point3<f32>) {
+        let target = Point3::new((min.x + max.x) / 2.0, (min.y + max.y) / 2.0, (min.z + max.z) / 2.0);
+        let bounding_radius = ((max.x - min.x).powi(2) + (max.y - min.y).powi(2) + (max.z - min.z).powi(2)).sqrt() / 2.0;
+
+        // Narrowest half angle between vertical and horizontal fov
+        let vertical_half_fov = self.fov.to_radians() / 2.0;
+        let horizontal_half_fov = (vertical_half_fov.tan() * self.aspect_ratio).atan();
+        let half_fov = vertical_half_fov.min(horizontal_half_fov);
+
+        // A sphere is inside a cone with half angle `half_fov` whenever its center is at least radius / sin(half_fov) away
+        let distance = (bounding_radius / half_fov.sin()).max(f32::EPSILON);
+
+        let (near, far) = default_near_far(distance, bounding_radius * 2.0);
+        self.projection_matrix = cgmath::perspective(Deg(self.fov), self.aspect_ratio, near, far);
+        self.look_at(target, distance);
+    }
+
+    /// Adds mouse motion to be applied on following frames.
+    pub(crate) fn add_motion(&mut self, x: f32, y: f32) {
+        self.pending_motion.0 += x;
+        self.pending_motion.1 += y;
+    }
+
+    /// # General Information
+    ///
+    /// Obtains portion of pending mouse motion to be applied in a frame that lasted `dt` seconds, and removes it from pending motion.
+    /// Portion released is `1 - exp(-dt / CAMERA_SMOOTHING_TIME)`, therefore, the same motion over the same time produces the same change
+    /// regardless of framerate.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Camera with pending motion
+    /// * `dt` - Time (in seconds) since last frame
+    ///
+    pub(crate) fn take_motion(&mut self, dt: f32) -> (f32, f32) {
+        let released = 1.0 - (-dt / CAMERA_SMOOTHING_TIME).exp();
+        let motion = (self.pending_motion.0 * released, self.pending_motion.1 * released);
+        self.pending_motion.0 -= motion.0;
+        self.pending_motion.1 -= motion.1;
+        motion
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use cgmath::{AbsDiffEq, Deg, Matrix4, Point3, Vector3, Vector4};
+
+    use super::{default_near_far, Camera};
+
+    #[test]
+    fn motion_does_not_depend_on_framerate() {
+        let mut slow_camera = Camera::builder().build(10.0, 600, 800);
+        let mut fast_camera = Camera::builder().build(10.0, 600, 800);
+        slow_camera.add_motion(30.0, -12.0);
+        fast_camera.add_motion(30.0, -12.0);
+
+        // same wall-clock time (0.1 s) at 10 and at 100 frames per second
+        let slow_change = slow_camera.take_motion(0.1);
+        let mut fast_change = (0.0, 0.0);
+        for _ in 0..10 {
+            let (x, y) = fast_camera.take_motion(0.01);
+            fast_change = (fast_change.0 + x, fast_change.1 + y);
+        }
+
+        assert!((slow_change.0 - fast_change.0).abs() < 1e-4);
+        assert!((slow_change.1 - fast_change.1).abs() < 1e-4);
+        // most of the motion has been applied after 0.1 s
+        assert!(slow_change.0 > 25.0);
+    }
+
+    #[test]
+    fn large_mesh_is_within_frustum() {
+        let mesh_length = 500.0;
+        let camera = Camera::builder().build(mesh_length, 600, 800);
+        let (near, far) = default_near_far(camera.radius, mesh_length);
+
+        // Farthest and nearest points of mesh from camera
+        assert!(far >= camera.radius + mesh_length / 2.0);
+        assert!(near <= camera.radius - mesh_length / 2.0);
+        assert!(near > 0.0 && near < far);
+
+        let builder = Camera::builder().with_near_far(1.0, 20.0);
+        assert!(builder.near_far == Some((1.0, 20.0)));
+    }
+
+    #[test]
+    fn framed_box_is_inside_ndc() {
+        let mut camera = Camera::builder().with_camera_position(60.0, 30.0).build(1.0, 600, 800);
+        let min = Point3::new(2.0, -1.0, 5.0);
+        let max = Point3::new(10.0, 3.0, 6.0);
+        camera.frame_bounds(min, max);
+
+        for corner in 0..8 {
+            let point = Vector4::new(
+                if corner & 1 == 0 { min.x } else { max.x },
+                if corner & 2 == 0 { min.y } else { max.y },
+                if corner & 4 == 0 { min.z } else { max.z },
+                1.0,
+            );
+            let clip = camera.projection_matrix * camera.view_matrix * point;
+            let ndc = [clip.x / clip.w, clip.y / clip.w, clip.z / clip.w];
+            assert!(clip.w > 0.0);
+            assert!(ndc.iter().all(|c| c.abs() <= 1.0));
+        }
+    }
+
+    #[test]
+    fn public_matrices_match_camera_parameters() {
+        let camera = Camera::builder()
+            .change_distance_to_object(5.0)
+            .with_camera_position(90.0, 90.0)
+            .with_fov(60.0)
+            .with_near_far(1.0, 10.0)
+            .build(1.0, 600, 800);
+
+        // theta = 90 and phi = 90 place camera on x axis
+        let view_matrix = Matrix4::look_at_rh(Point3::new(5.0, 0.0, 0.0), Point3::new(0.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        let projection_matrix = cgmath::perspective(Deg(60.0), 800.0 / 600.0, 1.0, 10.0);
+
+        assert!(camera.view_matrix().abs_diff_eq(&view_matrix, 1e-5));
+        assert!(camera.projection_matrix() == projection_matrix);
+    }
 }
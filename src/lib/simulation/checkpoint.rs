@@ -0,0 +1,147 @@
+// Internal dependencies
+use crate::mesh::MeshSnapshot;
+use crate::solvers::{solver_trait::SolverCheckpoint, DiffussionParamsTimeDependent, DiffussionParamsTimeIndependent, Solver};
+use crate::Error;
+
+// External dependencies
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
+
+/// # General Information
+///
+/// Params of an equation that can be written to a checkpoint. Equations with params given as functions (Poisson and Stokes) can not.
+///
+/// # Arms
+///
+/// * `DiffussionTimeIndependent` - Params of diffusion equation with time-independence
+/// * `DiffussionTimeDependent` - Params of diffusion equation with time-dependence. Boundary values given as functions of time are lost
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) enum SolverParams {
+    DiffussionTimeIndependent(DiffussionParamsTimeIndependent),
+    DiffussionTimeDependent(DiffussionParamsTimeDependent),
+}
+
+impl SolverParams {
+    /// Params of an equation. None when equation has none or they can not be written.
+    pub(crate) fn from_solver(solver: &Solver) -> Option<Self> {
+        match solver {
+            Solver::DiffussionSolverTimeIndependent(params) => Some(SolverParams::DiffussionTimeIndependent(params.clone())),
+            Solver::DiffussionSolverTimeDependent(params) => Some(SolverParams::DiffussionTimeDependent(params.clone())),
+            _ => None,
+        }
+    }
+
+    /// Equation these params belong to.
+    pub(crate) fn into_solver(self) -> Solver {
+        match self {
+            SolverParams::DiffussionTimeIndependent(params) => Solver::DiffussionSolverTimeIndependent(params),
+            SolverParams::DiffussionTimeDependent(params) => Solver::DiffussionSolverTimeDependent(params),
+        }
+    }
+}
+
+/// # General Information
+///
+/// How far a simulation has gone: last solution, steps and simulated time, along with the internal state of its solver.
+///
+/// # Fields
+///
+/// * `solution` - Last solution. Empty when nothing has been solved
+/// * `steps` - Solver steps made so far
+/// * `simulated_time` - Simulated time so far
+/// * `solver` - Internal state of solver. None for solvers without one
+///
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Progress {
+    pub(crate) solution: Vec<f64>,
+    pub(crate) steps: usize,
+    pub(crate) simulated_time: f64,
+    pub(crate) solver: Option<SolverCheckpoint>,
+}
+
+/// # General Information
+///
+/// Checkpoint of a simulation, so that it can be stopped and continued later. Written to files as JSON.
+///
+/// # Fields
+///
+/// * `mesh` - Mesh simulation runs on
+/// * `params` - Params of equation solved. None when there is no equation or its params can not be written
+/// * `progress` - Solution, steps, simulated time and solver state reached
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SimulationState {
+    pub(crate) mesh: MeshSnapshot,
+    pub(crate) params: Option<SolverParams>,
+    pub(crate) progress: Progress,
+}
+
+impl SimulationState {
+    /// Writes checkpoint to a file, replacing it if it exists.
+    pub(crate) fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Reads a checkpoint written by `save`.
+    pub(crate) fn load<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Progress, SimulationState, SolverParams};
+    use crate::mesh::{mesh_builder::MeshBuilder, Mesh};
+    use crate::solvers::{diffusion_solver::DiffussionParams, Solver};
+
+    #[test]
+    fn restored_solver_repeats_next_step() {
+        let mesh = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 11, |t| t).unwrap().build_mesh_1d(None).unwrap();
+        let params = DiffussionParams::time_dependent()
+            .mu(1.0)
+            .b(0.5)
+            .boundary_conditions(1.0, 0.0)
+            .initial_conditions([0.0; 9])
+            .build();
+        let equation = Solver::DiffussionSolverTimeDependent(params);
+
+        let mut solver = equation.rebuild_solver(mesh.filter_for_solving_1d().to_vec(), 20).unwrap();
+        let mut solution = vec![];
+        for _ in 0..5 {
+            solution = solver.solve(0.01).unwrap();
+        }
+
+        let state = SimulationState {
+            mesh: mesh.snapshot(),
+            params: SolverParams::from_solver(&equation),
+            progress: Progress {
+                solution,
+                steps: 5,
+                simulated_time: 0.05,
+                solver: solver.checkpoint(),
+            },
+        };
+        let path = std::env::temp_dir().join("dzahui_checkpoint_test.json");
+        state.save(&path).unwrap();
+        let restored = SimulationState::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(restored == state);
+
+        // Rebuilding from what was written and restoring continues exactly where original solver is
+        let restored_mesh = Mesh::from_snapshot(restored.mesh);
+        let mut restored_solver = restored
+            .params
+            .unwrap()
+            .into_solver()
+            .rebuild_solver(restored_mesh.filter_for_solving_1d().to_vec(), 20)
+            .unwrap();
+        restored_solver.restore(restored.progress.solver.as_ref().unwrap()).unwrap();
+        assert!(restored_solver.solve(0.01).unwrap() == solver.solve(0.01).unwrap());
+    }
+}
@@ -7,6 +7,23 @@ use std::{mem, os::raw::c_void, ptr};
 use ndarray::Array1;
 
 
+/// # General Information
+///
+/// Way in which a drawable object is sent to screen.
+///
+/// # Arms
+///
+/// * `Triangles` - Indices are read in triads to draw triangles.
+/// * `Points` - There are no indices. Every vertex is drawn as a point. Useful for meshes with no faces.
+/// * `LineStrip` - There are no indices. Vertices are joined in order by a single line. Useful for 1D solution profiles.
+///
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum DrawMode {
+    Triangles,
+    Points,
+    LineStrip,
+}
+
 /// # General Information
 ///
 /// An object that can be represented in CPU via a, ebo, vbo, vao and texture (the latter is not necessary).
@@ -67,6 +84,20 @@ pub(crate) trait Drawable: Bindable {
     /// Creates a way to obtain order of object's dimensions. Getter.
     fn get_max_length(&self) -> Result<f32, Error>;
 
+    /// Amount of vertices (sextuples of coordinates and color) in object. Can be overriden to avoid obtaining every vertex.
+    fn get_vertex_count(&self) -> Result<usize, Error> {
+        Ok(self.get_vertices()?.len() / 6)
+    }
+
+    /// Objects without indices are drawn as points, otherwise they're drawn as triangles.
+    fn get_draw_mode(&self) -> Result<DrawMode, Error> {
+        if self.get_indices()?.is_empty() {
+            Ok(DrawMode::Points)
+        } else {
+            Ok(DrawMode::Triangles)
+        }
+    }
+
     /// # General Information
     ///
     /// Once an object with Drawable trait has been created it can be sent to gpu.
@@ -95,13 +126,15 @@ pub(crate) trait Drawable: Bindable {
                 gl::DYNAMIC_DRAW,
             );
 
-            // Point to data, specify data length and how it should be drawn
-            gl::BufferData(
-                gl::ELEMENT_ARRAY_BUFFER,
-                (indices.len() * mem::size_of::<GLuint>()) as GLsizeiptr,
-                &indices[0] as *const u32 as *const c_void,
-                gl::DYNAMIC_DRAW,
-            );
+            // Point to data, specify data length and how it should be drawn. Objects drawn as points have no indices
+            if !indices.is_empty() {
+                gl::BufferData(
+                    gl::ELEMENT_ARRAY_BUFFER,
+                    (indices.len() * mem::size_of::<GLuint>()) as GLsizeiptr,
+                    &indices[0] as *const u32 as *const c_void,
+                    gl::DYNAMIC_DRAW,
+                );
+            }
 
             // How should coordinates be read.
             // Reading starts at index 0.
@@ -136,7 +169,7 @@ pub(crate) trait Drawable: Bindable {
 
     /// # General Information
     ///
-    /// A simple call to glDrawElements in triangles mode. It assumes all information to be drawn has been sent and is stored in a single vbo, veo pair.
+    /// A simple call to glDrawElements in triangles mode or glDrawArrays in points or line strip mode when there are no indices. It assumes all information to be drawn has been sent and is stored in a single vbo, veo pair.
     /// (Making multiple calls to draw is, in general, not a good idea, since it can really slow down a program reducing the FPS. When drawing
     /// multiple objects, it's better to use the so called 'batch rendering').
     ///
@@ -145,14 +178,29 @@ pub(crate) trait Drawable: Bindable {
     /// * `&self` - A reference to the object which is attached to a binder and knows how to get the indices and indices length.
     ///
     fn draw(&self) -> Result<(), Error> {
-        let indices_len: i32 = self.get_indices()?.len() as i32;
-
         // Draw only when window is created and inside loop
-        // Drawn as triangles
-        unsafe {
-            // Draw
-            // Comment to see the triangles filled instead of only the lines that form them.
-            gl::DrawElements(gl::TRIANGLES, indices_len, gl::UNSIGNED_INT, ptr::null());
+        match self.get_draw_mode()? {
+            DrawMode::Triangles => {
+                let indices_len: i32 = self.get_indices()?.len() as i32;
+                // Drawn as triangles
+                unsafe {
+                    gl::DrawElements(gl::TRIANGLES, indices_len, gl::UNSIGNED_INT, ptr::null());
+                }
+            }
+            DrawMode::Points => {
+                let vertex_count: i32 = self.get_vertex_count()? as i32;
+                // Drawn as points. Size is set on window creation
+                unsafe {
+                    gl::DrawArrays(gl::POINTS, 0, vertex_count);
+                }
+            }
+            DrawMode::LineStrip => {
+                let vertex_count: i32 = self.get_vertex_count()? as i32;
+                // Drawn as a single line
+                unsafe {
+                    gl::DrawArrays(gl::LINE_STRIP, 0, vertex_count);
+                }
+            }
         }
 
         Ok(())
@@ -316,6 +364,31 @@ impl Binder {
         }
     }
 
+    /// # General Information
+    ///
+    /// Reads back the first `len` floats stored in binder's vbo. Useful to verify what has been sent to GPU on tests and while debugging.
+    /// Leaves binder's vbo bound.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Instance does not need to be mutable since it's already setup.
+    /// * `len` - Amount of floats to read. Should not be bigger than the amount sent.
+    ///
+    #[allow(dead_code)]
+    pub(crate) fn read_vbo(&self, len: usize) -> Vec<f32> {
+        let mut data = vec![0_f32; len];
+        self.bind_vbo();
+        unsafe {
+            gl::GetBufferSubData(
+                gl::ARRAY_BUFFER,
+                0,
+                (len * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                data.as_mut_ptr() as *mut c_void,
+            );
+        }
+        data
+    }
+
     /// # General Information
     ///
     /// Binds vao, ebo, vbo and texture.
@@ -330,4 +403,109 @@ impl Binder {
         self.bind_ebo();
         self.bind_texture();
     }
+
+    /// # General Information
+    ///
+    /// Releases vao, vbo, ebo and texture from GPU and resets them to zero, so that calling it twice is harmless.
+    /// Ids that are zero were never set up and are skipped, therefore an OpenGL context is only needed when something was set up.
+    /// That context should still be alive: owners drop their binders before it (see `DzahuiWindow`).
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Instance whose ids are released.
+    ///
+    pub(crate) fn cleanup(&mut self) {
+        unsafe {
+            for buffer in [&mut self.vbo, &mut self.ebo] {
+                if *buffer != 0 {
+                    gl::DeleteBuffers(1, buffer);
+                    *buffer = 0;
+                }
+            }
+            if self.vao != 0 {
+                gl::DeleteVertexArrays(1, &self.vao);
+                self.vao = 0;
+            }
+            if self.texture != 0 {
+                gl::DeleteTextures(1, &self.texture);
+                self.texture = 0;
+            }
+        }
+    }
+}
+
+impl Drop for Binder {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Bindable, Binder, Drawable};
+    use crate::mesh::Mesh;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[ignore = "requires a display able to create an OpenGL context"]
+    fn sent_vertices_are_read_back() {
+        use glutin::{dpi::PhysicalSize, event_loop::EventLoop, platform::unix::EventLoopExtUnix, Api, ContextBuilder, GlRequest};
+
+        let event_loop: EventLoop<()> = EventLoop::new_any_thread();
+        let context = ContextBuilder::new()
+            .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+            .build_headless(&event_loop, PhysicalSize::new(1, 1))
+            .unwrap();
+        let context = unsafe { context.make_current() }.unwrap();
+        gl::load_with(|s| context.get_proc_address(s));
+
+        let mut new_mesh = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        new_mesh.setup().unwrap();
+        new_mesh.send_to_gpu().unwrap();
+
+        let vertices = new_mesh.get_vertices().unwrap();
+        let read_vertices = new_mesh.get_binder().unwrap().read_vbo(vertices.len());
+        assert!(read_vertices == vertices.to_vec());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[ignore = "requires a display able to create an OpenGL context"]
+    fn dropped_binders_release_ids() {
+        use glutin::{dpi::PhysicalSize, event_loop::EventLoop, platform::unix::EventLoopExtUnix, Api, ContextBuilder, GlRequest};
+
+        let event_loop: EventLoop<()> = EventLoop::new_any_thread();
+        let context = ContextBuilder::new()
+            .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+            .build_headless(&event_loop, PhysicalSize::new(1, 1))
+            .unwrap();
+        let context = unsafe { context.make_current() }.unwrap();
+        gl::load_with(|s| context.get_proc_address(s));
+
+        let ids: Vec<u32> = (0..100)
+            .map(|_| {
+                let mut binder = Binder::new();
+                binder.setup();
+                binder.vbo.max(binder.ebo)
+            })
+            .collect();
+
+        // Released ids are given again instead of new ones
+        assert!(ids.iter().all(|id| *id <= ids[0]));
+
+        let mut binder = Binder::new();
+        binder.setup();
+        binder.cleanup();
+        assert!(binder == Binder::new());
+    }
+
+    #[test]
+    fn binders_never_set_up_are_dropped_without_context() {
+        // No GL function is loaded here, so any call to one would panic
+        for _ in 0..1000 {
+            let mut binder = Binder::new();
+            binder.cleanup();
+            assert!(binder == Binder::new());
+        }
+    }
 }
@@ -0,0 +1,179 @@
+// Internal dependencies
+use super::binder::{Bindable, Binder, Drawable};
+use crate::{writer, Error};
+
+// External dependencies
+use ndarray::Array1;
+use num::ToPrimitive;
+
+/// # General Information
+///
+/// Color assigned to a value already normalized between 0 (minimum) and 1 (maximum). Normalized value is multiplied by pi/2 so that,
+/// when calculating its sine and cosine, there's a mapping between maximum <-> red and minimum <-> blue.
+///
+/// # Parameters
+///
+/// * `normalized` - Value between 0 and 1
+///
+pub(crate) fn gradient_color(normalized: f64) -> [f64; 3] {
+    let angle = normalized * (std::f64::consts::PI / 2.);
+    [angle.sin(), 0.0, angle.cos()]
+}
+
+/// # General Information
+///
+/// Legend of the color gradient drawn on a corner of the screen. Geometry is given in normalized device coordinates, so it should be drawn
+/// with identity model, view and projection matrices. It is a vertical stack of quads going from the color of the minimum (bottom) to the color of the maximum (top).
+/// Every vertex has 6 entries: 3 for coordinates and 3 for color (RGB), just like a mesh.
+///
+/// # Fields
+///
+/// * `binder` - vao, vbo and ebo variables bound to color bar in GPU.
+/// * `indices` - Indices of quads (two triangles each).
+/// * `vertices` - Vertices of color bar. Two per level of the gradient.
+/// * `origin` - Lower left corner of color bar in normalized device coordinates.
+/// * `size` - Width and height of color bar in normalized device coordinates.
+/// * `range` - Minimum and maximum values currently mapped to the gradient.
+///
+#[derive(Debug)]
+pub(crate) struct ColorBar {
+    binder: Binder,
+    indices: Array1<u32>,
+    pub(crate) vertices: Array1<f64>,
+    origin: (f64, f64),
+    size: (f64, f64),
+    range: (f64, f64),
+}
+
+impl ColorBar {
+    /// # General Information
+    ///
+    /// Creates a color bar whose lower left corner is `origin` and whose gradient is split in `segments` quads.
+    ///
+    /// # Parameters
+    ///
+    /// * `origin` - Lower left corner in normalized device coordinates
+    /// * `size` - Width and height in normalized device coordinates
+    /// * `segments` - Amount of quads. More segments give a smoother gradient
+    /// * `range` - Minimum and maximum values mapped to the gradient
+    ///
+    pub(crate) fn new(origin: (f64, f64), size: (f64, f64), segments: usize, range: (f64, f64)) -> Self {
+        // Slightly in front of everything else
+        let depth = -0.99;
+
+        let vertices: Vec<f64> = (0..=segments)
+            .flat_map(|level| {
+                let normalized = level as f64 / segments as f64;
+                let [r, g, b] = gradient_color(normalized);
+                let y = origin.1 + normalized * size.1;
+                [origin.0, y, depth, r, g, b, origin.0 + size.0, y, depth, r, g, b]
+            })
+            .collect();
+
+        let indices: Vec<u32> = (0..segments as u32)
+            .flat_map(|level| {
+                let (left, right) = (2 * level, 2 * level + 1);
+                [left, right, left + 2, right, right + 2, left + 2]
+            })
+            .collect();
+
+        Self {
+            binder: Binder::new(),
+            indices: Array1::from_vec(indices),
+            vertices: Array1::from_vec(vertices),
+            origin,
+            size,
+            range,
+        }
+    }
+
+    /// Changes minimum and maximum values shown on labels.
+    pub(crate) fn update_range(&mut self, range: (f64, f64)) {
+        self.range = range;
+    }
+
+    /// Labels of minimum and maximum, in that order, written with the given amount of decimals.
+    pub(crate) fn labels(&self, precision: Option<usize>) -> (String, String) {
+        (
+            writer::format_value(self.range.0, precision),
+            writer::format_value(self.range.1, precision),
+        )
+    }
+
+    /// # General Information
+    ///
+    /// Viewport positions (in pixels) where labels of minimum and maximum should be written: to the right of the bottom and top of the bar respectively.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Color bar to label
+    /// * `window_height` - Size of viewport to transform coordinates
+    /// * `window_width` - Size of viewport to transform coordinates
+    ///
+    pub(crate) fn label_positions(&self, window_height: u32, window_width: u32) -> [(f32, f32); 2] {
+        let to_viewport = |ndc: f64, size: u32| ((ndc + 1.0) / 2.0 * size as f64) as f32;
+        let x = to_viewport(self.origin.0 + self.size.0, window_width);
+
+        [
+            (x, to_viewport(self.origin.1, window_height)),
+            (x, to_viewport(self.origin.1 + self.size.1, window_height)),
+        ]
+    }
+}
+
+impl Bindable for ColorBar {
+    fn get_binder(&self) -> Result<&Binder, Error> {
+        Ok(&self.binder)
+    }
+
+    fn get_mut_binder(&mut self) -> Result<&mut Binder, Error> {
+        Ok(&mut self.binder)
+    }
+}
+
+impl Drawable for ColorBar {
+    fn get_indices(&self) -> Result<&Array1<u32>, Error> {
+        Ok(&self.indices)
+    }
+
+    fn get_vertices(&self) -> Result<Array1<f32>, Error> {
+        Ok(Array1::from_vec(
+            self.vertices.iter().map(|x| -> Result<f32,Error> { x.to_f32().ok_or(Error::FloatConversion) })
+            .collect::<Result<Vec<f32>,_>>()?
+        ))
+    }
+
+    fn get_max_length(&self) -> Result<f32, Error> {
+        self.size.0.max(self.size.1).to_f32().ok_or(Error::FloatConversion)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ColorBar;
+
+    #[test]
+    fn geometry_and_labels_follow_bar() {
+        let mut color_bar = ColorBar::new((0.5, -0.5), (0.25, 1.0), 4, (0.0, 1.0));
+
+        let xs: Vec<f64> = color_bar.vertices.iter().step_by(6).copied().collect();
+        let ys: Vec<f64> = color_bar.vertices.iter().skip(1).step_by(6).copied().collect();
+        assert!(xs.iter().all(|x| (0.5..=0.75).contains(x)));
+        assert!(ys.iter().all(|y| (-0.5..=0.5).contains(y)));
+        assert!(ys.first() == Some(&-0.5) && ys.last() == Some(&0.5));
+        assert!(color_bar.indices.iter().all(|index| (*index as usize) < xs.len()));
+
+        // Bottom is blue and top is red
+        assert!(color_bar.vertices[5] == 1.0);
+        let top = color_bar.vertices.len() - 6;
+        assert!(color_bar.vertices[top + 3] == 1.0);
+
+        // Labels sit to the right of the bar, at its bottom and top
+        let [min_position, max_position] = color_bar.label_positions(600, 800);
+        assert!(min_position == (700.0, 150.0));
+        assert!(max_position == (700.0, 450.0));
+
+        color_bar.update_range((0.25, 3.5));
+        assert!(color_bar.labels(Some(2)) == ("0.25".to_string(), "3.50".to_string()));
+    }
+}
@@ -0,0 +1,263 @@
+// Internal dependencies
+use super::{binder::{Bindable, Binder, DrawMode, Drawable}, color_bar::gradient_color};
+use crate::Error;
+
+// External dependencies
+use ndarray::Array1;
+use num::ToPrimitive;
+
+/// # General Information
+///
+/// Profile of a 1D solution drawn as a single line strip over the nodes of a mesh. Every vertex has 6 entries: 3 for coordinates and
+/// 3 for color (RGB), just like a mesh. Height of every vertex follows the value of the solution at its node.
+///
+/// # Fields
+///
+/// * `binder` - vao, vbo and ebo variables bound to line strip in GPU.
+/// * `indices` - Always empty. Line strip is drawn directly from vertices.
+/// * `vertices` - Vertices of line strip.
+/// * `base_height` - Height at which minimum value of solution is drawn.
+/// * `max_height` - Height above `base_height` at which maximum value of solution is drawn.
+///
+#[derive(Debug)]
+pub(crate) struct LineStrip {
+    binder: Binder,
+    indices: Array1<u32>,
+    pub(crate) vertices: Array1<f64>,
+    base_height: f64,
+    max_height: f64,
+}
+
+impl LineStrip {
+    /// Creates a flat line strip at `base_height` over the given nodes.
+    pub(crate) fn new(nodes: &[f64], base_height: f64, max_height: f64) -> Self {
+        let vertices: Vec<f64> = nodes
+            .iter()
+            .flat_map(|x| [*x, base_height, 0.0, 0.0, 0.0, 0.0])
+            .collect();
+
+        Self {
+            binder: Binder::new(),
+            indices: Array1::from_vec(vec![]),
+            vertices: Array1::from_vec(vertices),
+            base_height,
+            max_height,
+        }
+    }
+
+    /// Creates a flat line strip to plot a solution against its nodes, vertically centered on a bar of height `bar_height`.
+    /// Solution spans half of `length` (normally the length of the mesh), so that plot fits next to the bar.
+    pub(crate) fn line_plot(nodes: &[f64], bar_height: f64, length: f64) -> Self {
+        LineStrip::new(nodes, bar_height / 2.0 - length / 4.0, length / 2.0)
+    }
+
+    /// Creates a line strip joining the given points with a single color. It does not follow any solution, so heights are left at zero.
+    pub(crate) fn from_points(points: &[[f64; 3]], color: [f64; 3]) -> Self {
+        let vertices: Vec<f64> = points
+            .iter()
+            .flat_map(|[x, y, z]| [*x, *y, *z, color[0], color[1], color[2]])
+            .collect();
+
+        Self {
+            binder: Binder::new(),
+            indices: Array1::from_vec(vec![]),
+            vertices: Array1::from_vec(vertices),
+            base_height: 0.0,
+            max_height: 0.0,
+        }
+    }
+
+    /// # General Information
+    ///
+    /// Samples a function `samples_per_element` times per interval between consecutive vertices of this line strip, at the same heights.
+    /// Vertices are drawn with a single color and lie flat until updated. Returns the line strip along with the value of the function at every sample.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Line strip whose nodes and heights are followed (normally the profile of a solution)
+    /// * `function` - Function to sample
+    /// * `samples_per_element` - Amount of samples per interval. Should be at least one
+    /// * `color` - Color of every vertex
+    ///
+    pub(crate) fn sampled_like<F: Fn(f64) -> f64>(&self, function: F, samples_per_element: usize, color: [f64; 3]) -> (Self, Vec<f64>) {
+        let nodes: Vec<f64> = self.vertices.iter().step_by(6).copied().collect();
+        let samples_per_element = samples_per_element.max(1);
+
+        let mut positions: Vec<f64> = nodes
+            .windows(2)
+            .flat_map(|element| {
+                (0..samples_per_element).map(move |j| element[0] + (element[1] - element[0]) * j as f64 / samples_per_element as f64)
+            })
+            .collect();
+        positions.extend(nodes.last());
+
+        let values: Vec<f64> = positions.iter().map(|x| function(*x)).collect();
+        let mut line_strip = LineStrip::new(&positions, self.base_height, self.max_height);
+        for mut vertex in line_strip.vertices.exact_chunks_mut(6) {
+            vertex[3] = color[0];
+            vertex[4] = color[1];
+            vertex[5] = color[2];
+        }
+
+        (line_strip, values)
+    }
+
+    /// # General Information
+    ///
+    /// Moves every vertex of the line strip to the height of the solution at its node. Solution is normalized so that its minimum lies at
+    /// `base_height` and its maximum at `base_height + max_height`. Returns minimum and maximum used.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Line strip to update
+    /// * `solution` - Value of solution at every node. Should have as many values as nodes.
+    ///
+    pub(crate) fn update(&mut self, solution: &[f64]) -> Result<(f64, f64), Error> {
+        let sol_max = solution.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let sol_min = solution.iter().copied().fold(f64::INFINITY, f64::min);
+
+        self.update_in_range(solution, (sol_min, sol_max))?;
+        Ok((sol_min, sol_max))
+    }
+
+    /// # General Information
+    ///
+    /// Same as `update`, but values are normalized with a given minimum and maximum, so that several line strips can share a scale.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Line strip to update
+    /// * `values` - Value at every vertex
+    /// * `range` - Values drawn at `base_height` and at `base_height + max_height`
+    ///
+    pub(crate) fn update_in_range(&mut self, values: &[f64], (sol_min, sol_max): (f64, f64)) -> Result<(), Error> {
+        if values.len() != self.vertices.len() / 6 {
+            return Err(Error::WrongDims);
+        }
+
+        let range = sol_max - sol_min;
+        for (i, value) in values.iter().enumerate() {
+            let normalized = if range > 0.0 { (value - sol_min) / range } else { 0.0 };
+            self.vertices[6 * i + 1] = self.base_height + normalized * self.max_height;
+        }
+
+        Ok(())
+    }
+
+    /// # General Information
+    ///
+    /// Colors every vertex of the line strip with the gradient of its value. Values outside of `range` are clamped.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Line strip to update
+    /// * `solution` - Value of solution at every node. Should have as many values as nodes.
+    /// * `range` - Minimum and maximum values mapped to the ends of the gradient
+    ///
+    pub(crate) fn update_colors(&mut self, solution: &[f64], (sol_min, sol_max): (f64, f64)) -> Result<(), Error> {
+        if solution.len() != self.vertices.len() / 6 {
+            return Err(Error::WrongDims);
+        }
+
+        let range = sol_max - sol_min;
+        for (i, value) in solution.iter().enumerate() {
+            let value = value.clamp(sol_min, sol_max);
+            let normalized = if range > 0.0 { (value - sol_min) / range } else { 0.0 };
+            let [r, g, b] = gradient_color(normalized);
+            self.vertices[6 * i + 3] = r;
+            self.vertices[6 * i + 4] = g;
+            self.vertices[6 * i + 5] = b;
+        }
+
+        Ok(())
+    }
+}
+
+impl Bindable for LineStrip {
+    fn get_binder(&self) -> Result<&Binder, Error> {
+        Ok(&self.binder)
+    }
+
+    fn get_mut_binder(&mut self) -> Result<&mut Binder, Error> {
+        Ok(&mut self.binder)
+    }
+}
+
+impl Drawable for LineStrip {
+    fn get_indices(&self) -> Result<&Array1<u32>, Error> {
+        Ok(&self.indices)
+    }
+
+    fn get_vertices(&self) -> Result<Array1<f32>, Error> {
+        Ok(Array1::from_vec(
+            self.vertices.iter().map(|x| -> Result<f32,Error> { x.to_f32().ok_or(Error::FloatConversion) })
+            .collect::<Result<Vec<f32>,_>>()?
+        ))
+    }
+
+    fn get_vertex_count(&self) -> Result<usize, Error> {
+        Ok(self.vertices.len() / 6)
+    }
+
+    fn get_draw_mode(&self) -> Result<DrawMode, Error> {
+        Ok(DrawMode::LineStrip)
+    }
+
+    fn get_max_length(&self) -> Result<f32, Error> {
+        (self.base_height + self.max_height).to_f32().ok_or(Error::FloatConversion)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::LineStrip;
+
+    #[test]
+    fn vertices_follow_solution() {
+        let mut line_strip = LineStrip::new(&[0.0, 0.5, 1.0, 1.5], 0.2, 2.0);
+        line_strip.update(&[1.0, 3.0, 2.0, 5.0]).unwrap();
+
+        let heights: Vec<f64> = (0..4).map(|i| line_strip.vertices[6 * i + 1]).collect();
+        assert!(heights == vec![0.2, 1.2, 0.7, 2.2]);
+
+        let positions: Vec<f64> = (0..4).map(|i| line_strip.vertices[6 * i]).collect();
+        assert!(positions == vec![0.0, 0.5, 1.0, 1.5]);
+
+        assert!(line_strip.update(&[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn reference_samples_function_over_nodes() {
+        let profile = LineStrip::new(&[0.0, 0.5, 2.0], 1.0, 2.0);
+        let (mut reference, values) = profile.sampled_like(|x| x * x, 4, [1.0, 0.0, 1.0]);
+
+        // Four samples per element plus last node
+        let positions: Vec<f64> = reference.vertices.iter().step_by(6).copied().collect();
+        assert!(positions == vec![0.0, 0.125, 0.25, 0.375, 0.5, 0.875, 1.25, 1.625, 2.0]);
+        assert!(values.iter().zip(&positions).all(|(value, x)| *value == x * x));
+        assert!(reference.vertices.exact_chunks(6).into_iter().all(|vertex| vertex[3] == 1.0 && vertex[4] == 0.0 && vertex[5] == 1.0));
+
+        // Shares scale of solution it is compared with
+        reference.update_in_range(&values, (0.0, 2.0)).unwrap();
+        assert!(reference.vertices[1] == 1.0 && reference.vertices[6 * 8 + 1] == 5.0);
+    }
+
+    #[test]
+    fn line_plot_heights_track_solution() {
+        let nodes = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let solution = [0.0, 2.0, 4.0, 2.0, 0.0];
+        let mut plot = LineStrip::line_plot(&nodes, 0.4, 4.0);
+        plot.update(&solution).unwrap();
+        plot.update_colors(&solution, (0.0, 4.0)).unwrap();
+
+        // Plot spans half the length and is centered on the bar
+        let heights: Vec<f64> = (0..5).map(|i| plot.vertices[6 * i + 1]).collect();
+        assert!(heights.iter().zip([-0.8, 0.2, 1.2, 0.2, -0.8]).all(|(h, expected)| (h - expected).abs() < 1e-12));
+        assert!((0..5).all(|i| plot.vertices[6 * i] == nodes[i]));
+
+        // Equal values share height and color, bigger values go higher
+        let color = |i: usize| [plot.vertices[6 * i + 3], plot.vertices[6 * i + 4], plot.vertices[6 * i + 5]];
+        assert!(color(1) == color(3) && color(0) != color(2));
+        assert!(plot.update_colors(&solution[..2], (0.0, 4.0)).is_err());
+    }
+}
@@ -1,4 +1,8 @@
 // Module declaration
 pub(crate) mod text;
+/// Solution profile for 1D meshes
+pub(crate) mod line_strip;
+/// Legend of the color gradient
+pub(crate) mod color_bar;
 /// Common functions in drawable (2D or 3D objects)
 pub(crate) mod binder;
@@ -8,6 +8,7 @@ use std::{
     io::{BufRead, BufReader},
     mem,
     os::raw::c_void,
+    path::Path,
     ptr,
 };
 
@@ -121,8 +122,27 @@ impl CharacterSet {
     /// # Parameters
     ///
     /// * `character_file` - fnt file. It is important that the file is correctly created since metadata is important to struct instance.
+    ///   Texture image named inside is looked for in the same directory as this file.
     ///
     pub fn new(character_file: &str) -> Result<Self,Error> {
+        let asset_dir = Path::new(character_file)
+            .parent()
+            .unwrap_or_else(|| Path::new(""));
+
+        CharacterSet::new_with_asset_dir(character_file, asset_dir)
+    }
+
+    /// # General Information
+    ///
+    /// Same as `new`, but texture image named inside fnt file is looked for in `asset_dir` instead of next to the fnt file.
+    /// Useful for packaged applications whose assets do not depend on the working directory.
+    ///
+    /// # Parameters
+    ///
+    /// * `character_file` - fnt file. It is important that the file is correctly created since metadata is important to struct instance.
+    /// * `asset_dir` - Directory containing texture image.
+    ///
+    pub fn new_with_asset_dir<P: AsRef<Path>>(character_file: &str, asset_dir: P) -> Result<Self,Error> {
         let binder = Binder::new();
 
         let file = File::open(character_file)?;
@@ -131,120 +151,86 @@ impl CharacterSet {
         // read general properties of font first
         let info_line = reader
             .next()
-            .ok_or(Error::Parse("Could not read first line from text font file"))??;
+            .ok_or_else(|| Error::Parse("Could not read first line from text font file".to_string()))??;
 
-        let info_line: Vec<&str> = info_line.split("\"").collect();
+        let info_line: Vec<&str> = info_line.split('"').collect();
+        if info_line.len() < 3 {
+            return Err(Error::Parse("Could not find font face on line 1 of text font file".to_string()));
+        }
 
         // Font properties
         let font_type = info_line[1].to_string();
 
         // Need to split againd but this time via space, collecting every property from first line
-        let mut property_map_one: HashMap<String, String> = info_line[2]
-            .trim()
-            .split(" ")
-            .map(|property| {
-                let key_value: Vec<&str> = property.split("=").collect();
-                (key_value[0].to_string(), key_value[1].to_string())
-            })
-            .collect();
+        let property_map_one = property_map(info_line[2].split(' '), 1)?;
 
         // Second line also contains information
         let second_info_line = reader
             .next()
-            .ok_or(Error::Parse("Could not read second line from text font file"))??;
+            .ok_or_else(|| Error::Parse("Could not read second line from text font file".to_string()))??;
 
-        let mut property_map_two = second_info_line.split(" ");
         // Skip 'common' word
-        property_map_two.next();
-        // Shadowing
-        let mut property_map_two: HashMap<String, String> = property_map_two
-            .map(|property| {
-                let key_value: Vec<&str> = property.split("=").collect();
-                (key_value[0].to_string(), key_value[1].to_string())
-            })
-            .collect();
+        let property_map_two = property_map(second_info_line.split(' ').skip(1), 2)?;
 
         // Third line contains texture file
         let third_info_line = reader
             .next()
-            .ok_or(Error::Parse("Could not read third line from text font file"))??;
-            
-        let mut property_map_three = third_info_line.split(" ");
-        // SKip 'page' word
-        property_map_three.next();
-        // Shadowing
-        let mut property_map_three: HashMap<String, String> = property_map_three
-            .map(|property| {
-                let key_value: Vec<&str> = property.split("=").collect();
-                (key_value[0].to_string(), key_value[1].to_string())
-            })
-            .collect();
+            .ok_or_else(|| Error::Parse("Could not read third line from text font file".to_string()))??;
 
-        // After third line, image can be loaded.
-        let img = image::open(format!(
-            "./assets/{}",
-            property_map_three
-                .get("file")
-                .ok_or(Error::NotFound("Text image file"))?
-                .replace("\"", "")
-        ))?;
+        // SKip 'page' word
+        let property_map_three = property_map(third_info_line.split(' ').skip(1), 3)?;
+
+        // After third line, image can be loaded from asset directory
+        let texture_file = property_map_three
+            .get("file")
+            .ok_or(Error::NotFound("Text image file"))?
+            .replace('"', "");
+        let texture_path = asset_dir.as_ref().join(&texture_file);
+        let img = image::open(texture_path)?;
         let img_vec: Vec<u8> = img.into_bytes();
 
         // Fourth line contains number of characters
         let fourth_info_line = reader
             .next()
-            .ok_or(Error::Parse("Could not read fourth line from text font file"))??;
-            
-        let mut property_map_four = fourth_info_line.split(" ");
+            .ok_or_else(|| Error::Parse("Could not read fourth line from text font file".to_string()))??;
+
         // Skip 'chars' word
-        property_map_four.next();
-        // Shadowing
-        let mut property_map_four: HashMap<String, usize> = property_map_four
-            .map(|property| -> Result<(String, usize),Error> {
-
-                let key_value: Vec<&str> = property.split("=").collect();
-                Ok((key_value[0].to_string(), key_value[1].parse()?))
-            
-            })
-            .collect::<Result<HashMap<String,usize>,_>>()?;
+        let property_map_four = property_map(fourth_info_line.split(' ').skip(1), 4)?;
+        let character_number = parse_count(&property_map_four, "count", 4)?;
 
         // Processing rest of file to create the characters
-        let mut characters: HashMap<char, Character> =
-            HashMap::with_capacity(*property_map_four.get("count").ok_or(Error::Custom("Could not find propperty 'count' on text file".to_string()))?);
-        for line in reader {
+        let mut characters: HashMap<char, Character> = HashMap::with_capacity(character_number);
+        for (line_number, line) in reader.enumerate() {
 
+            // First four lines have already been read
+            let line_number = line_number + 5;
             let content = line?;
-            
+
             // Get rid of multiple space
-            let mut properties = content.split(" ").filter(|e| *e != "");
-            let is_character_line = properties.next().ok_or(Error::Parse("Could not parse text file propperly"))?;
+            let mut properties = content.split(' ').filter(|e| !e.is_empty());
+            let is_character_line = properties
+                .next()
+                .ok_or_else(|| Error::Parse(format!("Could not parse line {} of text font file", line_number)))?;
 
             if is_character_line != "char" {
                 continue;
             } else {
                 // Property map for character
-                let property_map: HashMap<&str, f32> = properties
-                    .map(|property| -> Result<(&str, f32),Error> {
-                        
-                        let key_value: Vec<&str> = property.split("=").collect();
-                        Ok((key_value[0], key_value[1].parse()?))
-                    
-                    })
-                    .collect::<Result<HashMap<&str,f32>,_>>()?;
+                let property_map = property_map(properties, line_number)?;
+                let id = parse_count(&property_map, "id", line_number)? as u32;
 
                 // Character creation
-
                 let temp_character = Character::new(
-                    property_map["id"] as u32,
-                    (property_map["x"], property_map["y"]),
-                    (property_map["width"], property_map["height"]),
-                    (property_map["xoffset"], property_map["yoffset"]),
-                    property_map["xadvance"]
+                    id,
+                    (parse_metric(&property_map, "x", line_number)?, parse_metric(&property_map, "y", line_number)?),
+                    (parse_size(&property_map, "width", line_number)?, parse_size(&property_map, "height", line_number)?),
+                    (parse_metric(&property_map, "xoffset", line_number)?, parse_metric(&property_map, "yoffset", line_number)?),
+                    parse_metric(&property_map, "xadvance", line_number)?
                 );
 
                 // Insert character
                 characters.insert(
-                    char::from_u32(property_map["id"] as u32).ok_or(Error::Parse("A letter provided in text file is not a valid char"))?,
+                    char::from_u32(id).ok_or_else(|| Error::Parse(format!("Property 'id' on line {} of text font file is not a valid char", line_number)))?,
                     temp_character,
                 );
             }
@@ -255,40 +241,35 @@ impl CharacterSet {
             characters,
             binder,
             image_as_vec: img_vec,
-            font_size: property_map_one
-                .remove("size")
-                .ok_or(Error::custom("Could not find 'size' property on text file"))?
-                .parse()?,
-            is_bold: property_map_one
+            font_size: parse_count(&property_map_one, "size", 1)? as u32,
+            is_bold: *property_map_one
                 .get("bold").ok_or(Error::custom("Could not find property 'bold' on text file"))?
                 == "1",
-            is_italic: property_map_one
+            is_italic: *property_map_one
                 .get("italic").ok_or(Error::custom("Could not find property 'bold'"))?
                 == "1",
             encoding: String::from("unicode"),
-            line_height: property_map_two
-                .remove("lineHeight").ok_or(Error::custom("Could not find property 'lineHEight' on text file"))?
-                .parse()?,
+            line_height: parse_count(&property_map_two, "lineHeight", 2)? as u32,
             texture_size: (
-                property_map_two
-                    .remove("scaleW").ok_or(Error::custom("Could not find property 'scaleW' on text file"))?
-                    .parse()?,
-                property_map_two
-                    .remove("scaleH").ok_or(Error::custom("Could not find property 'scaleH' on text file"))?
-                    .parse()?,
+                parse_count(&property_map_two, "scaleW", 2)? as u32,
+                parse_count(&property_map_two, "scaleH", 2)? as u32,
             ),
-            texture_file: property_map_three
-                .remove("file").ok_or(Error::custom("Could not find property 'file' on text file"))?
-                .replace("\"", ""),
-            character_number: property_map_four
-                .remove("count").ok_or(Error::custom("Could not find property 'count' on text file"))?,
+            texture_file,
+            character_number,
         })
     }
 
+    /// Every character available in font, sorted. Useful to check a text can be drawn before calling `draw_text`.
+    pub fn available_chars(&self) -> Vec<char> {
+        let mut available_chars: Vec<char> = self.characters.keys().copied().collect();
+        available_chars.sort_unstable();
+        available_chars
+    }
+
     /// # General Information
     ///
     /// Struct has it's own method to send to gpu since texture has to be considered. This means send_to_gpu method inside bindable trait does not work
-    /// with this struct. Text are sent by letter. Performance is not affected much since the amount of letters is small. Image vector is sent in it's entirety.
+    /// with this struct. Buffers are allocated here but filled by `draw_text` with the whole text at once. Image vector is sent in it's entirety.
     ///
     /// # Parameters
     ///
@@ -464,7 +445,7 @@ impl CharacterSet {
 
                         (width, last_index + 4)
                     }
-                    None => panic!("Character string {} not found",character_string)
+                    None => panic!("Character string {} not found. Available characters are: {:?}",character_string,self.available_chars())
                 }
             });
         Ok((vertices, indices))
@@ -472,7 +453,37 @@ impl CharacterSet {
 
     /// # General Information
     ///
-    /// Draw a given text string. It can even be dynamic and, as long as the text is not too big, there will be no framerate drop.
+    /// Joins the quads of every letter in a text into a single vertex array (4 vertices per letter, each with coordinates and texture coordinates)
+    /// and a single index array (6 indices per letter), so that the whole text can be drawn with one call. Space quads are not needed since every
+    /// letter already carries its own position.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Obtain vertices from text function
+    /// * `text` - A text string to parse. Every character has to be in the original font (CharacterSet).
+    ///
+    fn get_batched_vertices_from_text<A: AsRef<str>>(&self, text: A) -> Result<(Vec<f32>, Vec<u32>),Error> {
+        let (vertices, indices) = self.get_vertices_from_text(text)?;
+
+        let batched_vertices: Vec<f32> = vertices
+            .iter()
+            .flat_map(|letter_vertices| letter_vertices[..20].iter().copied())
+            .collect();
+
+        let batched_indices: Vec<u32> = indices
+            .iter()
+            .enumerate()
+            .flat_map(|(letter, letter_indices)| {
+                letter_indices[..6].iter().map(move |index| index + 4 * letter as u32)
+            })
+            .collect();
+
+        Ok((batched_vertices, batched_indices))
+    }
+
+    /// # General Information
+    ///
+    /// Draw a given text string. It can even be dynamic: the whole text is sent to gpu and drawn with a single call, so long texts do not drop framerate.
     ///
     /// # Parameters
     ///
@@ -482,34 +493,34 @@ impl CharacterSet {
     pub(crate) fn draw_text<A: AsRef<str>>(&self, text: A) -> Result<(),Error> {
         // use function inside event loop in dzahui window, not anywhere else.
         // obtain vertices and indices to draw
-        let (vertices, indices) = self.get_vertices_from_text(text)?;
+        let (vertices, indices) = self.get_batched_vertices_from_text(text)?;
 
-        vertices
-            .iter()
-            .zip(indices)
-            .for_each(|(vertices_subset, indices_subset)| {
-                unsafe {
-                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
-
-                    gl::BufferSubData(
-                        gl::ARRAY_BUFFER,
-                        0,
-                        (vertices_subset.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
-                        &vertices_subset[0] as *const f32 as *const c_void,
-                    ); // double casting to raw pointer of c_void
-
-                    gl::BufferSubData(
-                        gl::ELEMENT_ARRAY_BUFFER,
-                        0,
-                        (indices_subset.len() * mem::size_of::<GLuint>()) as GLsizeiptr,
-                        &indices_subset[0] as *const u32 as *const c_void,
-                    );
-
-                    gl::DrawElements(gl::TRIANGLES, 6, gl::UNSIGNED_INT, ptr::null());
-
-                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
-                }
-            });
+        if indices.is_empty() {
+            return Ok(());
+        }
+
+        unsafe {
+            gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+
+            // Buffers are reallocated since text length may change every frame
+            gl::BufferData(
+                gl::ARRAY_BUFFER,
+                (vertices.len() * mem::size_of::<GLfloat>()) as GLsizeiptr,
+                &vertices[0] as *const f32 as *const c_void,
+                gl::DYNAMIC_DRAW,
+            ); // double casting to raw pointer of c_void
+
+            gl::BufferData(
+                gl::ELEMENT_ARRAY_BUFFER,
+                (indices.len() * mem::size_of::<GLuint>()) as GLsizeiptr,
+                &indices[0] as *const u32 as *const c_void,
+                gl::DYNAMIC_DRAW,
+            );
+
+            gl::DrawElements(gl::TRIANGLES, indices.len() as GLsizei, gl::UNSIGNED_INT, ptr::null());
+
+            gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+        }
 
         Ok(())
     }
@@ -557,6 +568,65 @@ impl CharacterSet {
     }
 }
 
+/// # General Information
+///
+/// Collects `key=value` properties of a line of a fnt file.
+///
+/// # Parameters
+///
+/// * `properties` - Every `key=value` pair of a line. Empty ones (from repeated spaces) are skipped
+/// * `line` - Number of line in file, used to report errors
+///
+fn property_map<'a, I: Iterator<Item = &'a str>>(properties: I, line: usize) -> Result<HashMap<&'a str, &'a str>, Error> {
+    properties
+        .filter(|property| !property.is_empty())
+        .map(|property| {
+            property
+                .split_once('=')
+                .ok_or_else(|| Error::Parse(format!("Property '{}' on line {} of text font file has no value", property, line)))
+        })
+        .collect()
+}
+
+/// # General Information
+///
+/// Parses a numeric property of a fnt file. Exponent notation is accepted, NaN and infinite values are not.
+///
+/// # Parameters
+///
+/// * `properties` - Properties of a line (see `property_map`)
+/// * `name` - Name of property to parse
+/// * `line` - Number of line in file, used to report errors
+///
+fn parse_metric(properties: &HashMap<&str, &str>, name: &str, line: usize) -> Result<f32, Error> {
+    let value = properties
+        .get(name)
+        .ok_or_else(|| Error::Parse(format!("Could not find property '{}' on line {} of text font file", name, line)))?;
+
+    match value.parse::<f32>() {
+        Ok(metric) if metric.is_finite() => Ok(metric),
+        _ => Err(Error::Parse(format!("Property '{}' on line {} of text font file is not a number: '{}'", name, line, value))),
+    }
+}
+
+/// Same as `parse_metric`, but negative values are rejected.
+fn parse_size(properties: &HashMap<&str, &str>, name: &str, line: usize) -> Result<f32, Error> {
+    let size = parse_metric(properties, name, line)?;
+    if size < 0.0 {
+        return Err(Error::Parse(format!("Property '{}' on line {} of text font file can not be negative: {}", name, line, size)));
+    }
+    Ok(size)
+}
+
+/// Same as `parse_size`, but only whole values are accepted.
+fn parse_count(properties: &HashMap<&str, &str>, name: &str, line: usize) -> Result<usize, Error> {
+    let count = parse_size(properties, name, line)?;
+    if count.fract() != 0.0 {
+        return Err(Error::Parse(format!("Property '{}' on line {} of text font file should be a whole number: {}", name, line, count)));
+    }
+    Ok(count as usize)
+}
+
 #[cfg(test)]
 mod test {
     use std::collections::HashMap;
@@ -564,6 +634,7 @@ mod test {
     use crate::simulation::drawable::binder::Binder;
 
     use super::{CharacterSet, Character};
+    use crate::Error;
 
     #[test]
     fn read_properly() {
@@ -597,4 +668,89 @@ mod test {
         assert!( indices.len() == 6 );
         assert!( vertices.len() == 6 );
     }
+
+    #[test]
+    fn batched_vertices_content() {
+        let set = CharacterSet::new("./assets/dzahui-font_test.fnt").unwrap();
+        let text = "{a{ {a";
+        let (vertices, indices) = set.get_batched_vertices_from_text(text).unwrap();
+        let letters = text.chars().count();
+        // 4 vertices of 5 floats and 6 indices per letter
+        assert!(vertices.len() == 4 * 5 * letters);
+        assert!(indices.len() == 6 * letters);
+        // Every letter refers only to its own vertices
+        assert!(indices.chunks(6).enumerate().all(|(letter, quad)| {
+            quad.iter().all(|index| (*index as usize) / 4 == letter)
+        }));
+    }
+
+    #[test]
+    fn lists_available_chars() {
+        let set = CharacterSet::new("./assets/dzahui-font_test.fnt").unwrap();
+        assert!(set.available_chars() == vec![' ', 'a', '{']);
+    }
+
+    #[test]
+    fn texture_is_found_next_to_font_file() {
+        let font_directory = std::env::temp_dir().join("dzahui_font_test");
+        std::fs::create_dir_all(&font_directory).unwrap();
+        std::fs::copy("./assets/dzahui-font_test.fnt", font_directory.join("font.fnt")).unwrap();
+        std::fs::copy("./assets/dzahui-font.png", font_directory.join("dzahui-font.png")).unwrap();
+
+        let set = CharacterSet::new(font_directory.join("font.fnt").to_str().unwrap());
+        std::fs::remove_dir_all(&font_directory).unwrap();
+
+        let set = set.unwrap();
+        assert!(set.available_chars() == vec![' ', 'a', '{']);
+        assert!(!set.image_as_vec.is_empty());
+    }
+
+    #[test]
+    fn texture_is_found_in_asset_dir() {
+        let asset_directory = std::env::temp_dir().join("dzahui_font_asset_dir_test");
+        std::fs::create_dir_all(&asset_directory).unwrap();
+        std::fs::copy("./assets/dzahui-font.png", asset_directory.join("dzahui-font.png")).unwrap();
+
+        let set = CharacterSet::new_with_asset_dir("./assets/dzahui-font_test.fnt", &asset_directory);
+        let missing_texture = CharacterSet::new_with_asset_dir("./assets/dzahui-font_test.fnt", asset_directory.join("empty"));
+        std::fs::remove_dir_all(&asset_directory).unwrap();
+
+        let set = set.unwrap();
+        assert!(set.available_chars() == vec![' ', 'a', '{']);
+        assert!(!set.image_as_vec.is_empty());
+        assert!(missing_texture.is_err());
+    }
+
+    #[test]
+    fn malformed_metric_is_reported_with_property_and_line() {
+        let font_directory = std::env::temp_dir().join("dzahui_font_malformed_test");
+        std::fs::create_dir_all(&font_directory).unwrap();
+        std::fs::copy("./assets/dzahui-font.png", font_directory.join("dzahui-font.png")).unwrap();
+        let font = std::fs::read_to_string("./assets/dzahui-font_test.fnt").unwrap();
+
+        let write_and_read = |name: &str, from: &str, to: &str| {
+            let path = font_directory.join(name);
+            std::fs::write(&path, font.replacen(from, to, 1)).unwrap();
+            CharacterSet::new(path.to_str().unwrap())
+        };
+        let bad_width = write_and_read("width.fnt", "width=21", "width=2x1");
+        let negative_height = write_and_read("height.fnt", "height=37", "height=-37");
+        let nan_advance = write_and_read("advance.fnt", "xadvance=4 ", "xadvance=NaN ");
+        let exponent = write_and_read("exponent.fnt", "lineHeight=19", "lineHeight=1.9e1");
+        std::fs::remove_dir_all(&font_directory).unwrap();
+
+        match bad_width {
+            Err(Error::Parse(e)) => assert!(e.contains("'width'") && e.contains("line 6")),
+            _ => panic!("Malformed width should not be parsed"),
+        }
+        match negative_height {
+            Err(Error::Parse(e)) => assert!(e.contains("'height'") && e.contains("line 7") && e.contains("negative")),
+            _ => panic!("Negative height should not be parsed"),
+        }
+        match nan_advance {
+            Err(Error::Parse(e)) => assert!(e.contains("'xadvance'") && e.contains("line 5")),
+            _ => panic!("NaN advance should not be parsed"),
+        }
+        assert!(exponent.unwrap().line_height == 19);
+    }
 }
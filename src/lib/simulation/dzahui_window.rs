@@ -1,24 +1,40 @@
 // Internal dependencies
 use crate::{mesh::{mesh_builder::{MeshBuilder, MeshDimension}, Mesh},
-    solvers::{Solver, DiffussionSolverTimeDependent, DiffussionSolverTimeIndependent,
-        solver_trait::DiffEquationSolver, DiffussionParamsTimeDependent, DiffussionParamsTimeIndependent, NoSolver, StaticPressureSolver, StokesParams1D
-    }, Error, writer::{self, Writer}, logger
+    solvers::{Solver, diffusion_solver::element_gradients, quadrature::DEFAULT_INTEGRATION_ITERATION, from_scalar, to_scalar,
+        solver_trait::DiffEquationSolver, DiffussionParamsTimeDependent, DiffussionParamsTimeIndependent, PoissonParams1D, StokesParams1D
+    }, Error, writer::{self, ElementData, Output, Writer}, logger
 };
-use super::{shader::Shader, drawable::{text::CharacterSet, binder::{Bindable, Drawable}}, camera::{cone::Cone, Camera, CameraBuilder}};
+use super::{boundary_editor::BoundaryEditor, checkpoint::{Progress, SimulationState, SolverParams}, shader::Shader, drawable::{text::CharacterSet, line_strip::LineStrip, color_bar::ColorBar, binder::{Bindable, Drawable}}, camera::{cone::Cone, Camera, CameraBuilder}, profiler::FrameProfiler, render_mode::{CullMode, RenderMode, RenderPass}, status::StatusRecord, step_control::{FixedStepAccumulator, StepControl}};
 
 
 // External dependencies
 use glutin::{
     dpi::PhysicalSize,
-    event::{DeviceEvent, ElementState, Event, WindowEvent},
+    event::{DeviceEvent, ElementState, Event, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
     window::{Window, WindowBuilder},
     Api, ContextBuilder, ContextWrapper, GlProfile, GlRequest, PossiblyCurrent,
 };
 use cgmath::{Matrix4, Point2, Point3, SquareMatrix, Vector3};
-use std::{time::Instant, sync::mpsc::{self, SyncSender}};
+use std::{path::Path, time::Instant, sync::mpsc::{self, SyncSender}};
 use gl;
 
+/// Color of picking ray when debugging vertex selector
+const SELECTOR_RAY_COLOR: [f64; 3] = [1.0, 1.0, 0.0];
+/// Color of cone axis when debugging vertex selector
+const SELECTOR_AXIS_COLOR: [f64; 3] = [0.0, 1.0, 0.0];
+/// Color of cone outline when debugging vertex selector
+const SELECTOR_CONE_COLOR: [f64; 3] = [0.0, 1.0, 1.0];
+/// Color of vertex picked last with vertex selector
+const HIGHLIGHT_COLOR: [f64; 3] = [1.0, 1.0, 1.0];
+/// Color of reference solution drawn over 1D solutions
+const REFERENCE_COLOR: [f64; 3] = [1.0, 0.0, 1.0];
+/// Color of boundary vertices when boundary is shown
+const BOUNDARY_COLOR: [f64; 3] = [1.0, 0.0, 0.0];
+/// Color of interior vertices when boundary is shown
+const INTERIOR_COLOR: [f64; 3] = [0.0, 0.0, 1.0];
+/// Samples of reference solution per element of 1D mesh
+const REFERENCE_SAMPLES_PER_ELEMENT: usize = 10;
 
 /// # General Information
 ///
@@ -26,13 +42,12 @@ use gl;
 ///
 /// # Fields
 ///
-/// * `context` - Holds an *instance* of OpenGL. This normally means that all configuration associated with rendering is stored here. Only one context is allowed
 /// * `geometry_shader` - Geometry_shaders to compile and use. Responsible for mesh drawing
 /// * `event_loop` - To obtain user input in window and refresh window
 /// * `mouse_coordinates` - Current coordinates of mouse
-/// * `initial_time_step` - When solving a time-dependent problem and not specifiying a time, an initial time should be given while enough information is collected
-/// to use framerate
-/// * `character_set` - Set of characters to draw on screen
+/// * `initial_time_step` - When solving a time-dependent problem on real time, size of every step. As many steps as fit in real time elapsed are made
+///   on every frame, so results do not depend on framerate
+/// * `character_set` - Set of characters to draw on screen. None when text is disabled, in which case nothing is written on screen
 /// * `integration_iteration` - Amount of terms to approximate integral
 /// * `height` - Height of window created
 /// * `width` - Width of window created
@@ -46,14 +61,36 @@ use gl;
 /// * `mesh` - A mesh to draw to screen. Represents an object tessellated into triangles/traingular prisms
 /// * `write_location` - Where to write values from solved equation of needed
 /// * `file_prefix`- If writing files require a prefix to identify them
+/// * `profiling` - Wether time spent on solver, GPU update and drawing should be measured and logged
+/// * `solution_profile` - Line strip drawn over 1D meshes following the solution at every node. None for 2D and 3D meshes
+/// * `line_plot` - Wether a 1D solution is drawn only as a colored line plot of its values instead of a colored bar with a profile over it
+/// * `reference_solution` - Curve of a reference (normally analytic) solution drawn on the scale of `solution_profile`, with its value at every vertex. None when not set
+/// * `precision` - Decimal places of numbers written on screen and to files. Default float formatting when None
+/// * `output_stride` - Only every k-th node is written to files, along with its index. Every node is written (without index) when None
+/// * `color_range` - Fixed range of solution values mapped to the color gradient. Adjusted to every frame when None
+/// * `color_bar` - Legend of the color gradient with labels of its minimum and maximum. None when no equation is solved
+/// * `max_steps` - Amount of solver steps after which solving stops. No limit when None
+/// * `max_sim_time` - Simulated time after which solving stops. No limit when None
+/// * `auto_save` - Wether solution should be written to a file once solving stops because of `max_steps` or `max_sim_time`
+/// * `output` - Wether solutions can be written to files at all. No writer thread is spawned otherwise
+/// * `debug_selector` - Wether picking ray, cone axis and cone outline of `vertex_selector` should be drawn after every click
+/// * `selector_lines` - Picking ray, cone axis and cone outline of last click, in that order. Empty when `debug_selector` is off
+/// * `model_transform` - Additional transformation applied to mesh after it has been centered by its own model matrix
+/// * `fields` - Named scalar fields of last solution (like `v_x`, `v_y` and `p`). Empty until an equation is solved
+/// * `selected_field` - Index of field in `fields` mapped to colors and written on screen
+/// * `picking_index` - Desired vertices per cell of spatial index used by `vertex_selector`. Every vertex is tested on each click when None
+/// * `status_log` - Milliseconds between status records (step, simulated time, residual, FPS and mouse coordinates) written to log as JSON. Not written when None
+/// * `boundary_editor` - Conditions assigned by clicking boundary vertices (toggled with B), kept for the next time a solver is built
+/// * `progress` - Last solution, steps, simulated time and solver state. Written to checkpoints and, when restored, continued from
+/// * `boundary_view` - While boundary and interior vertices are drawn apart (toggled with O), colors they would otherwise have. None when not shown
+/// * `context` - Holds an *instance* of OpenGL. This normally means that all configuration associated with rendering is stored here. Only one context is allowed. Last field, so that it outlives everything holding GPU ids
 ///
 pub struct DzahuiWindow {
-    context: ContextWrapper<PossiblyCurrent, Window>,
     pub(crate) geometry_shader: Shader,
     event_loop: Option<EventLoop<()>>,
     mouse_coordinates: Point2<f32>,
     initial_time_step: Option<f64>,
-    character_set: CharacterSet,
+    character_set: Option<CharacterSet>,
     integration_iteration: usize,
     pub(crate) height: u32,
     pub(crate) width: u32,
@@ -67,6 +104,30 @@ pub struct DzahuiWindow {
     mesh: Mesh,
     write_location: String,
     file_prefix: String,
+    profiling: bool,
+    solution_profile: Option<LineStrip>,
+    line_plot: bool,
+    reference_solution: Option<(LineStrip, Vec<f64>)>,
+    precision: Option<usize>,
+    output_stride: Option<usize>,
+    color_range: Option<(f64, f64)>,
+    color_bar: Option<ColorBar>,
+    max_steps: Option<usize>,
+    max_sim_time: Option<f64>,
+    auto_save: bool,
+    output: bool,
+    debug_selector: bool,
+    selector_lines: Vec<LineStrip>,
+    model_transform: Matrix4<f32>,
+    fields: Vec<(String, Vec<f64>)>,
+    selected_field: usize,
+    picking_index: Option<usize>,
+    status_log: Option<u64>,
+    boundary_editor: BoundaryEditor,
+    progress: Progress,
+    boundary_view: Option<Vec<[f64; 3]>>,
+    // Fields are dropped in declaration order: binders of mesh, text and lines release their ids while context is still alive
+    context: ContextWrapper<PossiblyCurrent, Window>,
 }
 
 /// # General Information
@@ -82,11 +143,12 @@ pub struct DzahuiWindow {
 /// * `height_multiplier` - Makes height of mesh bigger. Useful for 1D mesh.
 /// * `integration_iteration` - Amount of elements to sum to approximate integral
 /// * `opengl_version` - opengl version to use. Tested with 3.3, latter versions should work too
-/// * `initial_time_step` - When solving a time-dependent problem and not specifiying a time, an initial time should be given while enough information is collected
-/// to use framerate
+/// * `initial_time_step` - When solving a time-dependent problem on real time, size of every step. As many steps as fit in real time elapsed are made
+///   on every frame, so results do not depend on framerate
 /// * `window_text_scale` - Scale of text in front of window. This text does not change with camera view
 /// * `mesh_dimension` - Dimension of mesh to build. Used to process certain elements of solution
 /// * `character_set` - Set of characters to draw on screen
+/// * `character_asset_dir` - Directory with texture image of character set. Defaults to directory of fnt file
 /// * `vertex_selector` - Angle for the cone that casts mouse coordinates to 3d world and selects vertices
 /// * `time_step` - How much to advance a time-dependent solution 
 /// * `camera` - A CameraBuilder. Certain properties can be changend via this structure's methods
@@ -96,6 +158,23 @@ pub struct DzahuiWindow {
 /// * `solver` - An enum representing the equation to be solved
 /// * `write_location` - Where to write values from solved equation of needed. Will be chosen automatically if None
 /// * `file_prefix`- If writing files require a prefix to identify them. Will be chosen automatically if None
+/// * `log_level` - Level of logging handed to the logger when window is built. If None, level already set (Info by default) is kept
+/// * `profiling` - Wether time spent on every stage of a frame should be measured. Defaults to false
+/// * `point_size` - Size in pixels of vertices when a mesh has no faces and is drawn as points. Defaults to 5
+/// * `face_culling` - Which faces of triangles are discarded. Defaults to none, so every triangle is seen from both sides
+/// * `title` - Title of window. Defaults to "Dzahui"
+/// * `precision` - Decimal places of numbers written on screen and to files. Defaults to default float formatting
+/// * `output_stride` - Only every k-th node is written to files. Defaults to every node
+/// * `color_range` - Fixed range of solution values mapped to the color gradient. Defaults to every frame's min and max
+/// * `max_steps` - Amount of solver steps after which solving stops. Defaults to no limit
+/// * `max_sim_time` - Simulated time after which solving stops. Defaults to no limit
+/// * `auto_save` - Wether solution should be written to a file once solving stops because of a budget. Defaults to false
+/// * `output` - Wether solutions can be written to files. Defaults to true
+/// * `text` - Wether a character set is loaded and text (coordinates, FPS and color bar labels) is written on screen. Defaults to true
+/// * `picking_index` - Desired vertices per cell of spatial index used when picking vertices. Defaults to no index
+/// * `line_plot` - Wether 1D solutions are drawn as a line plot instead of a bar. Defaults to false
+/// * `status_log` - Milliseconds between status records written to log. Defaults to no records
+/// * `restored_state` - Checkpoint to continue from. Its mesh (and equation, when written) replace the ones given. Defaults to starting over
 ///
 #[derive(Debug)]
 pub struct DzahuiWindowBuilder {
@@ -110,6 +189,7 @@ pub struct DzahuiWindowBuilder {
     window_text_scale: Option<f32>,
     mesh_dimension: MeshDimension,
     character_set: Option<String>,
+    character_asset_dir: Option<String>,
     vertex_selector: Option<f32>,
     time_step: Option<f64>,
     camera: CameraBuilder,
@@ -118,7 +198,24 @@ pub struct DzahuiWindowBuilder {
     mesh: MeshBuilder,
     solver: Solver,
     write_location: Option<String>,
-    file_prefix: Option<String>
+    file_prefix: Option<String>,
+    log_level: Option<log::LevelFilter>,
+    profiling: bool,
+    point_size: Option<f32>,
+    face_culling: Option<CullMode>,
+    title: Option<String>,
+    precision: Option<usize>,
+    output_stride: Option<usize>,
+    color_range: Option<(f64, f64)>,
+    max_steps: Option<usize>,
+    max_sim_time: Option<f64>,
+    auto_save: bool,
+    output: bool,
+    text: bool,
+    picking_index: Option<usize>,
+    line_plot: bool,
+    status_log: Option<u64>,
+    restored_state: Option<SimulationState>,
 }
 
 impl DzahuiWindowBuilder {
@@ -127,8 +224,10 @@ impl DzahuiWindowBuilder {
     where
         F: AsRef<str>,
     {
-        // Spawning logger
-        logger::spawn(log::LevelFilter::Info, "dzahui").unwrap();
+        // Spawning logger. A previously configured level is kept
+        if !logger::is_spawned() {
+            logger::spawn(log::LevelFilter::Info, "dzahui").unwrap();
+        }
         
         Self {
             mesh_dimension: MeshDimension::Two,
@@ -146,11 +245,29 @@ impl DzahuiWindowBuilder {
             vertex_selector: None,
             solver: Solver::None,
             character_set: None,
+            character_asset_dir: None,
             height: Some(600),
             width: Some(800),
             time_step: None,
             write_location: None,
-            file_prefix: None
+            file_prefix: None,
+            log_level: None,
+            profiling: false,
+            point_size: None,
+            face_culling: None,
+            title: None,
+            precision: None,
+            output_stride: None,
+            color_range: None,
+            max_steps: None,
+            max_sim_time: None,
+            auto_save: false,
+            output: true,
+            text: true,
+            picking_index: None,
+            line_plot: false,
+            status_log: None,
+            restored_state: None,
         }
     }
     /// Changes geometry shader.
@@ -249,6 +366,14 @@ impl DzahuiWindowBuilder {
             ..self
         }
     }
+    /// Changes near and far planes of projection. By default, they are chosen so that the whole mesh is visible
+    pub fn with_near_far(self, near: f32, far: f32) -> Self {
+        log::warn!("Changing near and far planes could clip simulation view");
+        Self {
+            camera: self.camera.with_near_far(near, far),
+            ..self
+        }
+    }
     /// Changes camera movement arround object being targeted
     pub fn with_sensitivity(self, sensitivity: f32) -> Self {
         log::warn!("Changing camera sensitivity can make harder to control simulation perspective");
@@ -271,6 +396,25 @@ impl DzahuiWindowBuilder {
             ..self
         }
     }
+    /// Changes initial color (RGB) of mesh vertices, which is what shows when no equation is solved. Blue by default
+    pub fn with_base_color(self, base_color: [f32; 3]) -> Self {
+        Self {
+            mesh: self.mesh.with_base_color(base_color),
+            ..self
+        }
+    }
+    /// Uses a generated 1D mesh instead of an .obj. Nodes are placed warping a uniform parameter in [0,1] with `mapping` (see `MeshBuilder::mesh_1d_from_mapping`)
+    pub fn with_mapped_mesh_1d<M: Fn(f64) -> f64>(self, start: f64, end: f64, n: usize, mapping: M) -> Self {
+        let mesh = match MeshBuilder::mesh_1d_from_mapping(start, end, n, mapping) {
+            Ok(mesh) => mesh,
+            Err(e) => panic!("Unable to generate 1D mesh!: {}", e),
+        };
+        Self {
+            mesh,
+            mesh_dimension: MeshDimension::One,
+            ..self
+        }
+    }
     /// Makes diffusion solver simulation
     pub fn solve_1d_diffussion(self, params: DiffussionParamsTimeIndependent) -> Self {
         Self {
@@ -287,6 +431,14 @@ impl DzahuiWindowBuilder {
             ..self
         }
     }
+    /// Makes Poisson solver simulation (-u_xx = f with Dirichlet ends). Simplest equation to start with
+    pub fn solve_1d_poisson(self, params: PoissonParams1D) -> Self {
+        Self {
+            solver: Solver::Poisson1DSolver(params),
+            mesh_dimension: MeshDimension::One,
+            ..self
+        }
+    }
     /// Makes Stokes time-independent solver simulation
     pub fn solve_1d_stokes(self, params: StokesParams1D) -> Self {
         Self {
@@ -325,7 +477,7 @@ impl DzahuiWindowBuilder {
             ..self
         }
     }
-    /// Initial time step when simulation on real time
+    /// Time step when simulation on real time. It stays fixed: instead, the amount of steps made on every frame follows real time elapsed
     pub fn with_initial_time_step(self, initial_time_step: f64) -> Self {
         if let Some(_) = self.time_step {
             log::warn!("time_step is set, therefore initial_time_step should not be set since simulation will not occur in real-time");
@@ -336,20 +488,226 @@ impl DzahuiWindowBuilder {
             ..self
         }
     }
-    /// Set file location. If let None, a predetermined will be chosen later
+    /// Set file location. If let None, a predetermined will be chosen later.
+    /// A file name (like "out.vtu") writes next to it with its name as prefix, and its extension chooses format (csv, json, vtu or raw)
     pub fn set_file_location<A: AsRef<str>>(self, write_location: A) -> Self {
         Self {
             write_location: Some(write_location.as_ref().to_string()),
             ..self
         }
     }
-    /// Set file prefix. If let None, a predetermined will be chosen later
+    /// Set file prefix. If let None, a predetermined will be chosen later. Its extension (csv, json, vtu) chooses format, CSV being the default
     pub fn set_file_prefix<A: AsRef<str>>(self, file_prefix: A) -> Self {
         Self {
             file_prefix: Some(file_prefix.as_ref().to_string()),
             ..self
         }
     }
+    /// Changes level of logging once window is built. Use `Debug` to diagnose mesh problems or `Off` to silence logs
+    pub fn with_log_level(self, log_level: log::LevelFilter) -> Self {
+        Self {
+            log_level: Some(log_level),
+            ..self
+        }
+    }
+    /// Changes size (in pixels) of vertices when mesh has no faces and is drawn as points
+    pub fn with_point_size(self, point_size: f32) -> Self {
+        Self {
+            point_size: Some(point_size),
+            ..self
+        }
+    }
+    /// Changes which faces of triangles are discarded (see `CullMode`)
+    pub fn with_face_culling(self, face_culling: CullMode) -> Self {
+        Self {
+            face_culling: Some(face_culling),
+            ..self
+        }
+    }
+    /// Changes font used to write on screen. Texture named inside fnt file has to be in the same directory as fnt file
+    pub fn with_character_set<A: AsRef<str>>(self, character_file: A) -> Self {
+        Self {
+            character_set: Some(character_file.as_ref().to_string()),
+            character_asset_dir: None,
+            ..self
+        }
+    }
+    /// Changes font used to write on screen. Texture named inside fnt file is looked for in `asset_dir`
+    pub fn with_character_set_and_asset_dir<A: AsRef<str>, B: AsRef<str>>(self, character_file: A, asset_dir: B) -> Self {
+        Self {
+            character_set: Some(character_file.as_ref().to_string()),
+            character_asset_dir: Some(asset_dir.as_ref().to_string()),
+            ..self
+        }
+    }
+    /// Draws 1D solutions as a line plot (nodes against solution values, rescaled to fit) colored with the gradient, instead of a colored bar
+    pub fn with_1d_line_plot(self) -> Self {
+        Self {
+            line_plot: true,
+            ..self
+        }
+    }
+    /// Picks vertices with a grid over their positions (built on first click) holding about `points_per_cell` vertices per cell, instead of testing every vertex
+    pub fn with_picking_index(self, points_per_cell: usize) -> Self {
+        if points_per_cell == 0 {
+            panic!("Cells of picking index need to hold at least one vertex");
+        }
+        Self {
+            picking_index: Some(points_per_cell),
+            ..self
+        }
+    }
+    /// Skips loading character set, so that nothing is written on screen. Useful for clean screenshots
+    pub fn without_text(self) -> Self {
+        Self {
+            text: false,
+            ..self
+        }
+    }
+    /// Changes title of window
+    pub fn with_title<A: AsRef<str>>(self, title: A) -> Self {
+        Self {
+            title: Some(title.as_ref().to_string()),
+            ..self
+        }
+    }
+    /// Changes amount of decimal places of numbers written on screen and to files
+    pub fn with_precision(self, decimals: usize) -> Self {
+        Self {
+            precision: Some(decimals),
+            ..self
+        }
+    }
+    /// Writes only every `stride`-th node of a solution to files, preceded by its index. Useful for very fine meshes
+    pub fn with_output_stride(self, stride: usize) -> Self {
+        if stride == 0 {
+            panic!("Output stride should be positive");
+        }
+        Self {
+            output_stride: Some(stride),
+            ..self
+        }
+    }
+    /// Maps solution values to colors using a fixed range instead of every frame's min and max, so that colors
+    /// mean the same across a transient simulation. Values outside the range are clamped
+    pub fn with_fixed_color_range(self, min: f64, max: f64) -> Self {
+        if min >= max {
+            panic!("Color range minimum should be smaller than maximum");
+        }
+        Self {
+            color_range: Some((min, max)),
+            ..self
+        }
+    }
+    /// Stops solving after `max_steps` solver steps. Window stays open to inspect the last solution
+    pub fn with_max_steps(self, max_steps: usize) -> Self {
+        Self {
+            max_steps: Some(max_steps),
+            ..self
+        }
+    }
+    /// Stops solving once simulated time reaches `max_sim_time`. Window stays open to inspect the last solution
+    pub fn with_max_sim_time(self, max_sim_time: f64) -> Self {
+        if max_sim_time <= 0.0 {
+            panic!("Maximum simulated time should be positive");
+        }
+        Self {
+            max_sim_time: Some(max_sim_time),
+            ..self
+        }
+    }
+    /// Writes solution to a file when solving stops because of `with_max_steps` or `with_max_sim_time`
+    pub fn with_auto_save(self) -> Self {
+        Self {
+            auto_save: true,
+            ..self
+        }
+    }
+    /// Never writes solutions to files, so no writer thread is spawned. Saving and `with_auto_save` do nothing.
+    /// Visualization-only windows (without an equation) never write anyway
+    pub fn without_output(self) -> Self {
+        Self {
+            output: false,
+            ..self
+        }
+    }
+    /// Measures time spent on solver, GPU update and drawing per frame. Averages are logged every second
+    pub fn with_profiling(self) -> Self {
+        Self {
+            profiling: true,
+            ..self
+        }
+    }
+    /// Logs step, simulated time, residual (change between last two solutions), FPS and mouse coordinates as a JSON object every `period_ms` milliseconds
+    pub fn with_status_log(self, period_ms: u64) -> Self {
+        if period_ms == 0 {
+            panic!("Period of status log should be positive");
+        }
+        Self {
+            status_log: Some(period_ms),
+            ..self
+        }
+    }
+
+    /// Continues a simulation from a checkpoint written with `DzahuiWindow::save_state`. Mesh is taken from checkpoint, and so is the equation
+    /// when its params could be written; otherwise the equation given to builder is used.
+    pub fn restore_state<P: AsRef<Path>>(self, path: P) -> Self {
+        let state = match SimulationState::load(path) {
+            Ok(state) => state,
+            Err(e) => panic!("Unable to read simulation checkpoint!: {}", e),
+        };
+        let solver = match state.params.clone() {
+            Some(params) => params.into_solver(),
+            None => self.solver,
+        };
+        Self {
+            mesh_dimension: state.mesh.dimension,
+            solver,
+            restored_state: Some(state),
+            ..self
+        }
+    }
+
+    /// Window configuration (title, size and resizability) to create context with.
+    fn window_builder(&self, height: u32, width: u32) -> WindowBuilder {
+        WindowBuilder::new()
+            .with_title(self.title.as_deref().unwrap_or("Dzahui"))
+            .with_inner_size(PhysicalSize {
+                height,
+                width,
+            })
+            .with_resizable(true)
+    }
+
+    /// # General Information
+    ///
+    /// Reads character set chosen (or default one) from its fnt file and texture. None when text has been disabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Builder with character set configuration
+    ///
+    fn load_character_set(&self) -> Result<Option<CharacterSet>, Error> {
+        if !self.text {
+            log::info!("Text is disabled, no character set is loaded");
+            return Ok(None);
+        }
+
+        // Default character set
+        let character_set_file = if let Some(set_file) = &self.character_set {
+            set_file.clone()
+        } else {
+            "assets/dzahui-font_2.fnt".to_string()
+        };
+        let character_set = if let Some(asset_dir) = &self.character_asset_dir {
+            CharacterSet::new_with_asset_dir(&character_set_file, asset_dir)
+        } else {
+            CharacterSet::new(&character_set_file)
+        }?;
+        log::info!("Character set loaded");
+
+        Ok(Some(character_set))
+    }
 
     /// # General Information
     ///
@@ -357,7 +715,7 @@ impl DzahuiWindowBuilder {
     ///
     /// # Details
     ///
-    /// First it generates a window builder with title 'Dzahui', size according to builder and always resizable.
+    /// First it generates a window builder with title 'Dzahui' (unless overriden), size according to builder and always resizable.
     /// Then an OpenGL version is assigned based on builder.
     /// Event loop is generated and, alongside window, made current context.
     /// OpenGL functions are made available and viewport for OpenGL is set.
@@ -372,44 +730,41 @@ impl DzahuiWindowBuilder {
     ///
     /// * `self` - All configuration required is within self. Default shaders are hardcoded in here.
     ///
-    pub fn build(self) -> DzahuiWindow {
+    /// # Errors
+    ///
+    /// When no OpenGL context can be created (see `context_attempts` for versions and profiles tried), or shaders, mesh or character set
+    /// can not be loaded.
+    ///
+    pub fn build(self) -> Result<DzahuiWindow, Error> {
+
+        // Level chosen is handed to logger (which is only spawned once)
+        logger::spawn(selected_log_level(self.log_level, log::max_level()), "dzahui").map_err(|e| Error::custom(format!("Unable to set log level!: {}", e)))?;
     
+        // Font is read before any field is moved out of builder
+        let character_set = self.load_character_set()?;
+
         // Will never be None
         let height = self.height.unwrap();
         let width = self.width.unwrap();
 
-        let window_builder = WindowBuilder::new()
-            .with_title("Dzahui")
-            .with_inner_size(PhysicalSize {
-                height,
-                width,
-            })
-            .with_resizable(true);
-
-        let opengl_version = GlRequest::Specific(Api::OpenGl, self.opengl_version.unwrap());
+        let window_builder = self.window_builder(height, width);
 
         // Generating event_loop to be used
         let event_loop = EventLoop::new();
 
-        // Creating context to use in application
-        let context = match ContextBuilder::new().
-        with_gl(opengl_version).
-        // core GL profile
-        // Future compatible functions. Not backwards compatible (no previous versions of openGL).
-        with_gl_profile(GlProfile::Core).
-        with_vsync(true).
-        build_windowed(window_builder, &event_loop) {
-            Ok(w) => w,
-            Err(e) => panic!("Error on window creation: {}",e)
-        };
+        // Creating context to use in application. Core profile of requested version is preferred, other ones are tried when drivers lack it.
+        let context = create_with_fallback(&context_attempts(self.opengl_version.unwrap()), |version, profile| {
+            ContextBuilder::new()
+                .with_gl(GlRequest::Specific(Api::OpenGl, version))
+                .with_gl_profile(profile)
+                .with_vsync(true)
+                .build_windowed(window_builder.clone(), &event_loop)
+                .map_err(|e| e.to_string())
+        })?;
 
         // The latest instance becomes the current context always
-        let context = unsafe { 
-            match context.make_current() {
-                Ok(context) => context,
-                Err(e) => panic!("Initializing context for window failed!: {}",e.1)
-            }
-        };
+        let context = unsafe { context.make_current() }
+            .map_err(|e| Error::ContextCreation(format!("context could not be made current: {}", e.1)))?;
         log::info!("Window context created");
 
         // Loading OpenGL functions. Only done once
@@ -424,7 +779,10 @@ impl DzahuiWindowBuilder {
                 height as i32,
             );
             gl::Enable(gl::DEPTH_TEST);
+            // Only used when mesh has no faces
+            gl::PointSize(self.point_size.unwrap_or(5.0));
         }
+        self.face_culling.unwrap_or(CullMode::None).apply();
         log::info!("OpenGL functions loaded");
 
         // Use text_shaders chosen
@@ -441,10 +799,7 @@ impl DzahuiWindowBuilder {
             "./assets/text_fragment_shader.fs".to_string()
         };
 
-        let text_shader = match Shader::new(vertex_shader, fragment_shader) {
-            Ok(shader) => shader,
-            Err(e) => panic!("Error on text shader creation!: {}", e)
-        };
+        let text_shader = Shader::new(vertex_shader, fragment_shader)?;
 
         // Use geometry_shaders chosen
         let vertex_shader: String = if let Some(vertex_shader) = self.geometry_vertex_shader {
@@ -460,28 +815,54 @@ impl DzahuiWindowBuilder {
             "./assets/geometry_fragment_shader.fs".to_string()
         };
 
-        let geometry_shader = match Shader::new(vertex_shader, fragment_shader) {
-            Ok(shader) => shader,
-            Err(e) => panic!("Error on geometry shader creation!: {}",e)
+        let geometry_shader = Shader::new(vertex_shader, fragment_shader)?;
+
+        // Creating mesh based on initial provided file, or on checkpoint being restored.
+        let (mesh, progress) = match self.restored_state {
+            Some(state) => {
+                log::info!("Continuing simulation from step {}", state.progress.steps);
+                (Mesh::from_snapshot(state.mesh), state.progress)
+            }
+            None => {
+                let mesh = match self.height_multiplier {
+                    Some(height_multiplier) => self.mesh.with_height_multiplier(height_multiplier),
+                    None => self.mesh,
+                }
+                .build(self.mesh_dimension)?;
+                (mesh, Progress::default())
+            }
         };
 
-        // Creating mesh based on initial provided file.
-        let mesh = match match self.mesh_dimension {
+        // 1D solutions are also drawn as a profile above the bar (whose height is the second coordinate of the upper half of vertices),
+        // or as a line plot in place of the bar
+        let solution_profile = match self.mesh_dimension {
             MeshDimension::One => {
-                log::info!("Creating a 1D Mesh");
-                self.mesh.build_mesh_1d(self.height_multiplier)
-            },
-            MeshDimension::Two => {
-                log::info!("Creating a 2D Mesh");
-                self.mesh.build_mesh_2d()
-            },
-            MeshDimension::Three => {
-                log::info!("Creating a 3D Mesh");
-                self.mesh.build_mesh_3d()
+                let bar_height = mesh.vertices[mesh.vertices.len() / 2 + 1];
+                let nodes = mesh.filter_for_solving_1d().to_vec();
+                if self.line_plot {
+                    Some(LineStrip::line_plot(&nodes, bar_height, mesh.max_length))
+                } else {
+                    Some(LineStrip::new(&nodes, bar_height, mesh.max_length / 2.0))
+                }
             },
-        } {
-            Ok(mesh) => mesh,
-            Err(e) => panic!("Error while creating mesh!: {}", e)
+            _ => None
+        };
+
+        // Conditions can only be edited on vertices of mesh's boundary (ends of bar in 1D)
+        let boundary_editor = match mesh.dimension {
+            MeshDimension::One => BoundaryEditor::new_1d(mesh.vertices.len() / 12),
+            _ => BoundaryEditor::new(mesh.boundary_indices.as_ref()),
+        };
+
+        // Legend of colors on the right side of the screen whenever an equation is solved
+        let color_bar = match self.solver {
+            Solver::None => None,
+            _ => Some(ColorBar::new(
+                (0.8, -0.6),
+                (0.05, 1.2),
+                32,
+                self.color_range.unwrap_or((0.0, 0.0)),
+            )),
         };
 
         let window_text_scale = if let Some(sc) = self.window_text_scale {
@@ -520,8 +901,8 @@ impl DzahuiWindowBuilder {
             log::info!("Integration iteration is {}",integration_iteration);
             integration_iteration
         } else {
-            log::info!("Integration iteration is 150");
-            150
+            log::info!("Integration iteration is {}", DEFAULT_INTEGRATION_ITERATION);
+            DEFAULT_INTEGRATION_ITERATION
         };
 
         // Set initial value for time step.
@@ -537,17 +918,6 @@ impl DzahuiWindowBuilder {
             }
         };
 
-        // Default character set
-        let character_set_file = if let Some(set_file) = self.character_set {
-            set_file
-        } else {
-            "assets/dzahui-font_2.fnt".to_string()
-        };
-        let character_set = match CharacterSet::new(&character_set_file) {
-            Ok(chs) => chs,
-            Err(e) => panic!("Error while creating character set!: {}",e)
-        };
-        log::info!("Character set loaded");
 
         // Writing location setting
         let write_location = if let Some(s) = self.write_location {
@@ -566,7 +936,7 @@ impl DzahuiWindowBuilder {
         // Start clock for delta time
         let timer = Instant::now();
 
-        DzahuiWindow {
+        Ok(DzahuiWindow {
             context,
             timer,
             geometry_shader,
@@ -586,9 +956,79 @@ impl DzahuiWindowBuilder {
             mouse_coordinates: Point2::new(0.0, 0.0),
             solver: self.solver,
             initial_time_step: self.initial_time_step,
+            profiling: self.profiling,
+            solution_profile,
+            line_plot: self.line_plot && matches!(self.mesh_dimension, MeshDimension::One),
+            reference_solution: None,
+            precision: self.precision,
+            output_stride: self.output_stride,
+            color_range: self.color_range,
+            color_bar,
+            max_steps: self.max_steps,
+            max_sim_time: self.max_sim_time,
+            auto_save: self.auto_save,
+            output: self.output,
+            debug_selector: false,
+            selector_lines: vec![],
+            model_transform: Matrix4::identity(),
+            fields: vec![],
+            selected_field: 0,
+            picking_index: self.picking_index,
+            status_log: self.status_log,
+            boundary_editor,
+            progress,
+            boundary_view: None,
+        })
+    }
+}
+
+/// # General Information
+///
+/// OpenGL versions and profiles to try, in order, when creating a context: requested version with core profile, then with compatibility profile,
+/// and then the same for every lower version down to 3.3. Lower versions are never tried since default shaders need GLSL 330.
+///
+/// # Parameters
+///
+/// * `requested` - OpenGL version asked for on builder
+///
+fn context_attempts(requested: (u8, u8)) -> Vec<((u8, u8), GlProfile)> {
+    let lower_versions = [(4, 5), (4, 4), (4, 3), (4, 2), (4, 1), (4, 0), (3, 3)]
+        .into_iter()
+        .filter(|version| *version < requested);
+
+    std::iter::once(requested)
+        .chain(lower_versions)
+        .flat_map(|version| [(version, GlProfile::Core), (version, GlProfile::Compatibility)])
+        .collect()
+}
 
+/// # General Information
+///
+/// Calls `create` with every version and profile in `attempts` until one of them succeeds.
+///
+/// # Parameters
+///
+/// * `attempts` - Versions and profiles in the order they should be tried
+/// * `create` - Creates a context for a version and profile, or explains why it could not
+///
+fn create_with_fallback<T, F>(attempts: &[((u8, u8), GlProfile)], mut create: F) -> Result<T, Error>
+where
+    F: FnMut((u8, u8), GlProfile) -> Result<T, String>,
+{
+    let mut tried = vec![];
+    for (version, profile) in attempts {
+        match create(*version, *profile) {
+            Ok(context) => {
+                log::info!("Using OpenGL {}.{} with {:?} profile", version.0, version.1, profile);
+                return Ok(context);
+            }
+            Err(e) => {
+                log::warn!("Unable to create OpenGL {}.{} context with {:?} profile: {}", version.0, version.1, profile, e);
+                tried.push(format!("{}.{} {:?} ({})", version.0, version.1, profile, e));
+            }
         }
     }
+    Err(Error::ContextCreation(tried.join(", ")))
 }
 
 impl DzahuiWindow {
@@ -605,6 +1045,61 @@ impl DzahuiWindow {
         self.timer = Instant::now();
     }
 
+    /// Camera currently used to draw the scene. Its view and projection matrices can be used to render external overlays.
+    pub fn camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    /// Model matrix sent to geometry shader: mesh's own model matrix followed by `model_transform`.
+    fn model_matrix(&self) -> Matrix4<f32> {
+        compose_model_matrix(&self.model_transform, self.mesh.get_model_matrix())
+    }
+
+    /// # General Information
+    ///
+    /// Poses mesh with an additional transformation (rotation, translation, scaling) applied after it has been centered. Replaces any previous
+    /// transformation and sends resulting model matrix to geometry shader.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Window whose mesh is posed
+    /// * `transform` - Transformation in world coordinates. Identity leaves mesh as it is built
+    ///
+    pub fn set_model_transform(&mut self, transform: Matrix4<f32>) -> Result<(), Error> {
+        self.model_transform = transform;
+        self.geometry_shader.use_shader();
+        self.geometry_shader.set_mat4("model", &self.model_matrix())
+    }
+
+    /// # General Information
+    ///
+    /// Draws a reference (normally analytic) solution over a 1D solution in its own color, to compare them visually. Function is sampled
+    /// several times per element and drawn on the same scale as the solution profile. Replaces any previous reference.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Window with a 1D mesh
+    /// * `function` - Reference solution
+    ///
+    pub fn set_reference_solution<F: Fn(f64) -> f64>(&mut self, function: F) -> Result<(), Error> {
+        let profile = self
+            .solution_profile
+            .as_ref()
+            .ok_or_else(|| Error::custom("A reference solution can only be drawn over a 1D mesh"))?;
+
+        let (mut reference, values) = profile.sampled_like(function, REFERENCE_SAMPLES_PER_ELEMENT, REFERENCE_COLOR);
+        // Until a solution is obtained, reference is drawn on its own scale
+        reference.update(&values)?;
+        self.reference_solution = Some((reference, values));
+
+        Ok(())
+    }
+
+    /// Changes title of window while it's running.
+    pub fn set_title(&self, title: &str) {
+        self.context.window().set_title(title);
+    }
+
     /// Callback to change mouse coordinates.
     pub fn update_mouse_coordinates(&mut self, x: f32, y: f32) {
         self.mouse_coordinates.x = x;
@@ -619,8 +1114,8 @@ impl DzahuiWindow {
         }
     }
 
-    /// Callback to obtain vertex intersection with click produced cone.
-    fn get_selected_vertex(&mut self) -> Result<(),Error> {
+    /// Callback to obtain vertex intersection with click produced cone. Returns index of vertex selected, if any.
+    fn get_selected_vertex(&mut self) -> Result<Option<usize>,Error> {
         
         self.vertex_selector.change_from_mouse_position(
             &self.mouse_coordinates,
@@ -629,15 +1124,53 @@ impl DzahuiWindow {
             self.height,
         )?;
         
-        let sel_vec = self
-            .vertex_selector
-            .obtain_nearest_intersection(&self.mesh.vertices, &self.camera.view_matrix);
+        let sel_vec = match self.picking_index {
+            Some(points_per_cell) => self
+                .vertex_selector
+                .obtain_nearest_intersection_indexed(self.mesh.spatial_index(points_per_cell), &self.camera.view_matrix),
+            None => self
+                .vertex_selector
+                .obtain_nearest_intersection(&self.mesh.vertices, &self.camera.view_matrix),
+        };
         println!("{:?}", sel_vec);
+
+        self.mesh.highlight_vertex(sel_vec.map(|(_, vertex)| vertex), HIGHLIGHT_COLOR)?;
+        self.mesh.bind_all_no_texture()?;
+        self.mesh.send_to_gpu()?;
+
+        if self.debug_selector {
+            self.update_selector_lines()?;
+        }
+        Ok(sel_vec.map(|(_, vertex)| vertex))
+    }
+
+    /// Rebuilds picking ray, cone axis and cone outline of vertex selector, and sends them to GPU.
+    fn update_selector_lines(&mut self) -> Result<(),Error> {
+        let length = 2.0 * self.camera.radius;
+        let [ray, axis] = self.vertex_selector.debug_segments(&self.camera.view_matrix, length)?;
+        let outline = self.vertex_selector.outline(&self.camera.view_matrix, length, 32)?;
+
+        self.selector_lines = [
+            (ray.to_vec(), SELECTOR_RAY_COLOR),
+            (axis.to_vec(), SELECTOR_AXIS_COLOR),
+            (outline, SELECTOR_CONE_COLOR),
+        ]
+            .into_iter()
+            .map(|(points, color)| {
+                let points: Vec<[f64; 3]> = points.iter().map(|point| [point.x as f64, point.y as f64, point.z as f64]).collect();
+                let mut line = LineStrip::from_points(&points, color);
+                line.setup()?;
+                line.send_to_gpu()?;
+                Ok(line)
+            })
+            .collect::<Result<Vec<LineStrip>, Error>>()?;
         Ok(())
     }
 
-    /// Callback to change camera view matrix based on user motion.
-    fn change_camera_view(&mut self, x: f32, y: f32) {
+    /// Callback to change camera view matrix based on user motion. Motion accumulated from mouse is released according to
+    /// `dt` (seconds since last frame) so that camera speed does not depend on framerate.
+    fn change_camera_view(&mut self, dt: f32) {
+        let (x, y) = self.camera.take_motion(dt);
         let x_offset = x * self.camera.camera_sensitivity;
         let y_offset = y * self.camera.camera_sensitivity;
         self.camera.theta -= y_offset;
@@ -651,11 +1184,7 @@ impl DzahuiWindow {
         }
 
         // update position
-        self.camera.camera_position = Point3::new(
-            self.camera.theta.to_radians().sin() * self.camera.phi.to_radians().sin(),
-            self.camera.theta.to_radians().cos(),
-            self.camera.theta.to_radians().sin() * self.camera.phi.to_radians().cos(),
-        ) * self.camera.radius;
+        self.camera.update_position();
 
         // generate new matrix
         self.camera.modify_view_matrix();
@@ -668,8 +1197,98 @@ impl DzahuiWindow {
         self.width = new_size.width;
     }
 
-    /// Send information of vertices to be written
-    fn send_vertex_info(&self, info: Vec<f64>, sender: &SyncSender<Vec<f64>>) {
+    /// # General Information
+    ///
+    /// Maps field selected to colors of mesh, color bar and solution profile, and sends them to GPU. Does nothing when there are no fields yet.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Window with fields of last solution
+    ///
+    fn update_colors(&mut self) -> Result<(), Error> {
+        let values = match self.fields.get(self.selected_field) {
+            Some((_, values)) => values,
+            None => return Ok(()),
+        };
+
+        // updating colors. One time per vertex should be updated (that is, every 6 steps).
+        let color_range = self.mesh.update_gradient_1d(values.iter().map(|x| x.abs()).collect(), self.color_range);
+        if self.boundary_view.is_some() {
+            self.boundary_view = Some(self.mesh.colors());
+            self.mesh.color_boundary(BOUNDARY_COLOR, INTERIOR_COLOR)?;
+        }
+        if let Some(color_bar) = &mut self.color_bar {
+            color_bar.update_range(color_range);
+        }
+
+        self.mesh.bind_all_no_texture()?;
+        self.mesh.send_to_gpu()?;
+        if let Some(profile) = &mut self.solution_profile {
+            let profile_range = profile.update(values)?;
+            if self.line_plot {
+                profile.update_colors(&values.iter().map(|x| x.abs()).collect::<Vec<f64>>(), color_range)?;
+            }
+            profile.bind_all_no_texture()?;
+            profile.send_to_gpu()?;
+
+            // Reference is drawn on scale of solution
+            if let Some((reference, reference_values)) = &mut self.reference_solution {
+                reference.update_in_range(reference_values, profile_range)?;
+                reference.bind_all_no_texture()?;
+                reference.send_to_gpu()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Draws boundary vertices in red and interior ones in blue, or gives them back their colors when they are already drawn that way.
+    fn toggle_boundary_view(&mut self) -> Result<(), Error> {
+        match self.boundary_view.take() {
+            Some(colors) => self.mesh.set_colors(&colors)?,
+            None => {
+                let colors = self.mesh.colors();
+                self.mesh.color_boundary(BOUNDARY_COLOR, INTERIOR_COLOR)?;
+                self.boundary_view = Some(colors);
+            }
+        }
+
+        self.mesh.bind_all_no_texture()?;
+        self.mesh.send_to_gpu()
+    }
+
+    /// # General Information
+    ///
+    /// Writes a checkpoint with mesh, equation params, last solution, steps, simulated time and solver state, so that simulation can be continued later
+    /// with `DzahuiWindowBuilder::restore_state`. Equations with params given as functions (Poisson and Stokes) are not written and have to be given again.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Window whose simulation is written
+    /// * `path` - File to write to. Replaced if it exists
+    ///
+    pub fn save_state<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        SimulationState {
+            mesh: self.mesh.snapshot(),
+            params: SolverParams::from_solver(&self.solver),
+            progress: self.progress.clone(),
+        }
+        .save(path)
+    }
+
+    /// Send information of vertices to be written, along with the flux on every element of scalar 1D equations
+    fn send_vertex_info(&self, info: Vec<f64>, sender: &Option<SyncSender<Output>>) {
+        let sender = match sender {
+            Some(sender) => sender,
+            None => return,
+        };
+        let elements = match flux_data(&self.solver, &self.mesh.filter_for_solving_1d().to_vec(), &info) {
+            Ok(elements) => elements,
+            Err(e) => {
+                log::warn!("Flux on elements is not written!: {}", e);
+                None
+            }
+        };
+        let info = Output { nodes: info, elements };
         match sender.send(info) {
             Err(e) => panic!("Error while communicating between threads. Report this to the deveoper!: {}",e),
             _ => {}
@@ -693,110 +1312,54 @@ impl DzahuiWindow {
         let mut counter = 0;
         let mut fps = 0;
         let mut prev_time = 0;
+        // Time of previous frame in seconds
+        let mut prev_frame_time = 0_f32;
+        // Pause, single-step and budget state of solver
+        let mut step_control = StepControl::with_budget(self.max_steps, self.max_sim_time);
+        // On real time, steps of fixed size follow time elapsed between frames. Otherwise a single step is made every frame
+        let mut accumulator = self.initial_time_step.map(|_| FixedStepAccumulator::new(self.time_step));
+        // Time spent on every stage of a frame. Only used when profiling
+        let mut profiler = FrameProfiler::default();
+        let mut prev_profiling_time = 0;
+        // Time of last status record. Only used when status is logged
+        let mut prev_status_time = 0;
         // To know wether writer can be called again or not
         let mut writer_sleep = 0;
 
-        //set up objects for thread writer
-        let (tx, rx) = mpsc::sync_channel(3);
-        
-        // set writer
-        let writer = match self.solver {
-            Solver::DiffussionSolverTimeDependent(_) => {
-                Writer::new(rx, &self.write_location, &self.file_prefix, ["v_x"], true)
-            },
-            Solver::DiffussionSolverTimeIndependent(_) => {
-                Writer::new(rx, &self.write_location, &self.file_prefix, ["v_x"], true)
-            },
-            Solver::Stokes1DSolver(_) => {
-                Writer::new(rx,&self.write_location, &self.file_prefix, ["p"],true)
-            },
-            Solver::Stokes2DSolver(_) => {
-                Writer::new(rx,&self.write_location, &self.file_prefix,["v_x","v_y","p"],true)
-            }
-            Solver::None => {
-                Writer::new(rx, &self.write_location, &self.file_prefix, [""], false)
-            }
-        };
+        // Channel to writer thread. None when nothing is written
+        let tx = if writes_output(&self.solver, self.output) {
+            //set up objects for thread writer
+            let (tx, rx) = mpsc::sync_channel(3);
 
-        let writer = match writer {
-            Ok(w) => w,
-            Err(e) => panic!("Unable to create writer to record values to files!: {}",e)
+            // set writer
+            let writer = match Writer::new(rx, &self.write_location, &self.file_prefix, self.solver.field_names(), true) {
+                Ok(w) => w.with_precision(self.precision).with_stride(self.output_stride).with_coordinates(self.mesh.node_coordinates()),
+                Err(e) => panic!("Unable to create writer to record values to files!: {}",e)
+            };
+            // copy of timer for new thread
+            let timer_copy = self.timer.clone();
+
+            // sending writer to thread and start execution
+            writer::spawn(writer, timer_copy);
+            log::info!("Writer has been set in: {}",self.write_location);
+            log::info!("Files will have prefix: {}",self.file_prefix);
+            Some(tx)
+        } else {
+            log::info!("No solution is written, so writer is not set");
+            None
         };
-        // copy of timer for new thread
-        let timer_copy = self.timer.clone();
-
-        // sending writer to thread and start execution
-        writer::spawn(writer, timer_copy);
-        log::info!("Writer has been set in: {}",self.write_location);
-        log::info!("Files will have prefix: {}",self.file_prefix);
         
 
         // Obtaining Event Loop is necessary since `event_loop.run()` consumes it alongside window if let inside struct instance.
         let event_loop = Option::take(&mut self.event_loop).unwrap();
 
         // Generating differential equation solver.
-        let mut solver: Box<dyn DiffEquationSolver> = match self.solver {
-
-            Solver::DiffussionSolverTimeIndependent(ref params) => {
-                
-                let diffussion_solver = DiffussionSolverTimeIndependent::new(
-                    &params,
-                    self.mesh.filter_for_solving_1d().to_vec(),
-                    self.integration_iteration);
-
-                    
-                match diffussion_solver {
-                    Ok(d) => {
-                        log::info!("Diffussion solver with time independence created");
-                        Box::new(d)
-                    },
-                    Err(error) => panic!("Error creating instance of DiffussionSolverTimeIndependent!: {}",error)
-                }
-
-            },
-
-            Solver::DiffussionSolverTimeDependent(ref params) => {
-                
-                let diffussion_solver = DiffussionSolverTimeDependent::new(
-                    &params,
-                    self.mesh.filter_for_solving_1d().to_vec(),
-                    self.integration_iteration,
-                );
-
-                match diffussion_solver {
-                    Ok(d) => {
-                        log::info!("Diffussion solver with time dependence created");
-                        Box::new(d)
-                    },
-                    Err(error) => panic!("Error creating instance of DiffussionSolverTimeDependent!: {}",error)
-                }
-            },
-
-            Solver::Stokes1DSolver(ref params) => {
-                
-                let stokes_1d_solver = StaticPressureSolver::new(
-                    &params,
-                    self.mesh.filter_for_solving_1d().to_vec(),
-                    self.integration_iteration
-                );
-
-                match stokes_1d_solver {
-                    Ok(n) => {
-                        log::info!("Stokes solver in 1D with no time dependency created");
-                        Box::new(n)
-                    },
-                    Err(error) => panic!("Error creating instance of StokesSolver1D!: {}",error)
-                }
-            },
-
-            Solver::Stokes2DSolver(ref _params) => {
-                panic!("Not implemented yet!")
-            }
-
-            Solver::None => {
-                log::info!("No solver selected. Program will display Mesh");
-                Box::new(NoSolver())
-            }
+        let mut solver: Box<dyn DiffEquationSolver> = match self.solver.rebuild_solver(
+            self.mesh.filter_for_solving_1d().iter().map(|x| to_scalar(*x)).collect(),
+            self.integration_iteration,
+        ) {
+            Ok(solver) => solver,
+            Err(error) => panic!("Error creating instance of solver!: {}",error)
         };
 
         // Send mesh info: mesh structure and vertices to create body on each one.
@@ -806,23 +1369,49 @@ impl DzahuiWindow {
         if let Err(e) = self.mesh.send_to_gpu() {
             panic!("Error while sending mesh to GPU!: {}",e)
         }
+        if let Some(profile) = &mut self.solution_profile {
+            if let Err(e) = profile.setup() {
+                panic!("Error while setting up solution profile on GPU!: {}",e)
+            }
+            if let Err(e) = profile.send_to_gpu() {
+                panic!("Error while sending solution profile to GPU!: {}",e)
+            }
+        }
+        if let Some((reference, _)) = &mut self.reference_solution {
+            if let Err(e) = reference.setup() {
+                panic!("Error while setting up reference solution on GPU!: {}",e)
+            }
+            if let Err(e) = reference.send_to_gpu() {
+                panic!("Error while sending reference solution to GPU!: {}",e)
+            }
+        }
+        if let Some(color_bar) = &mut self.color_bar {
+            if let Err(e) = color_bar.setup() {
+                panic!("Error while setting up color bar on GPU!: {}",e)
+            }
+            if let Err(e) = color_bar.send_to_gpu() {
+                panic!("Error while sending color bar to GPU!: {}",e)
+            }
+        }
         log::info!("Mesh info has been set up");
 
         // Setup character set info.
-        if let Err(e) = self.character_set.setup() {
-            panic!("Error while setting up character set to write on screen!: {}",e)
-        }
-        if let Err(e) = self.character_set.setup_texture() {
-            panic!("Error while setting up texture for character set!: {}",e)
+        if let Some(character_set) = &mut self.character_set {
+            if let Err(e) = character_set.setup() {
+                panic!("Error while setting up character set to write on screen!: {}",e)
+            }
+            if let Err(e) = character_set.setup_texture() {
+                panic!("Error while setting up texture for character set!: {}",e)
+            }
+            character_set.send_to_gpu();
+            log::info!("Characters for writing have been set up");
         }
-        self.character_set.send_to_gpu();
-        log::info!("Characters for writing have been set up");
 
         // Use geometry shader.
         self.geometry_shader.use_shader();
         // translation for mesh to always be near (0,0).
         if let Err(e) = self.geometry_shader
-            .set_mat4("model", self.mesh.get_model_matrix()) {
+            .set_mat4("model", &self.model_matrix()) {
                 panic!("Unable to set model matrix for geometry!: {}",e)
             }
         if let Err(e) = self.geometry_shader
@@ -835,9 +1424,6 @@ impl DzahuiWindow {
             }
         log::info!("Matrices for Mesh visualization set up");
 
-        // Use text shader to assign matrices.
-        self.text_shader.use_shader();
-
         let model_mat =
             match CharacterSet::matrix_for_screen(0.0, 0.0,
                 &self.camera.projection_matrix, self.height, self.width, self.window_text_scale) {
@@ -847,22 +1433,44 @@ impl DzahuiWindow {
             
             };
 
-        if let Err(e) = self.text_shader.set_mat4("model", &model_mat) {
-            panic!("Unable to set model matrix for text!: {}",e)
-        }
-        if let Err(e) = self.text_shader
-            .set_mat4("projection", &self.camera.projection_matrix) {
-                panic!("Unable to set projection  matrix for text!: {}",e)
+        // Use text shader to assign matrices.
+        if self.character_set.is_some() {
+            self.text_shader.use_shader();
+            if let Err(e) = self.text_shader.set_mat4("model", &model_mat) {
+                panic!("Unable to set model matrix for text!: {}",e)
             }
-        if let Err(e) = self.text_shader.set_mat4("view", &Matrix4::identity()) {
-            panic!("Unable to set view matrix for text shader!: {}",e)
+            if let Err(e) = self.text_shader
+                .set_mat4("projection", &self.camera.projection_matrix) {
+                    panic!("Unable to set projection  matrix for text!: {}",e)
+                }
+            if let Err(e) = self.text_shader.set_mat4("view", &Matrix4::identity()) {
+                panic!("Unable to set view matrix for text shader!: {}",e)
+            }
+            log::info!("Matrices for Character visualization set up");
         }
-        log::info!("Matrices for Character visualization set up");
 
         // Keep last result
-        let mut solution: Vec<f64> = vec![];
+        let mut solution: Vec<f64> = Vec::with_capacity(solver.dof_count());
+        // Result before last one. Only kept when status is logged
+        let mut previous_solution: Vec<f64> = vec![];
+        // Continue from a restored checkpoint. A new simulation starts from zero as usual
+        if let Some(checkpoint) = &self.progress.solver {
+            if let Err(e) = solver.restore(checkpoint) {
+                panic!("Unable to restore solver from checkpoint!: {}",e)
+            }
+        }
+        step_control.resume_from(self.progress.steps, self.progress.simulated_time);
+        if !self.progress.solution.is_empty() {
+            solution = self.progress.solution.clone();
+            self.fields = split_fields(&self.solver.field_names(), &solution);
+            if let Err(e) = self.update_colors() {
+                panic!("Error while sending restored solution colors to GPU!: {}",e)
+            }
+        }
         // to fill or not mesh
         let mut fill = true;
+        // to draw triangle edges over filled mesh
+        let mut overlay = false;
 
         event_loop.run(move |event, _, control_flow| {
 
@@ -909,7 +1517,124 @@ impl DzahuiWindow {
 
                             }
                         }
-                        _ => {},
+                        // W draws triangle edges over filled mesh or stops doing so
+                        13 => {
+                            if let ElementState::Pressed = input.state {
+                                overlay = !overlay;
+                            }
+                        }
+                        // U and J multiply or divide mu by 1.1, I and K add or substract 0.1 to b. Solver is rebuilt with new values
+                        32 | 38 | 34 | 40 => {
+                            if let (ElementState::Pressed, Some((mu, b))) = (input.state, self.solver.mu_and_b()) {
+                                let (mu, b) = match input.scancode {
+                                    32 => (mu * 1.1, b),
+                                    38 => (mu / 1.1, b),
+                                    34 => (mu, b + 0.1),
+                                    _ => (mu, b - 0.1),
+                                };
+                                if let Err(e) = self.solver.set_mu_and_b(mu, b) {
+                                    panic!("Unable to change solver params!: {}",e)
+                                }
+                                // Conditions edited by clicking ends of a 1D mesh are used from now on
+                                if let Err(e) = self.solver.set_boundary_conditions(self.boundary_editor.conditions_1d()) {
+                                    log::warn!("Edited boundary conditions are not used: {}", e);
+                                }
+                                solver = match self.solver.rebuild_solver(
+                                    self.mesh.filter_for_solving_1d().iter().map(|x| to_scalar(*x)).collect(),
+                                    self.integration_iteration,
+                                ) {
+                                    Ok(solver) => solver,
+                                    Err(e) => panic!("Error rebuilding solver with new params!: {}",e)
+                                };
+                                log::info!("Solver rebuilt with mu: {}, b: {}", mu, b);
+                            }
+                        }
+                        // D draws picking ray and cone axis of vertex selector on every click or stops doing so
+                        2 => {
+                            if let ElementState::Pressed = input.state {
+                                self.debug_selector = !self.debug_selector;
+                                self.selector_lines.clear();
+                                log::info!("Vertex selector debug lines: {}", self.debug_selector);
+                            }
+                        }
+                        // = and - widen or narrow the cone of vertex selector by half a degree
+                        24 | 27 => {
+                            if let ElementState::Pressed = input.state {
+                                let change = if input.scancode == 24 { 0.5 } else { -0.5 };
+                                self.vertex_selector.set_angle(self.vertex_selector.angle() + change);
+                                log::info!("Angle for vertex selector is: {}", self.vertex_selector.angle());
+                                if !self.selector_lines.is_empty() {
+                                    if let Err(e) = self.update_selector_lines() {
+                                        panic!("Error while updating vertex selector lines!: {}",e)
+                                    }
+                                }
+                            }
+                        }
+                        // Space pauses and resumes simulation
+                        49 => {
+                            if let ElementState::Pressed = input.state {
+                                step_control.toggle_pause();
+                                log::info!("Simulation paused: {}", step_control.is_paused());
+                            }
+                        }
+                        // F points camera back to the whole mesh
+                        3 => {
+                            if let ElementState::Pressed = input.state {
+                                let (min, max) = self.mesh.world_bounds();
+                                self.camera.frame_bounds(min, max);
+                            }
+                        }
+                        // V maps next field of solution (like velocity components and pressure) to colors
+                        9 => {
+                            if let ElementState::Pressed = input.state {
+                                self.selected_field = next_field(self.selected_field, self.fields.len());
+                                if let Some((name, _)) = self.fields.get(self.selected_field) {
+                                    log::info!("Field shown: {}", name);
+                                }
+                                if let Err(e) = self.update_colors() {
+                                    panic!("Error while sending colors of selected field to GPU!: {}",e)
+                                }
+                            }
+                        }
+                        // C writes a checkpoint to continue simulation later
+                        8 => {
+                            if let ElementState::Pressed = input.state {
+                                // Extension of prefix only chooses format of result files
+                                let prefix = Path::new(&self.file_prefix).with_extension("");
+                                let path = Path::new(&self.write_location).join(format!("{}checkpoint.json", prefix.display()));
+                                match self.save_state(&path) {
+                                    Ok(()) => log::info!("Checkpoint written to {:?}", path),
+                                    Err(e) => log::error!("Unable to write checkpoint!: {}", e),
+                                }
+                            }
+                        }
+                        // B starts or stops editing boundary conditions by clicking boundary vertices
+                        11 => {
+                            if let ElementState::Pressed = input.state {
+                                log::info!("Boundary condition editor active: {}", self.boundary_editor.toggle());
+                            }
+                        }
+                        // O draws boundary and interior vertices apart, to check boundary detection
+                        31 => {
+                            if let ElementState::Pressed = input.state {
+                                match self.toggle_boundary_view() {
+                                    Ok(()) => log::info!("Boundary shown: {}", self.boundary_view.is_some()),
+                                    Err(e) => log::warn!("Unable to show boundary!: {}", e),
+                                }
+                            }
+                        }
+                        // N advances a paused simulation a single step
+                        45 => {
+                            if let ElementState::Pressed = input.state {
+                                step_control.request_step();
+                            }
+                        }
+                        // Unhandled keys are logged so that scancodes of every keyboard can be found out
+                        _ => {
+                            if let ElementState::Pressed = input.state {
+                                log::info!("Key {} (scancode {}) has no action", key_label(input.virtual_keycode), input.scancode);
+                            }
+                        },
                     },
 
                     _ => (),
@@ -925,8 +1650,17 @@ impl DzahuiWindow {
                         0 => self.activate_view_change(state),
                         1 => {
                             if let ElementState::Pressed = state {
-                                if let Err(e) = self.get_selected_vertex() {
-                                    panic!("Error while using cone vertex selector!: {}",e)
+                                let vertex = match self.get_selected_vertex() {
+                                    Ok(vertex) => vertex,
+                                    Err(e) => panic!("Error while using cone vertex selector!: {}",e)
+                                };
+                                // Editor cycles condition of boundary vertices clicked. Interior ones are only reported
+                                if let (true, Some(vertex)) = (self.boundary_editor.active, vertex) {
+                                    match self.boundary_editor.cycle(vertex) {
+                                        Ok(Some(condition)) => log::info!("Vertex {} will use condition {:?} when solver is rebuilt (U, J, I or K)", vertex, condition),
+                                        Ok(None) => log::info!("Vertex {} no longer has a boundary condition", vertex),
+                                        Err(e) => log::warn!("{}", e),
+                                    }
                                 }
                             }
                         }
@@ -936,7 +1670,7 @@ impl DzahuiWindow {
                     DeviceEvent::MouseMotion { delta: (x, y) } => {
                         match self.camera.active_view_change {
                             true => {
-                                self.change_camera_view(x as f32, y as f32);
+                                self.camera.add_motion(x as f32, y as f32);
                             }
                             false => (),
                         }
@@ -947,18 +1681,40 @@ impl DzahuiWindow {
 
                 Event::MainEventsCleared => {
 
+                    // Delta time for camera motion
+                    let frame_time = self.timer.elapsed().as_secs_f32();
+                    if self.camera.pending_motion != (0.0, 0.0) {
+                        self.change_camera_view(frame_time - prev_frame_time);
+                    }
+                    let frame_steps = match accumulator.as_mut() {
+                        Some(accumulator) if !step_control.is_paused() => accumulator.advance((frame_time - prev_frame_time) as f64),
+                        _ => 1,
+                    };
+                    prev_frame_time = frame_time;
+
                     let current_time = self.timer.elapsed().as_millis();
                     if current_time - prev_time >= 100 {
                         prev_time = current_time;
                         fps = counter * 10;
-                        match self.initial_time_step {
-                            Some(_) => {
-                                self.time_step = 1_f64 / (fps as f64);
-                            },
-                            None => ()
-                        }
                         counter = 0;
                     }
+
+                    if self.profiling && current_time - prev_profiling_time >= 1000 {
+                        prev_profiling_time = current_time;
+                        profiler.log_and_reset();
+                    }
+
+                    if let Some(period) = self.status_log {
+                        if current_time - prev_status_time >= period as u128 {
+                            prev_status_time = current_time;
+                            let record = StatusRecord::new(&step_control, &previous_solution, &solution, fps as u32,
+                                [self.mouse_coordinates.x, self.mouse_coordinates.y]);
+                            match record.to_json() {
+                                Ok(json) => log::info!("Status: {}", json),
+                                Err(error) => log::warn!("Status could not be written: {}", error),
+                            }
+                        }
+                    }
                     
                     unsafe {
                         // Update to some color
@@ -971,21 +1727,47 @@ impl DzahuiWindow {
                     match self.solver {
                         
                         Solver::None => {},
+                        _ if frame_steps == 0 || !step_control.should_solve() => {},
                         _ => {
 
-                            solution = match solver.solve(self.time_step) {
-                                Ok(solution) => solution,
-                                Err(e) => panic!("Error while solving equation!: {}",e)
+                            for step in 0..frame_steps {
+                                // First step was already allowed by guard
+                                if step > 0 && !step_control.should_solve() {
+                                    break;
+                                }
+
+                                let stage_start = Instant::now();
+                                if self.status_log.is_some() {
+                                    previous_solution = std::mem::take(&mut solution);
+                                }
+                                solution = match solver.solve(to_scalar(self.time_step)) {
+                                    Ok(solution) => solution.into_iter().map(from_scalar).collect(),
+                                    Err(e) => panic!("Error while solving equation!: {}",e)
+                                };
+                                if self.profiling {
+                                    profiler.solver.add(stage_start.elapsed());
+                                }
+                                if step_control.record_step(self.time_step) {
+                                    log::info!("Solver budget used up. Solving stops, window stays open");
+                                    if self.auto_save {
+                                        self.send_vertex_info(solution.clone(), &tx)
+                                    }
+                                }
+                            }
+                            self.progress = Progress {
+                                solution: solution.clone(),
+                                steps: step_control.steps(),
+                                simulated_time: step_control.simulated_time(),
+                                solver: solver.checkpoint(),
                             };
                 
-                            // updating colors. One time per vertex should be updated (that is, every 6 steps).
-                            self.mesh.update_gradient_1d(solution.iter().map(|x| x.abs()).collect());
-                            
-                            if let Err(e) = self.mesh.bind_all_no_texture() {
-                                panic!("Error while binding mesh again!: {}",e)
+                            let stage_start = Instant::now();
+                            self.fields = split_fields(&self.solver.field_names(), &solution);
+                            if let Err(e) = self.update_colors() {
+                                panic!("Error while sending updated solution colors to GPU!: {}",e)
                             }
-                            if let Err(e) = self.mesh.send_to_gpu() {
-                                panic!("Error while sending updated mesh to GPU!: {}",e)
+                            if self.profiling {
+                                profiler.gpu_update.add(stage_start.elapsed());
                             }
                         
                         }
@@ -994,21 +1776,55 @@ impl DzahuiWindow {
         
         
                     
+                    let stage_start = Instant::now();
                     // Text shader to draw text
-                    self.text_shader.use_shader();
+                    if let Some(character_set) = &self.character_set {
+                        self.text_shader.use_shader();
         
-                    if let Err(e) = self.character_set.bind_all() {
-                        panic!("Error while binding character set again! {}",e)
-                    }
-                    if let Err(e) = self.character_set.draw_text(format!(
-                        "x: {}, y: {}, FPS: {}",
-                        self.mouse_coordinates.x, self.mouse_coordinates.y, fps
-                    )) {
-                        panic!("Error while writing coordinates and fps counter: {}",e);
-                    }
+                        if let Err(e) = character_set.bind_all() {
+                            panic!("Error while binding character set again! {}",e)
+                        }
+                        let field = match self.fields.get(self.selected_field) {
+                            Some((name, _)) => format!(", field: {}", name),
+                            None => String::new(),
+                        };
+                        if let Err(e) = character_set.draw_text(format!(
+                            "x: {}, y: {}, FPS: {}, angle: {}{}",
+                            writer::format_value(self.mouse_coordinates.x as f64, self.precision),
+                            writer::format_value(self.mouse_coordinates.y as f64, self.precision),
+                            fps,
+                            self.vertex_selector.angle(),
+                            field
+                        )) {
+                            panic!("Error while writing coordinates and fps counter: {}",e);
+                        }
+
+                        // Labels of color bar are written next to its bottom and top
+                        if let Some(color_bar) = &self.color_bar {
+                            let (min_label, max_label) = color_bar.labels(self.precision);
+                            let positions = color_bar.label_positions(self.height, self.width);
+
+                            for ((x, y), label) in positions.into_iter().zip([min_label, max_label]) {
+                                let label_mat = match CharacterSet::matrix_for_screen(x + 5.0, y,
+                                    &self.camera.projection_matrix, self.height, self.width, self.window_text_scale) {
+                                        Ok(mat) => mat,
+                                        Err(e) => panic!("Matrix for color bar label not created properly!: {}",e)
+                                    };
+                                if let Err(e) = self.text_shader.set_mat4("model", &label_mat) {
+                                    panic!("Unable to set model matrix for color bar label!: {}",e)
+                                }
+                                if let Err(e) = character_set.draw_text(label) {
+                                    panic!("Error while writing color bar label: {}",e);
+                                }
+                            }
+                            if let Err(e) = self.text_shader.set_mat4("model", &model_mat) {
+                                panic!("Unable to set model matrix for text!: {}",e)
+                            }
+                        }
 
-                    if let Err(e) = self.character_set.unbind_texture() {
-                        panic!("Error while unbinding texture for character set!: {}",e)
+                        if let Err(e) = character_set.unbind_texture() {
+                            panic!("Error while unbinding texture for character set!: {}",e)
+                        }
                     }
         
                     // Geometry shader to draw mesh
@@ -1017,20 +1833,83 @@ impl DzahuiWindow {
                         .set_mat4("view", &self.camera.view_matrix) {
                             panic!("Unable to set new view matrix for geometry!: {}",e)
                         }
+                    if let Err(e) = self.geometry_shader
+                        .set_mat4("projection", &self.camera.projection_matrix) {
+                            panic!("Unable to set new projection matrix for geometry!: {}",e)
+                        }
         
                     if let Err(e) = self.mesh.bind_vao() {
                         panic!("Unable to bind vao of mesh!: {}",e)
                     }
 
-                    // Draw filled or not filled
-                    match fill {
-                        false => {}
-                        true => unsafe {
+                    // Draw filled, not filled or both. Line plots replace the bar
+                    let passes = if self.line_plot { vec![] } else { RenderMode::new(fill, overlay).passes() };
+                    for pass in passes {
+                        if let Err(e) = pass.apply(&self.geometry_shader) {
+                            panic!("Unable to set up render pass for mesh!: {e}")
+                        }
+                        if let Err(e) = self.mesh.draw() {
+                            panic!("Unable to draw mesh!: {e}")
+                        }
+                    }
+                    if let Err(e) = RenderPass::reset(&self.geometry_shader) {
+                        panic!("Unable to reset render state after drawing mesh!: {e}")
+                    }
+                    if let Some(profile) = &self.solution_profile {
+                        if let Err(e) = profile.bind_vao() {
+                            panic!("Unable to bind vao of solution profile!: {e}")
+                        }
+                        if let Err(e) = profile.draw() {
+                            panic!("Unable to draw solution profile!: {e}")
+                        }
+                    }
+                    if let Some((reference, _)) = &self.reference_solution {
+                        if let Err(e) = reference.bind_vao() {
+                            panic!("Unable to bind vao of reference solution!: {e}")
+                        }
+                        if let Err(e) = reference.draw() {
+                            panic!("Unable to draw reference solution!: {e}")
+                        }
+                    }
+                    // Selector lines are already in world coordinates
+                    if !self.selector_lines.is_empty() {
+                        if let Err(e) = self.geometry_shader.set_mat4("model", &Matrix4::identity()) {
+                            panic!("Unable to set model matrix for vertex selector lines!: {}",e)
+                        }
+                        for line in &self.selector_lines {
+                            if let Err(e) = line.bind_vao() {
+                                panic!("Unable to bind vao of vertex selector line!: {e}")
+                            }
+                            if let Err(e) = line.draw() {
+                                panic!("Unable to draw vertex selector line!: {e}")
+                            }
+                        }
+                        if let Err(e) = self.geometry_shader.set_mat4("model", &self.model_matrix()) {
+                            panic!("Unable to set model matrix for geometry!: {}",e)
+                        }
+                    }
+                    // Color bar is already in screen coordinates
+                    if let Some(color_bar) = &self.color_bar {
+                        for matrix in ["model", "view", "projection"] {
+                            if let Err(e) = self.geometry_shader.set_mat4(matrix, &Matrix4::identity()) {
+                                panic!("Unable to set {} matrix for color bar!: {}",matrix,e)
+                            }
+                        }
+                        if let Err(e) = color_bar.bind_vao() {
+                            panic!("Unable to bind vao of color bar!: {e}")
+                        }
+                        unsafe {
                             gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
                         }
+                        if let Err(e) = color_bar.draw() {
+                            panic!("Unable to draw color bar!: {e}")
+                        }
+                        if let Err(e) = self.geometry_shader.set_mat4("model", &self.model_matrix()) {
+                            panic!("Unable to set model matrix for geometry!: {}",e)
+                        }
                     }
-                    if let Err(e) = self.mesh.draw() {
-                        panic!("Unable to draw mesh!: {e}")
+                    if self.profiling {
+                        profiler.draw.add(stage_start.elapsed());
                     }
                     // Need to change old and new buffer to redraw
                     if let Err(e) = self.context.swap_buffers() {
@@ -1044,3 +1923,261 @@ impl DzahuiWindow {
         })
     }
 }
+
+/// Applies `transform` after `model_matrix` (which centers a mesh).
+fn compose_model_matrix(transform: &Matrix4<f32>, model_matrix: &Matrix4<f32>) -> Matrix4<f32> {
+    transform * model_matrix
+}
+
+/// # General Information
+///
+/// Splits a solution into named fields. Values of every node are stored one after another in the order of `names`, as done when writing to files.
+///
+/// # Parameters
+///
+/// * `names` - Name of every field, in order
+/// * `solution` - Values of every field at every node
+///
+fn split_fields(names: &[&str], solution: &[f64]) -> Vec<(String, Vec<f64>)> {
+    names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.to_string(), solution.iter().skip(i).step_by(names.len()).copied().collect()))
+        .collect()
+}
+
+/// Index of field after `current`, going back to the first one after the last. Zero when there are no fields.
+fn next_field(current: usize, amount: usize) -> usize {
+    if amount == 0 {
+        0
+    } else {
+        (current + 1) % amount
+    }
+}
+
+/// # General Information
+///
+/// Flux (derivative of solution) on every element of a 1D mesh, for equations whose solution is a single scalar field (diffusion and Poisson).
+/// None for every other equation.
+///
+/// # Parameters
+///
+/// * `solver` - Equation being solved
+/// * `nodes` - Nodes of 1D mesh
+/// * `solution` - Value of solution at every node
+///
+fn flux_data(solver: &Solver, nodes: &[f64], solution: &[f64]) -> Result<Option<ElementData>, Error> {
+    match solver {
+        Solver::DiffussionSolverTimeIndependent(_) | Solver::DiffussionSolverTimeDependent(_) | Solver::Poisson1DSolver(_) => {
+            let flux = element_gradients(
+                &nodes.iter().map(|node| to_scalar(*node)).collect(),
+                &solution.iter().map(|value| to_scalar(*value)).collect::<Vec<_>>(),
+            )?;
+            let elements = (0..flux.len()).map(|element| vec![element, element + 1]).collect();
+            Ok(Some(ElementData::new(elements, vec!["flux"], flux.into_iter().map(from_scalar).collect())?))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Level handed to logger: the one requested on builder or, if none was, the one currently set.
+fn selected_log_level(requested: Option<log::LevelFilter>, current: log::LevelFilter) -> log::LevelFilter {
+    requested.unwrap_or(current)
+}
+
+/// Wether a writer thread is needed: only when an equation is solved and output has not been disabled.
+fn writes_output(solver: &Solver, output: bool) -> bool {
+    output && !matches!(solver, Solver::None)
+}
+
+/// Readable name of a key. Digits, punctuation and a few special keys get the symbol printed on them, other keys their `VirtualKeyCode` name.
+fn key_label(key: Option<VirtualKeyCode>) -> String {
+    let key = match key {
+        Some(key) => key,
+        None => return String::from("unknown"),
+    };
+    let label = match key {
+        VirtualKeyCode::Key0 => "0",
+        VirtualKeyCode::Key1 => "1",
+        VirtualKeyCode::Key2 => "2",
+        VirtualKeyCode::Key3 => "3",
+        VirtualKeyCode::Key4 => "4",
+        VirtualKeyCode::Key5 => "5",
+        VirtualKeyCode::Key6 => "6",
+        VirtualKeyCode::Key7 => "7",
+        VirtualKeyCode::Key8 => "8",
+        VirtualKeyCode::Key9 => "9",
+        VirtualKeyCode::Equals => "=",
+        VirtualKeyCode::Minus => "-",
+        VirtualKeyCode::Comma => ",",
+        VirtualKeyCode::Period => ".",
+        VirtualKeyCode::Slash => "/",
+        VirtualKeyCode::Semicolon => ";",
+        VirtualKeyCode::Apostrophe => "'",
+        VirtualKeyCode::LBracket => "[",
+        VirtualKeyCode::RBracket => "]",
+        VirtualKeyCode::Space => "Space",
+        VirtualKeyCode::Escape => "Esc",
+        VirtualKeyCode::Return => "Enter",
+        _ => return format!("{:?}", key),
+    };
+
+    label.to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{compose_model_matrix, context_attempts, create_with_fallback, key_label, next_field, split_fields, writes_output, flux_data, selected_log_level, CullMode, DzahuiWindow};
+    use crate::Error;
+    use glutin::GlProfile;
+    use crate::mesh::Mesh;
+    use cgmath::{Deg, InnerSpace, Matrix4, Point3, SquareMatrix, Transform};
+
+    #[test]
+    fn log_level_is_handed_to_logger() {
+        let builder = DzahuiWindow::builder("./assets/1dbar.obj").with_log_level(log::LevelFilter::Debug);
+
+        assert!(builder.log_level == Some(log::LevelFilter::Debug));
+        assert!(selected_log_level(builder.log_level, log::LevelFilter::Info) == log::LevelFilter::Debug);
+        assert!(selected_log_level(None, log::LevelFilter::Warn) == log::LevelFilter::Warn);
+    }
+
+    #[test]
+    fn title_is_handed_to_window_builder() {
+        let builder = DzahuiWindow::builder("./assets/1dbar.obj");
+        assert!(builder.window_builder(600, 800).window.title == "Dzahui");
+
+        let builder = builder.with_title("Diffusion run 2");
+        assert!(builder.title == Some("Diffusion run 2".to_string()));
+        assert!(builder.window_builder(600, 800).window.title == "Diffusion run 2");
+    }
+
+    #[test]
+    fn transform_is_applied_after_centering() {
+        let centering = Matrix4::from_translation([-1.0, 0.0, 0.0].into());
+        let rotation = Matrix4::from_angle_y(Deg(90.0));
+        let model_matrix = compose_model_matrix(&rotation, &centering);
+
+        // Point is first centered at origin, so rotation leaves it there
+        let point = model_matrix.transform_point(Point3::new(1.0, 0.0, 0.0));
+        assert!(point.x.abs() < 1e-6 && point.y.abs() < 1e-6 && point.z.abs() < 1e-6);
+        assert!(compose_model_matrix(&Matrix4::identity(), &centering) == centering);
+    }
+
+    #[test]
+    fn mesh_model_matrix_is_composed_with_transform() {
+        let mesh = Mesh::builder("./assets/test.obj").build_mesh_3d().unwrap();
+        let rotation = Matrix4::from_angle_x(Deg(30.0));
+        let model_matrix = compose_model_matrix(&rotation, mesh.get_model_matrix());
+        assert!(model_matrix != *mesh.get_model_matrix());
+
+        // Every vertex is centered first and rotated afterwards
+        for vertex in mesh.vertices.as_slice().unwrap().chunks_exact(6) {
+            let point = Point3::new(vertex[0] as f32, vertex[1] as f32, vertex[2] as f32);
+            let expected = rotation.transform_point(mesh.get_model_matrix().transform_point(point));
+            let composed = model_matrix.transform_point(point);
+            assert!((composed - expected).magnitude() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn context_creation_falls_back_in_order() {
+        let attempts = context_attempts((4, 1));
+        assert!(attempts[..4] == [
+            ((4, 1), GlProfile::Core),
+            ((4, 1), GlProfile::Compatibility),
+            ((4, 0), GlProfile::Core),
+            ((4, 0), GlProfile::Compatibility),
+        ]);
+        assert!(attempts.last() == Some(&((3, 3), GlProfile::Compatibility)));
+        assert!(context_attempts((3, 3)).len() == 2);
+
+        // Only compatibility profile of 4.0 is supported
+        let mut tried = vec![];
+        let context = create_with_fallback(&attempts, |version, profile| {
+            tried.push((version, profile));
+            if (version, profile) == ((4, 0), GlProfile::Compatibility) { Ok("context") } else { Err("unsupported".to_string()) }
+        });
+        assert!(context.unwrap() == "context");
+        assert!(tried == attempts[..4]);
+
+        // Nothing is supported
+        let context: Result<(), Error> = create_with_fallback(&attempts, |_, _| Err("unsupported".to_string()));
+        match context {
+            Err(Error::ContextCreation(tried)) => assert!(tried.starts_with("4.1 Core (unsupported), 4.1 Compatibility (unsupported)")),
+            _ => panic!("Context creation should have failed"),
+        }
+    }
+
+    #[test]
+    fn face_culling_is_handed_to_setup() {
+        let builder = DzahuiWindow::builder("./assets/untitled.obj");
+        assert!(builder.face_culling.is_none());
+
+        let builder = builder.with_face_culling(CullMode::Back);
+        assert!(builder.face_culling == Some(CullMode::Back));
+        assert!(builder.face_culling.and_then(|cull_mode| cull_mode.cull_face()) == Some(gl::BACK));
+    }
+
+    #[test]
+    fn disabled_text_loads_no_character_set() {
+        let builder = DzahuiWindow::builder("./assets/1dbar.obj").with_character_set("./assets/dzahui-font_test.fnt");
+        assert!(builder.load_character_set().unwrap().is_some());
+
+        // Every text draw call goes through the character set, so none is made without it
+        let builder = builder.without_text();
+        assert!(!builder.text);
+        assert!(builder.load_character_set().unwrap().is_none());
+    }
+
+    #[test]
+    fn field_selection_cycles_and_wraps() {
+        let fields = split_fields(&["v_x", "v_y", "p"], &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(fields.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>() == ["v_x", "v_y", "p"]);
+        assert!(fields[2].1 == vec![3.0, 6.0]);
+
+        let visited: Vec<usize> = (0..4).scan(0, |current, _| { *current = next_field(*current, fields.len()); Some(*current) }).collect();
+        assert!(visited == vec![1, 2, 0, 1]);
+        assert!(next_field(0, 1) == 0);
+        assert!(next_field(3, 0) == 0);
+    }
+
+    #[test]
+    fn keys_are_labeled() {
+        use glutin::event::VirtualKeyCode;
+
+        assert!(key_label(Some(VirtualKeyCode::Q)) == "Q");
+        assert!(key_label(Some(VirtualKeyCode::Key7)) == "7");
+        assert!(key_label(Some(VirtualKeyCode::Equals)) == "=");
+        assert!(key_label(Some(VirtualKeyCode::Space)) == "Space");
+        assert!(key_label(Some(VirtualKeyCode::F5)) == "F5");
+        assert!(key_label(None) == "unknown");
+    }
+
+    #[test]
+    fn flux_is_written_for_scalar_equations() {
+        let diffusion = DzahuiWindow::builder("./assets/1dbar.obj").solve_1d_diffussion(
+            crate::DiffussionParams::time_independent().mu(1.0).b(1.0).boundary_conditions(0.0, 1.0).build()
+        );
+        let flux = flux_data(&diffusion.solver, &[0.0, 0.5, 2.0], &[0.0, 1.0, 4.0]).unwrap().unwrap();
+        assert!(flux.elements == vec![vec![0, 1], vec![1, 2]]);
+        assert!(flux.values == vec![2.0, 2.0]);
+
+        let visualization = DzahuiWindow::builder("./assets/1dbar.obj");
+        assert!(flux_data(&visualization.solver, &[0.0, 1.0], &[0.0, 1.0]).unwrap().is_none());
+    }
+
+    #[test]
+    fn writer_only_spawned_when_solving_with_output() {
+        let visualization = DzahuiWindow::builder("./assets/1dbar.obj");
+        assert!(!writes_output(&visualization.solver, visualization.output));
+
+        let diffusion = DzahuiWindow::builder("./assets/1dbar.obj").solve_1d_diffussion(
+            crate::DiffussionParams::time_independent().mu(1.0).b(1.0).boundary_conditions(0.0, 1.0).build()
+        );
+        assert!(writes_output(&diffusion.solver, diffusion.output));
+
+        let diffusion = diffusion.without_output();
+        assert!(!writes_output(&diffusion.solver, diffusion.output));
+    }
+}
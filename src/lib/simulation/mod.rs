@@ -1,4 +1,10 @@
-mod camera;
+pub(crate) mod camera;
+mod boundary_editor;
+mod checkpoint;
 pub(crate) mod drawable;
 pub mod dzahui_window;
+mod profiler;
+pub(crate) mod render_mode;
+mod step_control;
+mod status;
 mod shader;
@@ -0,0 +1,94 @@
+// External dependencies
+use std::time::Duration;
+
+/// # General Information
+///
+/// Accumulates time samples to obtain an average. Used to know how much time a certain stage of a frame takes.
+///
+/// # Fields
+///
+/// * `total` - Sum of every sample since last reset
+/// * `samples` - Number of samples since last reset
+///
+#[derive(Debug, Default)]
+pub(crate) struct TimeAccumulator {
+    total: Duration,
+    samples: u32,
+}
+
+/// # General Information
+///
+/// Time spent per frame on every important stage of `DzahuiWindow::run`: solving the equation, updating the mesh on GPU and drawing.
+///
+/// # Fields
+///
+/// * `solver` - Time spent in `solve`
+/// * `gpu_update` - Time spent updating gradient and sending mesh to GPU
+/// * `draw` - Time spent drawing text and mesh
+///
+#[derive(Debug, Default)]
+pub(crate) struct FrameProfiler {
+    pub(crate) solver: TimeAccumulator,
+    pub(crate) gpu_update: TimeAccumulator,
+    pub(crate) draw: TimeAccumulator,
+}
+
+impl TimeAccumulator {
+    /// Adds a new sample.
+    pub(crate) fn add(&mut self, sample: Duration) {
+        self.total += sample;
+        self.samples += 1;
+    }
+
+    /// Average of samples since last reset. Zero when there are no samples.
+    pub(crate) fn average(&self) -> Duration {
+        if self.samples == 0 {
+            Duration::ZERO
+        } else {
+            self.total / self.samples
+        }
+    }
+
+    /// Erases every sample.
+    pub(crate) fn reset(&mut self) {
+        self.total = Duration::ZERO;
+        self.samples = 0;
+    }
+}
+
+impl FrameProfiler {
+    /// Logs average of every stage and starts accumulating again.
+    pub(crate) fn log_and_reset(&mut self) {
+        log::info!(
+            "Average per frame. Solver: {:?}, GPU update: {:?}, Draw: {:?}",
+            self.solver.average(),
+            self.gpu_update.average(),
+            self.draw.average()
+        );
+        self.solver.reset();
+        self.gpu_update.reset();
+        self.draw.reset();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::TimeAccumulator;
+
+    #[test]
+    fn averages_samples() {
+        let mut accumulator = TimeAccumulator::default();
+        assert!(accumulator.average() == Duration::ZERO);
+
+        for millis in [10, 20, 30, 40] {
+            accumulator.add(Duration::from_millis(millis));
+        }
+        assert!(accumulator.average() == Duration::from_millis(25));
+
+        accumulator.reset();
+        accumulator.add(Duration::from_millis(7));
+        assert!(accumulator.average() == Duration::from_millis(7));
+    }
+}
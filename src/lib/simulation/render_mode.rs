@@ -0,0 +1,178 @@
+// Internal dependencies
+use super::shader::Shader;
+use crate::Error;
+
+// External dependencies
+use cgmath::Vector4;
+use gl::types::GLenum;
+
+/// Color of triangle edges when drawn over filled triangles
+const OVERLAY_LINE_COLOR: [f32; 3] = [0.0, 0.0, 0.0];
+
+/// # General Information
+///
+/// How a mesh is drawn: filled triangles, only their edges or both at once.
+///
+/// # Arms
+///
+/// * `Fill` - Filled triangles with solution colors
+/// * `Wireframe` - Triangle edges with solution colors
+/// * `FillWithWireframe` - Filled triangles with solution colors and their edges drawn on top in a single contrasting color
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RenderMode {
+    Fill,
+    Wireframe,
+    FillWithWireframe,
+}
+
+/// # General Information
+///
+/// A single draw call of a mesh with its polygon mode.
+///
+/// # Arms
+///
+/// * `Fill` - Filled triangles. When `offset` is true they are pushed slightly back in depth so that lines drawn afterwards do not z-fight with them
+/// * `Line` - Triangle edges. Drawn with a single color when given, otherwise with vertex colors
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum RenderPass {
+    Fill { offset: bool },
+    Line { color: Option<[f32; 3]> },
+}
+
+/// # General Information
+///
+/// Which triangles are discarded depending on the side of them facing the camera. Front faces are the ones whose vertices appear counter-clockwise.
+///
+/// # Arms
+///
+/// * `None` - Every triangle is drawn. Thin 2D meshes are seen from both sides
+/// * `Back` - Triangles facing away from camera are not drawn
+/// * `Front` - Triangles facing camera are not drawn
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CullMode {
+    None,
+    Back,
+    Front,
+}
+
+impl CullMode {
+    /// Face given to `glCullFace`. None when culling should be disabled.
+    pub(crate) fn cull_face(&self) -> Option<GLenum> {
+        match self {
+            CullMode::None => None,
+            CullMode::Back => Some(gl::BACK),
+            CullMode::Front => Some(gl::FRONT),
+        }
+    }
+
+    /// Enables and configures culling, or disables it. Should be called once an OpenGL context is current.
+    pub(crate) fn apply(&self) {
+        unsafe {
+            match self.cull_face() {
+                Some(face) => {
+                    gl::Enable(gl::CULL_FACE);
+                    gl::CullFace(face);
+                }
+                None => gl::Disable(gl::CULL_FACE),
+            }
+        }
+    }
+}
+
+impl RenderMode {
+    /// Mode from window state: triangles are filled unless only the wireframe is wanted, and edges are overlaid when requested.
+    pub(crate) fn new(fill: bool, overlay: bool) -> Self {
+        match (fill, overlay) {
+            (false, _) => RenderMode::Wireframe,
+            (true, true) => RenderMode::FillWithWireframe,
+            (true, false) => RenderMode::Fill,
+        }
+    }
+
+    /// Passes to make, in order, to draw a mesh in this mode.
+    pub(crate) fn passes(&self) -> Vec<RenderPass> {
+        match self {
+            RenderMode::Fill => vec![RenderPass::Fill { offset: false }],
+            RenderMode::Wireframe => vec![RenderPass::Line { color: None }],
+            RenderMode::FillWithWireframe => vec![
+                RenderPass::Fill { offset: true },
+                RenderPass::Line {
+                    color: Some(OVERLAY_LINE_COLOR),
+                },
+            ],
+        }
+    }
+}
+
+impl RenderPass {
+    /// # General Information
+    ///
+    /// Sets polygon mode, polygon offset and line color of a pass. Should be called right before drawing.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Pass to set up
+    /// * `shader` - Geometry shader in use. Its `lineColor` uniform replaces vertex colors when alpha is one
+    ///
+    pub(crate) fn apply(&self, shader: &Shader) -> Result<(), Error> {
+        match self {
+            RenderPass::Fill { offset } => {
+                unsafe {
+                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::FILL);
+                    if *offset {
+                        gl::Enable(gl::POLYGON_OFFSET_FILL);
+                        gl::PolygonOffset(1.0, 1.0);
+                    } else {
+                        gl::Disable(gl::POLYGON_OFFSET_FILL);
+                    }
+                }
+                shader.set_vec4("lineColor", &Vector4::new(0.0, 0.0, 0.0, 0.0))
+            }
+            RenderPass::Line { color } => {
+                unsafe {
+                    gl::PolygonMode(gl::FRONT_AND_BACK, gl::LINE);
+                    gl::Disable(gl::POLYGON_OFFSET_FILL);
+                }
+                match color {
+                    Some([r, g, b]) => shader.set_vec4("lineColor", &Vector4::new(*r, *g, *b, 1.0)),
+                    None => shader.set_vec4("lineColor", &Vector4::new(0.0, 0.0, 0.0, 0.0)),
+                }
+            }
+        }
+    }
+
+    /// Leaves vertex colors and depth as they were before any pass, so that other objects are drawn as usual.
+    pub(crate) fn reset(shader: &Shader) -> Result<(), Error> {
+        unsafe {
+            gl::Disable(gl::POLYGON_OFFSET_FILL);
+        }
+        shader.set_vec4("lineColor", &Vector4::new(0.0, 0.0, 0.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CullMode, RenderMode, RenderPass};
+
+    #[test]
+    fn combined_mode_fills_then_draws_lines() {
+        let passes = RenderMode::new(true, true).passes();
+        assert!(passes.len() == 2);
+        assert!(passes[0] == RenderPass::Fill { offset: true });
+        assert!(matches!(passes[1], RenderPass::Line { color: Some(_) }));
+
+        assert!(RenderMode::new(true, false).passes() == vec![RenderPass::Fill { offset: false }]);
+        // Holding wireframe wins over overlay
+        assert!(RenderMode::new(false, true).passes() == vec![RenderPass::Line { color: None }]);
+    }
+
+    #[test]
+    fn cull_mode_maps_to_gl_faces() {
+        assert!(CullMode::None.cull_face().is_none());
+        assert!(CullMode::Back.cull_face() == Some(gl::BACK));
+        assert!(CullMode::Front.cull_face() == Some(gl::FRONT));
+    }
+}
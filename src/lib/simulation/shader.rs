@@ -3,7 +3,7 @@ use crate::Error;
 
 // External dependencies
 use std::{ffi::CString, fs::File, ptr};
-use cgmath::{Matrix, Matrix4};
+use cgmath::{Matrix, Matrix4, Vector4};
 use gl::types::GLint;
 use std::io::Read;
 use gl;
@@ -136,4 +136,19 @@ impl Shader {
         }
         Ok(())
     }
+
+    /// Send a 4-component vector variable to shader. Ignored by OpenGL when shader does not declare it as a uniform.
+    pub fn set_vec4(&self, opengl_variable_name: &str, vec4_value: &Vector4<f32>) -> Result<(),Error> {
+        let c_str_name = CString::new(opengl_variable_name.as_bytes())?;
+        unsafe {
+            gl::Uniform4f(
+                gl::GetUniformLocation(self.id, c_str_name.as_ptr()),
+                vec4_value.x,
+                vec4_value.y,
+                vec4_value.z,
+                vec4_value.w,
+            );
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,93 @@
+// Internal dependencies
+use super::step_control::StepControl;
+use crate::solvers::{from_scalar, norms::l2_norm, to_scalar};
+use crate::Error;
+
+// External dependencies
+use serde::Serialize;
+
+/// # General Information
+///
+/// Values shown on screen (and a few more) at a given frame, so that automated runs can follow a simulation through logs.
+///
+/// # Fields
+///
+/// * `step` - Solver steps made so far
+/// * `time` - Simulated time so far
+/// * `residual` - Euclidean norm of change between last two solutions. None until two solutions with the same length have been obtained
+/// * `fps` - Frames per second
+/// * `mouse` - Mouse coordinates on window
+///
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub(crate) struct StatusRecord {
+    pub(crate) step: usize,
+    pub(crate) time: f64,
+    pub(crate) residual: Option<f64>,
+    pub(crate) fps: u32,
+    pub(crate) mouse: [f32; 2],
+}
+
+impl StatusRecord {
+    /// # General Information
+    ///
+    /// Gathers a record from state kept by `DzahuiWindow::run`.
+    ///
+    /// # Parameters
+    ///
+    /// * `step_control` - Step control holding steps and simulated time
+    /// * `previous` - Solution before last one. Empty when there is none
+    /// * `current` - Last solution. Empty when there is none
+    /// * `fps` - Frames per second
+    /// * `mouse` - Mouse coordinates on window
+    ///
+    pub(crate) fn new(step_control: &StepControl, previous: &[f64], current: &[f64], fps: u32, mouse: [f32; 2]) -> Self {
+        let residual = if !current.is_empty() && previous.len() == current.len() {
+            let difference: Vec<_> = current.iter().zip(previous.iter()).map(|(c, p)| to_scalar(c - p)).collect();
+            Some(from_scalar(l2_norm(&difference)))
+        } else {
+            None
+        };
+
+        Self {
+            step: step_control.steps(),
+            time: step_control.simulated_time(),
+            residual,
+            fps,
+            mouse,
+        }
+    }
+
+    /// Record as a single-line JSON object. Missing or non-finite values are written as null.
+    pub(crate) fn to_json(self) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StatusRecord;
+    use crate::simulation::step_control::StepControl;
+
+    #[test]
+    fn record_captures_frame_values() {
+        let mut step_control = StepControl::with_budget(None, None);
+        step_control.record_step(0.25);
+        step_control.record_step(0.25);
+
+        let record = StatusRecord::new(&step_control, &[0.0, 1.0], &[3.0, 5.0], 60, [1.5, -2.0]);
+        assert!(record.step == 2);
+        assert!(record.time == 0.5);
+        assert!(record.residual == Some(5.0));
+        assert!(record.fps == 60);
+        assert!(record.to_json().unwrap() == "{\"step\":2,\"time\":0.5,\"residual\":5.0,\"fps\":60,\"mouse\":[1.5,-2.0]}");
+
+        // First solution has nothing to be compared with
+        let record = StatusRecord::new(&step_control, &[], &[3.0, 5.0], 60, [0.0, 0.0]);
+        assert!(record.residual.is_none());
+        assert!(record.to_json().unwrap().contains("\"residual\":null"));
+
+        // Non-finite values cannot be written as JSON numbers
+        let record = StatusRecord { time: f64::INFINITY, residual: Some(f64::NAN), ..record };
+        assert!(record.to_json().unwrap().contains("\"time\":null,\"residual\":null"));
+    }
+}
@@ -0,0 +1,206 @@
+/// # General Information
+///
+/// Decides wether the solver should advance on a given frame. When paused, the solver only advances once per single-step request,
+/// which allows to inspect transient behavior one step at a time. Once a budget of steps or simulated time is used up, the solver stops advancing for good.
+///
+/// # Fields
+///
+/// * `paused` - Wether the simulation is paused
+/// * `pending_steps` - Steps requested while paused that have not been made yet
+/// * `max_steps` - Amount of steps after which the solver stops. No limit when None
+/// * `max_time` - Simulated time after which the solver stops. No limit when None
+/// * `steps` - Steps made so far
+/// * `simulated_time` - Sum of time steps made so far
+///
+#[derive(Debug, Default)]
+pub(crate) struct StepControl {
+    paused: bool,
+    pending_steps: usize,
+    max_steps: Option<usize>,
+    max_time: Option<f64>,
+    steps: usize,
+    simulated_time: f64,
+}
+
+impl StepControl {
+    /// Creates a step control that stops the solver after `max_steps` steps or `max_time` simulated time, whichever comes first.
+    pub(crate) fn with_budget(max_steps: Option<usize>, max_time: Option<f64>) -> Self {
+        Self {
+            max_steps,
+            max_time,
+            ..Default::default()
+        }
+    }
+
+    /// Wether the budget of steps or simulated time has been used up.
+    pub(crate) fn budget_exhausted(&self) -> bool {
+        matches!(self.max_steps, Some(max_steps) if self.steps >= max_steps)
+            || matches!(self.max_time, Some(max_time) if self.simulated_time >= max_time)
+    }
+
+    /// Records a step of size `time_step` made by the solver. Returns true only on the step that uses up the budget.
+    pub(crate) fn record_step(&mut self, time_step: f64) -> bool {
+        let was_exhausted = self.budget_exhausted();
+        self.steps += 1;
+        self.simulated_time += time_step;
+        !was_exhausted && self.budget_exhausted()
+    }
+
+    /// Continues counting from steps and simulated time of a previous run, as when a checkpoint is restored.
+    pub(crate) fn resume_from(&mut self, steps: usize, simulated_time: f64) {
+        self.steps = steps;
+        self.simulated_time = simulated_time;
+    }
+
+    /// Getter for steps.
+    pub(crate) fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Getter for simulated_time.
+    pub(crate) fn simulated_time(&self) -> f64 {
+        self.simulated_time
+    }
+
+    /// Pauses a running simulation or resumes a paused one. Pending steps are discarded.
+    pub(crate) fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.pending_steps = 0;
+    }
+
+    /// Getter for paused.
+    pub(crate) fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Requests a single step. Ignored when simulation is not paused, since it's advancing anyway.
+    pub(crate) fn request_step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// Wether solver should be called on current frame. Consumes a pending step when paused. Never true once the budget is used up.
+    pub(crate) fn should_solve(&mut self) -> bool {
+        if self.budget_exhausted() {
+            return false;
+        }
+        if !self.paused {
+            return true;
+        }
+        if self.pending_steps > 0 {
+            self.pending_steps -= 1;
+            return true;
+        }
+        false
+    }
+}
+
+/// Most steps made on a single frame. Time beyond them is dropped, so that a slow frame does not make following ones even slower.
+const MAX_STEPS_PER_FRAME: usize = 10;
+
+/// # General Information
+///
+/// Accumulates real time elapsed between frames and turns it into a whole amount of steps of fixed size. Time left over is kept
+/// for the next frame, so simulated time follows real time while every step has the same size regardless of framerate.
+///
+/// # Fields
+///
+/// * `time_step` - Size of every step
+/// * `accumulated` - Real time not yet turned into steps
+///
+#[derive(Debug)]
+pub(crate) struct FixedStepAccumulator {
+    time_step: f64,
+    accumulated: f64,
+}
+
+impl FixedStepAccumulator {
+    /// Creates an accumulator for steps of size `time_step`. Panics when `time_step` is not positive.
+    pub(crate) fn new(time_step: f64) -> Self {
+        if time_step <= 0.0 {
+            panic!("Fixed time step should be positive, got {}", time_step);
+        }
+
+        Self {
+            time_step,
+            accumulated: 0.0,
+        }
+    }
+
+    /// Adds `elapsed` real seconds and returns how many fixed steps fit in time accumulated so far (at most `MAX_STEPS_PER_FRAME`).
+    pub(crate) fn advance(&mut self, elapsed: f64) -> usize {
+        self.accumulated += elapsed;
+        let steps = (self.accumulated / self.time_step).floor() as usize;
+
+        if steps > MAX_STEPS_PER_FRAME {
+            self.accumulated = 0.0;
+            return MAX_STEPS_PER_FRAME;
+        }
+
+        self.accumulated -= steps as f64 * self.time_step;
+        steps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FixedStepAccumulator, StepControl, MAX_STEPS_PER_FRAME};
+
+    #[test]
+    fn single_step_solves_once() {
+        let mut step_control = StepControl::default();
+        assert!(step_control.should_solve());
+
+        step_control.toggle_pause();
+        assert!(step_control.is_paused());
+        assert!(!step_control.should_solve());
+
+        step_control.request_step();
+        let solver_calls = (0..10).filter(|_| step_control.should_solve()).count();
+        assert!(solver_calls == 1);
+        assert!(step_control.is_paused());
+
+        step_control.toggle_pause();
+        assert!(step_control.should_solve());
+    }
+
+    #[test]
+    fn budget_stops_solver() {
+        let mut step_control = StepControl::with_budget(Some(3), None);
+        let triggers: Vec<bool> = (0..3).map(|_| step_control.record_step(0.1)).collect();
+        assert!(triggers == vec![false, false, true]);
+        assert!(step_control.budget_exhausted());
+        assert!(!step_control.should_solve());
+
+        let mut step_control = StepControl::with_budget(None, Some(1.0));
+        let mut steps = 0;
+        while step_control.should_solve() {
+            step_control.record_step(0.25);
+            steps += 1;
+        }
+        assert!(steps == 4);
+
+        // Pending steps do not go over budget
+        step_control.toggle_pause();
+        step_control.request_step();
+        assert!(!step_control.should_solve());
+        assert!(!step_control.record_step(0.25));
+    }
+
+    #[test]
+    fn accumulator_makes_fixed_steps() {
+        let mut accumulator = FixedStepAccumulator::new(0.25);
+
+        // Leftover time carries to next frame
+        assert!(accumulator.advance(0.625) == 2);
+        assert!(accumulator.advance(0.125) == 1);
+        assert!(accumulator.advance(0.125) == 0);
+        let steps: usize = (0..100).map(|_| accumulator.advance(0.01)).sum();
+        assert!(steps == 4);
+
+        // A long frame is capped and its excess dropped
+        assert!(accumulator.advance(100.0) == MAX_STEPS_PER_FRAME);
+        assert!(accumulator.advance(0.0) == 0);
+    }
+}
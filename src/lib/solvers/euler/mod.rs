@@ -1,32 +1,36 @@
+// Internal dependencies
+use super::Scalar;
+use crate::Error;
+
 // FIRST EXCERCISE
 /// Euler's method for ordinary differential equations.
 /// The form of the equation is assumed to be "y'(n) = f(t,y,y',..,y'(n-1))".
 ///
 /// # Parameters
-/// - initial_val: Vec<f32>
+/// - initial_val: Vec<Scalar>
 ///   The previous values of every variable. The derivatives go in descending order: [y'(n),y'(n-1),...,t]
-/// - step: f32
+/// - step: Scalar
 ///   The step used to calculate the approximation.
-/// - f: T where T: Fn(Vec<f32>) -> f32
+/// - f: T where T: Fn(Vec<Scalar>) -> Scalar
 ///   Reffers to the function on the left side of the above equation.
 ///
 /// # Returns
-/// - Vec<f32>
+/// - Vec<Scalar>
 ///   A vector of values corresponding to the new approximation. The derivatives are delivered as in 'initial_val'
 ///
-pub trait FunctionArguments: Into<Vec<f64>> + Clone + std::convert::TryFrom<Vec<f64>> {}
+pub trait FunctionArguments: Into<Vec<Scalar>> + Clone + std::convert::TryFrom<Vec<Scalar>> {}
 
-impl FunctionArguments for [f64; 2] {}
-impl FunctionArguments for [f64; 3] {}
-impl FunctionArguments for [f64; 4] {}
-impl FunctionArguments for [f64; 5] {}
+impl FunctionArguments for [Scalar; 2] {}
+impl FunctionArguments for [Scalar; 3] {}
+impl FunctionArguments for [Scalar; 4] {}
+impl FunctionArguments for [Scalar; 5] {}
 
 pub struct EulerSolver<A, F> {
     derivative_function: F,
     phantom: std::marker::PhantomData<A>,
 }
 
-impl<A: FunctionArguments, F: Fn(&A) -> f64> EulerSolver<A, F> {
+impl<A: FunctionArguments, F: Fn(&A) -> Scalar> EulerSolver<A, F> {
     pub fn new(derivative_function: F) -> EulerSolver<A, F> {
         EulerSolver {
             derivative_function,
@@ -34,27 +38,85 @@ impl<A: FunctionArguments, F: Fn(&A) -> f64> EulerSolver<A, F> {
         }
     }
 
-    pub fn do_step(&self, values: A, step: f64) -> A {
-        let f_eval: f64 = (self.derivative_function)(&values);
-        let as_vec: Vec<f64> = values.into();
+    pub fn do_step(&self, values: A, step: Scalar) -> A {
+        match self.try_step(values, step) {
+            Ok(v) => v,
+            Err(_) => panic!("Nooo"),
+        }
+    }
+
+    /// Same as `do_step`, but an error is returned when new values cannot be converted back into `A`.
+    fn try_step(&self, values: A, step: Scalar) -> Result<A, Error> {
+        let f_eval: Scalar = (self.derivative_function)(&values);
+        let as_vec: Vec<Scalar> = values.into();
 
-        let mut next_values: Vec<f64> = vec![];
+        let mut next_values: Vec<Scalar> = vec![];
 
-        let mut value: f64 = as_vec.get(0).unwrap() + step * f_eval;
-        let t_new: f64 = as_vec.get(as_vec.len() - 1).unwrap() + step;
+        let mut value: Scalar = as_vec.get(0).unwrap() + step * f_eval;
+        let t_new: Scalar = as_vec.get(as_vec.len() - 1).unwrap() + step;
 
         next_values.push(value);
 
         as_vec[1..as_vec.len() - 1].into_iter().for_each(|x| {
-            let new_val: f64 = x + step * value;
+            let new_val: Scalar = x + step * value;
             value = new_val;
             next_values.push(new_val);
         });
         next_values.push(t_new);
 
-        match A::try_from(next_values) {
-            Ok(v) => v,
-            Err(_) => panic!("Nooo"),
+        A::try_from(next_values).map_err(|_| Error::WrongDims)
+    }
+
+    /// Advances `n_steps` steps from `initial` and returns every state visited, starting with `initial` itself (so `n_steps + 1` states).
+    pub fn integrate(&self, initial: A, step: Scalar, n_steps: usize) -> Result<Vec<A>, Error> {
+        let mut trajectory = Vec::with_capacity(n_steps + 1);
+        trajectory.push(initial);
+
+        for _ in 0..n_steps {
+            let next = self.try_step(trajectory[trajectory.len() - 1].clone(), step)?;
+            trajectory.push(next);
+        }
+
+        Ok(trajectory)
+    }
+}
+
+impl<'a, A: FunctionArguments + 'a> EulerSolver<A, Box<dyn Fn(&A) -> Scalar + 'a>> {
+    /// Creates a solver from a function with time and state as separate arguments: "f(t, [y'(n-1),...,y])".
+    /// Internally it is adapted to the form used by `new`, where time is the last value.
+    pub fn new_with_time<G: Fn(Scalar, &[Scalar]) -> Scalar + 'a>(derivative_function: G) -> Self {
+        EulerSolver::new(Box::new(move |values: &A| {
+            let as_vec: Vec<Scalar> = values.clone().into();
+            let (time, state) = as_vec.split_last().unwrap();
+            derivative_function(*time, state)
+        }))
+    }
+}
+
+/// Euler's method for systems of first order ordinary differential equations: "y' = f(t,y)", where y is a vector.
+/// Every component is advanced simultaneously using the values of the previous step.
+pub struct SystemEulerSolver<F> {
+    derivative_function: F,
+}
+
+impl<F: Fn(Scalar, &[Scalar]) -> Vec<Scalar>> SystemEulerSolver<F> {
+    pub fn new(derivative_function: F) -> SystemEulerSolver<F> {
+        SystemEulerSolver {
+            derivative_function,
         }
     }
+
+    pub fn do_step(&self, state: Vec<Scalar>, t: Scalar, step: Scalar) -> Vec<Scalar> {
+        let f_eval: Vec<Scalar> = (self.derivative_function)(t, &state);
+
+        if f_eval.len() != state.len() {
+            panic!("Derivative function should return as many values as there are in state");
+        }
+
+        state
+            .iter()
+            .zip(f_eval.iter())
+            .map(|(value, derivative)| value + step * derivative)
+            .collect()
+    }
 }
@@ -1,3 +1,5 @@
+use crate::solvers::quadrature::{gauss_legendre, interval_map::IntervalMap};
+use crate::solvers::Scalar;
 use crate::Error;
 
 /// # General Information
@@ -8,10 +10,101 @@ use crate::Error;
 /// # Functions
 ///
 /// * `evaluate(...)` - Evaluation of a 1D function.
+/// * `integrate(...)` - Integral over an interval via Gauss-Legendre quadrature.
+/// * `times(...)` - Pointwise product with another function.
+/// * `plus(...)` - Pointwise sum with another function.
+/// * `scale(...)` - Function multiplied by a constant.
 ///
 pub trait Function1D {
     /// Evaluation of a 1D function.
-    fn evaluate(&self, x: f64) -> f64;
+    fn evaluate(&self, x: Scalar) -> Scalar;
+
+    /// # General Information
+    ///
+    /// Integral of function over [a,b] via Gauss-Legendre quadrature with `n` nodes. Exact for polynomials of degree up to 2n - 1.
+    /// Zero nodes give zero.
+    /// Fails if a quadrature node can not be obtained.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Function to integrate
+    /// * `a` - Start of interval
+    /// * `b` - End of interval
+    /// * `n` - Amount of nodes
+    ///
+    fn integrate(&self, a: Scalar, b: Scalar, n: usize) -> Result<Scalar, Error> {
+        let map = IntervalMap::new(a, b);
+        let pairs = (1..=n).map(|k| gauss_legendre::quad_pair(n, k)).collect::<Result<Vec<_>, _>>()?;
+
+        Ok(pairs
+            .into_iter()
+            .map(|(theta, w)| w * self.evaluate(map.from_reference(theta.cos())))
+            .sum::<Scalar>()
+            * map.jacobian())
+    }
+
+    /// Pointwise product "f(x) g(x)". Functions are moved inside, so references should be given to keep using them.
+    fn times<'a, T: Function1D + 'a>(self, other: T) -> Box<dyn Function1D + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(Product(Box::new(self), Box::new(other)))
+    }
+
+    /// Pointwise sum "f(x) + g(x)". Functions are moved inside, so references should be given to keep using them.
+    fn plus<'a, T: Function1D + 'a>(self, other: T) -> Box<dyn Function1D + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(Sum(Box::new(self), Box::new(other)))
+    }
+
+    /// Function multiplied by a constant "c f(x)".
+    fn scale<'a>(self, factor: Scalar) -> Box<dyn Function1D + 'a>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(Scaled(factor, Box::new(self)))
+    }
+}
+
+impl<T: Function1D + ?Sized> Function1D for &T {
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        (**self).evaluate(x)
+    }
+}
+
+impl<T: Function1D + ?Sized> Function1D for Box<T> {
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        (**self).evaluate(x)
+    }
+}
+
+/// Pointwise product of two functions, as built by `Function1D::times`.
+struct Product<'a>(Box<dyn Function1D + 'a>, Box<dyn Function1D + 'a>);
+
+/// Pointwise sum of two functions, as built by `Function1D::plus`.
+struct Sum<'a>(Box<dyn Function1D + 'a>, Box<dyn Function1D + 'a>);
+
+/// Function multiplied by a constant, as built by `Function1D::scale`.
+struct Scaled<'a>(Scalar, Box<dyn Function1D + 'a>);
+
+impl Function1D for Product<'_> {
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        self.0.evaluate(x) * self.1.evaluate(x)
+    }
+}
+
+impl Function1D for Sum<'_> {
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        self.0.evaluate(x) + self.1.evaluate(x)
+    }
+}
+
+impl Function1D for Scaled<'_> {
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        self.0 * self.1.evaluate(x)
+    }
 }
 
 /// # General Information
@@ -25,7 +118,7 @@ pub trait Function1D {
 ///
 pub trait Function2D {
     /// Evaluation of a 2D function.
-    fn evaluate(&self, x: f64, y: f64) -> f64;
+    fn evaluate(&self, x: Scalar, y: Scalar) -> Scalar;
 }
 
 /// # General Information
@@ -38,7 +131,7 @@ pub trait Function2D {
 /// 
 pub trait Function2D2D {
     /// Evaluation of a 2D vector function
-    fn evaluate(&self, x: f64, y: f64) -> (f64,f64);
+    fn evaluate(&self, x: Scalar, y: Scalar) -> (Scalar,Scalar);
 }
 
 /// # General Information
@@ -114,3 +207,37 @@ where
 {
     fn compose(self,other: T) -> Result<V,Error>;
 }
+
+#[cfg(test)]
+mod test {
+    use super::Function1D;
+    use crate::solvers::basis::single_variable::polynomials_1d::FirstDegreePolynomial;
+
+    #[test]
+    fn integrate_first_degree_polynomial() {
+        // 3x + 2 over [1,4]: 3/2 (16 - 1) + 2 (4 - 1) = 28.5
+        let polynomial = FirstDegreePolynomial::new(3.0, 2.0);
+        assert!((polynomial.integrate(1.0, 4.0, 1).unwrap() - 28.5).abs() < 1e-12);
+        assert!((polynomial.integrate(1.0, 4.0, 5).unwrap() - 28.5).abs() < 1e-12);
+        // Reversed interval changes sign
+        assert!((polynomial.integrate(4.0, 1.0, 2).unwrap() + 28.5).abs() < 1e-12);
+        assert!(polynomial.integrate(1.0, 4.0, 0).unwrap() == 0.0);
+    }
+
+    #[test]
+    fn product_and_sum_of_linear_functions() {
+        let f = FirstDegreePolynomial::new(2.0, 1.0);
+        let g = FirstDegreePolynomial::new(-1.0, 3.0);
+
+        let product = (&f).times(&g);
+        let combination = (&f).times(&g).scale(2.0).plus(&f);
+        for x in [-2.0, -0.5, 0.0, 1.0, 3.5] {
+            let expected = (2.0 * x + 1.0) * (-x + 3.0);
+            assert!((product.evaluate(x) - expected).abs() < 1e-12);
+            assert!((combination.evaluate(x) - (2.0 * expected + 2.0 * x + 1.0)).abs() < 1e-12);
+        }
+
+        // Product is quadratic, so two nodes integrate it exactly: -2x^2 + 5x + 3 over [0,1] is -2/3 + 5/2 + 3
+        assert!((product.integrate(0.0, 1.0, 2).unwrap() - (-2.0 / 3.0 + 2.5 + 3.0)).abs() < 1e-12);
+    }
+}
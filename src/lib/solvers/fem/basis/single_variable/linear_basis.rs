@@ -1,4 +1,5 @@
 // Internal dependencies
+use crate::solvers::Scalar;
 use super::piecewise_polynomials_1degree::PiecewiseFirstDegreePolynomial;
 use super::polynomials_1d::FirstDegreePolynomial;
 use crate::solvers::basis::functions::Composable1D;
@@ -28,7 +29,7 @@ impl LinearBasis {
     ///
     /// * `mesh` - A reference to the original mesh of points (filtered to omit RGB values).
     ///
-    pub(crate) fn new(mesh: &Vec<f64>) -> Result<LinearBasis, Error> {
+    pub(crate) fn new(mesh: &Vec<Scalar>) -> Result<LinearBasis, Error> {
         // Left-side function
         let transformation = FirstDegreePolynomial::transformation_to_0_1(mesh[0], mesh[1]);
         let initial_transform_function = FirstDegreePolynomial::phi_2().compose(transformation)?;
@@ -1,6 +1,7 @@
 // Internal dependencies.
 use super::{polynomials_1d::FirstDegreePolynomial};
 use crate::solvers::basis::functions::{Differentiable1D,Function1D};
+use crate::solvers::Scalar;
 use crate::Error;
 
 /// # General Information
@@ -16,7 +17,7 @@ use crate::Error;
 #[derive(PartialEq, Debug)]
 pub struct PiecewiseFirstDegreePolynomial {
     polynomials: Vec<FirstDegreePolynomial>,
-    interval_breakpoints: Vec<f64>,
+    interval_breakpoints: Vec<Scalar>,
 }
 
 impl PiecewiseFirstDegreePolynomial {
@@ -30,15 +31,15 @@ impl PiecewiseFirstDegreePolynomial {
     /// * `independent_terms` - Values that are added to variable.
     /// * `interval_breakpoints` - Points in ascending order to know which function to evaluate.
     ///
-    pub fn from_values<A: IntoIterator<Item = f64>, B: IntoIterator<Item = f64>>(
+    pub fn from_values<A: IntoIterator<Item = Scalar>, B: IntoIterator<Item = Scalar>>(
         coefficients: A,
         independent_terms: A,
         interval_breakpoints: B,
     ) -> Result<Self, Error> {
 
-        let independent_terms: Vec<f64> = independent_terms.into_iter().collect();
-        let coefficients: Vec<f64> = coefficients.into_iter().collect();
-        let interval_breakpoints: Vec<f64> = interval_breakpoints.into_iter().collect();
+        let independent_terms: Vec<Scalar> = independent_terms.into_iter().collect();
+        let coefficients: Vec<Scalar> = coefficients.into_iter().collect();
+        let interval_breakpoints: Vec<Scalar> = interval_breakpoints.into_iter().collect();
 
         if independent_terms.len() != interval_breakpoints.len() + 1
             || independent_terms.len() != coefficients.len()
@@ -69,13 +70,13 @@ impl PiecewiseFirstDegreePolynomial {
     /// * `independent_terms` - Vector of constants to create function.
     /// * `interval_breakpoints` - Points in ascending order to know which constant to return.
     ///
-    pub fn from_constants<A: IntoIterator<Item = f64>, B: IntoIterator<Item = f64>>(
+    pub fn from_constants<A: IntoIterator<Item = Scalar>, B: IntoIterator<Item = Scalar>>(
         independent_terms: A,
         interval_breakpoints: B,
     ) -> Result<Self, Error> {
 
-        let independent_terms: Vec<f64> = independent_terms.into_iter().collect();
-        let interval_breakpoints: Vec<f64> = interval_breakpoints.into_iter().collect();
+        let independent_terms: Vec<Scalar> = independent_terms.into_iter().collect();
+        let interval_breakpoints: Vec<Scalar> = interval_breakpoints.into_iter().collect();
 
         if independent_terms.len() != interval_breakpoints.len() + 1 {
             return Err(Error::PieceWiseDims);
@@ -83,7 +84,7 @@ impl PiecewiseFirstDegreePolynomial {
 
         let polynomials = independent_terms
             .into_iter()
-            .map(|i_term| -> FirstDegreePolynomial { FirstDegreePolynomial::new(0_f64, i_term) })
+            .map(|i_term| -> FirstDegreePolynomial { FirstDegreePolynomial::new(0.0, i_term) })
             .collect();
 
         Ok(Self {
@@ -101,13 +102,13 @@ impl PiecewiseFirstDegreePolynomial {
     /// * `polynomials` - A vector with all the polynomials to use for piecewise definition.
     /// * `interval_breakpoints` - Points in ascending order to know which function to evaluate.
     ///
-    pub fn from_polynomials<A: IntoIterator<Item = FirstDegreePolynomial>, B: IntoIterator<Item = f64>>(
+    pub fn from_polynomials<A: IntoIterator<Item = FirstDegreePolynomial>, B: IntoIterator<Item = Scalar>>(
         polynomials: A,
         interval_breakpoints: B,
     ) -> Result<Self, Error> {
 
         let polynomials: Vec<FirstDegreePolynomial> = polynomials.into_iter().collect();
-        let interval_breakpoints: Vec<f64> = interval_breakpoints.into_iter().collect();
+        let interval_breakpoints: Vec<Scalar> = interval_breakpoints.into_iter().collect();
 
         if polynomials.len() != interval_breakpoints.len() + 1 {
             return Err(Error::PieceWiseDims);
@@ -130,9 +131,9 @@ impl Function1D for PiecewiseFirstDegreePolynomial {
     /// Evaluation is made via checking if variable `x` is less than current breakpoint. If x is bigger than every breakpoint, then the last function is
     /// evaluated.
     ///
-    fn evaluate(&self, x: f64) -> f64 {
+    fn evaluate(&self, x: Scalar) -> Scalar {
         let val = self.interval_breakpoints.iter().enumerate().find_map(
-            |(i, breakpoint)| -> Option<f64> {
+            |(i, breakpoint)| -> Option<Scalar> {
                 if x < *breakpoint {
                     Some(self.polynomials[i].evaluate(x))
                 } else {
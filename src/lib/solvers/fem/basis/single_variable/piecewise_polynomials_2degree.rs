@@ -3,6 +3,7 @@ use crate::Error;
 
 use super::{polynomials_1d::{SecondDegreePolynomial, FirstDegreePolynomial}, piecewise_polynomials_1degree::PiecewiseFirstDegreePolynomial};
 use crate::solvers::basis::functions::{Function1D,Differentiable1D};
+use crate::solvers::Scalar;
 /// # General Information
 ///
 /// A piecewise definition of a first-degree polynomial function. Carries both a vector of functions and the intervals on which each must be evaluated.
@@ -16,7 +17,7 @@ use crate::solvers::basis::functions::{Function1D,Differentiable1D};
 #[derive(PartialEq, Debug)]
 pub struct PiecewiseSecondDegreePolynomial {
     polynomials: Vec<SecondDegreePolynomial>,
-    interval_breakpoints: Vec<f64>,
+    interval_breakpoints: Vec<Scalar>,
 }
 
 impl PiecewiseSecondDegreePolynomial {
@@ -31,17 +32,17 @@ impl PiecewiseSecondDegreePolynomial {
     /// * `independent_terms` - Values that are added to variable.
     /// * `interval_breakpoints` - Points in ascending order to know which function to evaluate.
     ///
-    pub fn from_values<A: IntoIterator<Item = f64>, B: IntoIterator<Item = f64>>(
+    pub fn from_values<A: IntoIterator<Item = Scalar>, B: IntoIterator<Item = Scalar>>(
         quadratic_coefficients: A,
         linear_coefficients: A,
         independent_terms: A,
         interval_breakpoints: B,
     ) -> Result<Self, Error> {
 
-        let independent_terms: Vec<f64> = independent_terms.into_iter().collect();
-        let linear_coefficients: Vec<f64> = linear_coefficients.into_iter().collect();
-        let quadratic_coefficients: Vec<f64> = quadratic_coefficients.into_iter().collect();
-        let interval_breakpoints: Vec<f64> = interval_breakpoints.into_iter().collect();
+        let independent_terms: Vec<Scalar> = independent_terms.into_iter().collect();
+        let linear_coefficients: Vec<Scalar> = linear_coefficients.into_iter().collect();
+        let quadratic_coefficients: Vec<Scalar> = quadratic_coefficients.into_iter().collect();
+        let interval_breakpoints: Vec<Scalar> = interval_breakpoints.into_iter().collect();
 
         if independent_terms.len() != interval_breakpoints.len() + 1
             || independent_terms.len() != linear_coefficients.len()
@@ -75,13 +76,13 @@ impl PiecewiseSecondDegreePolynomial {
     /// * `polynomials` - A vector with all the polynomials to use for piecewise definition.
     /// * `interval_breakpoints` - Points in ascending order to know which function to evaluate.
     ///
-    pub fn from_polynomials<A: IntoIterator<Item = SecondDegreePolynomial>, B: IntoIterator<Item = f64>>(
+    pub fn from_polynomials<A: IntoIterator<Item = SecondDegreePolynomial>, B: IntoIterator<Item = Scalar>>(
         polynomials: A,
         interval_breakpoints: B,
     ) -> Result<Self, Error> {
 
         let polynomials: Vec<SecondDegreePolynomial> = polynomials.into_iter().collect();
-        let interval_breakpoints: Vec<f64> = interval_breakpoints.into_iter().collect();
+        let interval_breakpoints: Vec<Scalar> = interval_breakpoints.into_iter().collect();
 
         if polynomials.len() != interval_breakpoints.len() + 1 {
             return Err(Error::PieceWiseDims);
@@ -104,9 +105,9 @@ impl Function1D for PiecewiseSecondDegreePolynomial {
     /// Evaluation is made via checking if variable `x` is less than current breakpoint. If x is bigger than every breakpoint, then the last function is
     /// evaluated.
     ///
-    fn evaluate(&self, x: f64) -> f64 {
+    fn evaluate(&self, x: Scalar) -> Scalar {
         let val = self.interval_breakpoints.iter().enumerate().find_map(
-            |(i, breakpoint)| -> Option<f64> {
+            |(i, breakpoint)| -> Option<Scalar> {
                 if x < *breakpoint {
                     Some(self.polynomials[i].evaluate(x))
                 } else {
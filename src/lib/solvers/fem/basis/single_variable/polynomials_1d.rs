@@ -2,6 +2,7 @@ use crate::Error;
 
 // Internal dependencies.
 use crate::solvers::basis::functions::{Composable1D, Differentiable1D, Function1D};
+use crate::solvers::Scalar;
 
 #[derive(PartialEq, Debug)]
 /// # General Information
@@ -14,8 +15,8 @@ use crate::solvers::basis::functions::{Composable1D, Differentiable1D, Function1
 /// * `independent_term` - constant that adds to variable.
 ///
 pub struct FirstDegreePolynomial {
-    pub(crate) coefficient: f64,
-    pub(crate) independent_term: f64,
+    pub(crate) coefficient: Scalar,
+    pub(crate) independent_term: Scalar,
 }
 
 #[derive(PartialEq, Debug)]
@@ -30,14 +31,14 @@ pub struct FirstDegreePolynomial {
 /// * `independent_term` - constant that is added to varaible.
 ///
 pub struct SecondDegreePolynomial {
-    quadratic_coefficient: f64,
-    linear_coefficient: f64,
-    independent_term: f64,
+    quadratic_coefficient: Scalar,
+    linear_coefficient: Scalar,
+    independent_term: Scalar,
 }
 
 impl FirstDegreePolynomial {
     /// Normal constructor.
-    pub fn new(coefficient: f64, independent_term: f64) -> FirstDegreePolynomial {
+    pub fn new(coefficient: Scalar, independent_term: Scalar) -> FirstDegreePolynomial {
         FirstDegreePolynomial {
             coefficient,
             independent_term,
@@ -47,22 +48,22 @@ impl FirstDegreePolynomial {
     /// Zero function factory.
     pub fn zero() -> FirstDegreePolynomial {
         Self {
-            coefficient: 0_f64,
-            independent_term: 0_f64,
+            coefficient: 0.0,
+            independent_term: 0.0,
         }
     }
 
     /// Constant function factory.
-    pub fn constant(independent_term: f64) -> FirstDegreePolynomial {
+    pub fn constant(independent_term: Scalar) -> FirstDegreePolynomial {
         Self {
-            coefficient: 0_f64,
+            coefficient: 0.0,
             independent_term,
         }
     }
 
     /// Transformation from any interval to [0,1].
-    pub fn transformation_to_0_1(beg: f64, end: f64) -> FirstDegreePolynomial {
-        let coefficient = 1_f64 / (end - beg);
+    pub fn transformation_to_0_1(beg: Scalar, end: Scalar) -> FirstDegreePolynomial {
+        let coefficient = 1.0 / (end - beg);
         let independent_term = -beg / (end - beg);
         FirstDegreePolynomial {
             coefficient,
@@ -71,9 +72,9 @@ impl FirstDegreePolynomial {
     }
 
     /// Transformation from [-1,1] to any interval.
-    pub fn transformation_from_m1_p1(beg: f64, end: f64) -> FirstDegreePolynomial {
-        let coefficient = (end - beg) / 2_f64;
-        let independent_term = (end + beg) / 2_f64;
+    pub fn transformation_from_m1_p1(beg: Scalar, end: Scalar) -> FirstDegreePolynomial {
+        let coefficient = (end - beg) / 2.0;
+        let independent_term = (end + beg) / 2.0;
         FirstDegreePolynomial {
             coefficient,
             independent_term,
@@ -83,16 +84,16 @@ impl FirstDegreePolynomial {
     // One of two basis functions on unit interval [0,1]
     pub fn phi_1() -> FirstDegreePolynomial {
         FirstDegreePolynomial {
-            coefficient: 1_f64,
-            independent_term: 0_f64,
+            coefficient: 1.0,
+            independent_term: 0.0,
         }
     }
 
     // One of two basis functions on unit interval [0,1]
     pub fn phi_2() -> FirstDegreePolynomial {
         FirstDegreePolynomial {
-            coefficient: -1_f64,
-            independent_term: 1_f64,
+            coefficient: -1.0,
+            independent_term: 1.0,
         }
     }
 }
@@ -102,7 +103,7 @@ impl Function1D for FirstDegreePolynomial {
     ///
     /// Simple evaluation of a polynomial.
     ///
-    fn evaluate(&self, x: f64) -> f64 {
+    fn evaluate(&self, x: Scalar) -> Scalar {
         self.coefficient * x + self.independent_term
     }
 }
@@ -128,7 +129,7 @@ impl Differentiable1D<FirstDegreePolynomial> for FirstDegreePolynomial {
     ///
     fn differentiate(&self) -> Result<FirstDegreePolynomial,Error> {
         Ok(FirstDegreePolynomial {
-            coefficient: 0_f64,
+            coefficient: 0.0,
             independent_term: self.coefficient,
         })
     }
@@ -137,9 +138,9 @@ impl Differentiable1D<FirstDegreePolynomial> for FirstDegreePolynomial {
 impl SecondDegreePolynomial {
     /// Simple constructor for second degree polynomial.
     pub fn new(
-        quadratic_coefficient: f64,
-        linear_coefficient: f64,
-        independent_term: f64,
+        quadratic_coefficient: Scalar,
+        linear_coefficient: Scalar,
+        independent_term: Scalar,
     ) -> SecondDegreePolynomial {
         SecondDegreePolynomial {
             quadratic_coefficient,
@@ -151,27 +152,27 @@ impl SecondDegreePolynomial {
     // One of three basis functions on unit interval [0,1]
     pub fn psi_1() -> SecondDegreePolynomial {
         SecondDegreePolynomial {
-            quadratic_coefficient: 2_f64,
-            linear_coefficient: -3_f64,
-            independent_term: 1_f64
+            quadratic_coefficient: 2.0,
+            linear_coefficient: -3.0,
+            independent_term: 1.0
         }
     }
 
     // One of three basis functions on unit interval [0,1]
     pub fn psi_2() -> SecondDegreePolynomial {
         SecondDegreePolynomial {
-            quadratic_coefficient: -4_f64,
-            linear_coefficient: 4_f64,
-            independent_term: 0_f64
+            quadratic_coefficient: -4.0,
+            linear_coefficient: 4.0,
+            independent_term: 0.0
         }
     }
 
     // One of three basis functions on unit interval [0,1]
     pub fn psi_3() -> SecondDegreePolynomial {
         SecondDegreePolynomial {
-            quadratic_coefficient: 2_f64,
-            linear_coefficient: -1_f64,
-            independent_term: 0_f64
+            quadratic_coefficient: 2.0,
+            linear_coefficient: -1.0,
+            independent_term: 0.0
         }
     }
 
@@ -182,8 +183,8 @@ impl Function1D for SecondDegreePolynomial {
     ///
     /// Simple evaluation of a second degree polynomial.
     ///
-    fn evaluate(&self, x: f64) -> f64 {
-        self.quadratic_coefficient * x.powf(2_f64)
+    fn evaluate(&self, x: Scalar) -> Scalar {
+        self.quadratic_coefficient * x.powf(2.0)
             + self.linear_coefficient * x
             + self.independent_term
     }
@@ -196,7 +197,7 @@ impl Differentiable1D<FirstDegreePolynomial> for SecondDegreePolynomial {
     ///
     fn differentiate(&self) -> Result<FirstDegreePolynomial,Error> {
         Ok(FirstDegreePolynomial {
-            coefficient: 2_f64 * self.quadratic_coefficient,
+            coefficient: 2.0 * self.quadratic_coefficient,
             independent_term: self.linear_coefficient,
         })
     }
@@ -210,13 +211,13 @@ impl Composable1D<FirstDegreePolynomial, SecondDegreePolynomial> for SecondDegre
     ///
     fn compose(self, other: FirstDegreePolynomial) -> Result<SecondDegreePolynomial,Error> {
         Ok(SecondDegreePolynomial {
-            quadratic_coefficient: self.quadratic_coefficient * other.coefficient.powf(2_f64),
-            linear_coefficient: 2_f64
+            quadratic_coefficient: self.quadratic_coefficient * other.coefficient.powf(2.0),
+            linear_coefficient: 2.0
                 * self.quadratic_coefficient
                 * other.coefficient
                 * other.independent_term
                 + self.linear_coefficient * other.coefficient,
-            independent_term: other.independent_term.powf(2_f64)
+            independent_term: other.independent_term.powf(2.0)
                 + other.independent_term * self.linear_coefficient
                 + self.independent_term,
         })
@@ -1,5 +1,6 @@
 // Internal dependencies
 use crate::solvers::basis::functions::{Function2D, Function2D2D, Composable2D, Differentiable2D};
+use crate::solvers::Scalar;
 
 #[derive(PartialEq, Debug)]
 /// # General Information
@@ -13,9 +14,9 @@ use crate::solvers::basis::functions::{Function2D, Function2D2D, Composable2D, D
 /// * `independent_term` - constant that adds to variable.
 ///
 pub struct FirstDegreePolynomial2D {
-    pub(crate) x_coefficient: f64,
-    pub(crate) y_coefficient: f64,
-    pub(crate) independent_term: f64
+    pub(crate) x_coefficient: Scalar,
+    pub(crate) y_coefficient: Scalar,
+    pub(crate) independent_term: Scalar
 }
 
 #[derive(PartialEq, Debug)]
@@ -33,12 +34,12 @@ pub struct FirstDegreePolynomial2D {
 /// * `independent_term` - constant that is added to varaibles.
 ///
 pub struct SecondDegreePolynomial2D {
-    x_quadratic_coefficient: f64,
-    y_quadratic_coefficient: f64,
-    xy_coefficient: f64,
-    x_linear_coefficient: f64,
-    y_linear_coefficient: f64,
-    independent_term: f64,
+    x_quadratic_coefficient: Scalar,
+    y_quadratic_coefficient: Scalar,
+    xy_coefficient: Scalar,
+    x_linear_coefficient: Scalar,
+    y_linear_coefficient: Scalar,
+    independent_term: Scalar,
 }
 
 /// # General Information
@@ -54,16 +55,16 @@ pub struct SecondDegreePolynomial2D {
 /// * `d` - Element [1,1] in matrix
 /// 
 pub struct Transformation2D {
-    a: f64,
-    b: f64,
-    c: f64,
-    d: f64
+    a: Scalar,
+    b: Scalar,
+    c: Scalar,
+    d: Scalar
 }
 
 impl Transformation2D {
 
     ///  New instance
-    pub fn new(a: f64, b: f64, c: f64, d: f64) -> Transformation2D {
+    pub fn new(a: Scalar, b: Scalar, c: Scalar, d: Scalar) -> Transformation2D {
         Transformation2D {
             a,
             b,
@@ -75,7 +76,7 @@ impl Transformation2D {
     /// Inverse of a  2x2 matrix
     pub fn inverse(self) -> Transformation2D {
         
-        let determinant = 1_f64 / (self.a * self.d - self.b * self.c);
+        let determinant = 1.0 / (self.a * self.d - self.b * self.c);
         
         Transformation2D {
              a: self.d * determinant,
@@ -88,7 +89,7 @@ impl Transformation2D {
 }
 
 impl Function2D2D for Transformation2D {
-    fn evaluate(&self, x: f64, y: f64) -> (f64,f64) {
+    fn evaluate(&self, x: Scalar, y: Scalar) -> (Scalar,Scalar) {
         (self.a * x + self.b * y, self.c * x + self.d * y)
     }
 }
@@ -96,7 +97,7 @@ impl Function2D2D for Transformation2D {
 
 impl FirstDegreePolynomial2D {
     /// Normal constructor.
-    pub fn new(x_coefficient: f64, y_coefficient: f64, independent_term: f64) -> FirstDegreePolynomial2D {
+    pub fn new(x_coefficient: Scalar, y_coefficient: Scalar, independent_term: Scalar) -> FirstDegreePolynomial2D {
         FirstDegreePolynomial2D {
             x_coefficient,
             y_coefficient,
@@ -107,23 +108,23 @@ impl FirstDegreePolynomial2D {
     /// Zero function factory.
     pub fn zero() -> FirstDegreePolynomial2D {
         Self {
-            x_coefficient: 0_f64,
-            y_coefficient: 0_f64,
-            independent_term: 0_f64,
+            x_coefficient: 0.0,
+            y_coefficient: 0.0,
+            independent_term: 0.0,
         }
     }
 
     /// Constant function factory.
-    pub fn constant(independent_term: f64) -> FirstDegreePolynomial2D {
+    pub fn constant(independent_term: Scalar) -> FirstDegreePolynomial2D {
         Self {
-            x_coefficient: 0_f64,
-            y_coefficient: 0_f64,
+            x_coefficient: 0.0,
+            y_coefficient: 0.0,
             independent_term,
         }
     }
 
     /// Translate a function by a given point
-    pub fn translate(self, w: f64, z: f64) -> FirstDegreePolynomial2D {
+    pub fn translate(self, w: Scalar, z: Scalar) -> FirstDegreePolynomial2D {
         Self {
             x_coefficient: self.x_coefficient,
             y_coefficient: self.y_coefficient,
@@ -134,33 +135,33 @@ impl FirstDegreePolynomial2D {
     /// One of three basis functions on unit triangle {(0,0),(1,0),(0,1)}
     pub fn psi_1() -> FirstDegreePolynomial2D {
         FirstDegreePolynomial2D {
-            x_coefficient: -1_f64,
-            y_coefficient: -1_f64,
-            independent_term: 1_f64,
+            x_coefficient: -1.0,
+            y_coefficient: -1.0,
+            independent_term: 1.0,
         }
     }
 
     /// One of three basis functions on unit triangle {(0,0),(1,0),(0,1)}
     pub fn psi_2() -> FirstDegreePolynomial2D {
         FirstDegreePolynomial2D {
-            x_coefficient: 1_f64,
-            y_coefficient: 0_f64,
-            independent_term: 0_f64,
+            x_coefficient: 1.0,
+            y_coefficient: 0.0,
+            independent_term: 0.0,
         }
     }
 
     /// One of three basis functions on unit triangle {(0,0),(1,0),(0,1)}
     pub fn psi_3() -> FirstDegreePolynomial2D {
         FirstDegreePolynomial2D {
-            x_coefficient: 0_f64,
-            y_coefficient: 1_f64,
-            independent_term: 0_f64
+            x_coefficient: 0.0,
+            y_coefficient: 1.0,
+            independent_term: 0.0
         }
     }
 }
 
 impl Function2D for FirstDegreePolynomial2D {
-    fn evaluate(&self, x: f64, y: f64) -> f64 {
+    fn evaluate(&self, x: Scalar, y: Scalar) -> Scalar {
         self.x_coefficient * x + self.y_coefficient * y + self.independent_term
     }
 }
@@ -186,8 +187,8 @@ impl Differentiable2D<FirstDegreePolynomial2D,FirstDegreePolynomial2D> for First
     fn differentiate_x(&self) -> Result<FirstDegreePolynomial2D,crate::Error> {
         Ok(
             FirstDegreePolynomial2D {
-                x_coefficient: 0_f64,
-                y_coefficient: 0_f64,
+                x_coefficient: 0.0,
+                y_coefficient: 0.0,
                 independent_term: self.x_coefficient
             }
         )
@@ -197,8 +198,8 @@ impl Differentiable2D<FirstDegreePolynomial2D,FirstDegreePolynomial2D> for First
     fn differentiate_y(&self) -> Result<FirstDegreePolynomial2D,crate::Error> {
         Ok(
             FirstDegreePolynomial2D {
-                x_coefficient: 0_f64,
-                y_coefficient: 0_f64,
+                x_coefficient: 0.0,
+                y_coefficient: 0.0,
                 independent_term: self.y_coefficient
             }
         )
@@ -0,0 +1,254 @@
+// Internal dependencies
+use crate::solvers::Scalar;
+use crate::solvers::{matrix_solver, quadrature::{gauss_legendre, interval_map::IntervalMap}, solver_trait::DiffEquationSolver};
+use crate::Error;
+use super::{BoundaryCondition, DiffussionParamsTimeIndependent};
+
+// External dependencies
+use ndarray::{Array1, Array2};
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+/// # General Information
+///
+/// Solver for "-μu_xx + bu_x = f" where every element of a 1D mesh may use its own polynomial order (p-refinement).
+/// Basis is hierarchical: a linear hat function per node plus, on every quadratic element, a bubble function "1 - ξ²" (ξ the reference coordinate)
+/// which vanishes on both nodes of its element. Linear and quadratic elements are therefore joined without extra constraints.
+/// Degrees of freedom are numbered node by node first and then one per quadratic element, in element order, so the first values
+/// of a solution are always the values on nodes.
+///
+/// # Fields
+///
+/// * `stiffness_matrix` - Matrix over every degree of freedom, boundary conditions already applied.
+/// * `b_vector` - Load vector over every degree of freedom.
+/// * `mesh` - Vector of f64 representing a line.
+/// * `bubbles` - Degree of freedom of the bubble function on every element. None on linear elements.
+/// * `assembly_time` - Time spent assembling matrix and vector.
+///
+pub struct MixedOrderSolver1D {
+    pub(crate) stiffness_matrix: Array2<Scalar>,
+    pub(crate) b_vector: Array1<Scalar>,
+    pub(crate) mesh: Vec<Scalar>,
+    pub(crate) bubbles: Vec<Option<usize>>,
+    pub(crate) assembly_time: Duration,
+}
+
+impl MixedOrderSolver1D {
+    /// # General Information
+    ///
+    /// Creates new instance. Element matrices and loads are integrated with Gauss-Legendre over every element and added to the global system.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - Movement term, velocity term and boundary conditions (Dirichlet or Neumann) of the equation.
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `orders` - Polynomial order of every element, 1 (linear) or 2 (quadratic). Has one less entry than mesh.
+    /// * `force_function` - Right-hand side "f" of the equation.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    pub fn new<F: Fn(Scalar) -> Scalar>(
+        params: &DiffussionParamsTimeIndependent,
+        mesh: Vec<Scalar>,
+        orders: &[usize],
+        force_function: F,
+        gauss_step: usize,
+    ) -> Result<Self, Error> {
+
+        if mesh.len() < 2 || orders.len() != mesh.len() - 1 {
+            return Err(Error::WrongDims);
+        }
+        if let Some(order) = orders.iter().find(|order| !matches!(order, 1 | 2)) {
+            return Err(Error::Config(format!("Element order {} is not supported, only 1 and 2 are", order)));
+        }
+
+        let assembly_start = Instant::now();
+
+        // Bubble degrees of freedom come after every node
+        let mut next_dof = mesh.len();
+        let bubbles: Vec<Option<usize>> = orders
+            .iter()
+            .map(|order| {
+                (*order == 2).then(|| {
+                    next_dof += 1;
+                    next_dof - 1
+                })
+            })
+            .collect();
+
+        let (stiffness_matrix, b_vector) =
+            Self::gen_stiffness_matrix_and_b_vector(params, &mesh, &bubbles, next_dof, &force_function, gauss_step)?;
+
+        Ok(Self {
+            stiffness_matrix,
+            b_vector,
+            mesh,
+            bubbles,
+            assembly_time: assembly_start.elapsed(),
+        })
+    }
+
+    /// Shape functions of an element on reference coordinate ξ and their derivatives with respect to ξ: both hat functions and the bubble.
+    fn shape_functions(xi: Scalar) -> ([Scalar; 3], [Scalar; 3]) {
+        (
+            [(1.0 - xi) / 2.0, (1.0 + xi) / 2.0, 1.0 - xi * xi],
+            [-0.5, 0.5, -2.0 * xi],
+        )
+    }
+
+    /// # General Information
+    ///
+    /// Assembles matrix and vector element by element. Every entry integrates "μ φ_j' φ_i + b φ_j' φ_i" and every load "f φ_i".
+    /// Afterwards, rows of Dirichlet ends are replaced by their value and Neumann ends receive the boundary term "μ u'(end) n" with n the outward normal.
+    ///
+    /// # Parameters
+    ///
+    /// * `params` - Movement term, velocity term and boundary conditions of the equation.
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `bubbles` - Degree of freedom of the bubble function on every element.
+    /// * `dofs` - Amount of degrees of freedom.
+    /// * `force_function` - Right-hand side "f" of the equation.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    fn gen_stiffness_matrix_and_b_vector<F: Fn(Scalar) -> Scalar>(
+        params: &DiffussionParamsTimeIndependent,
+        mesh: &[Scalar],
+        bubbles: &[Option<usize>],
+        dofs: usize,
+        force_function: &F,
+        gauss_step: usize,
+    ) -> Result<(Array2<Scalar>, Array1<Scalar>), Error> {
+
+        let mut stiffness_matrix = Array2::from_elem((dofs, dofs), 0.0);
+        let mut b_vector = Array1::from_elem(dofs, 0.0);
+
+        for (element, bubble) in bubbles.iter().enumerate() {
+
+            let transform_function = IntervalMap::new(mesh[element], mesh[element + 1]);
+            let jacobian = transform_function.jacobian();
+            // (local shape function, global degree of freedom)
+            let local_dofs: Vec<(usize, usize)> = [(0, element), (1, element + 1)].into_iter().chain(bubble.map(|dof| (2, dof))).collect();
+
+            for k in 1..=gauss_step {
+                // Obtaining arccos(node) and weight
+                let (theta, w) = gauss_legendre::quad_pair(gauss_step, k)?;
+                let xi = theta.cos();
+                let (values, reference_derivatives) = Self::shape_functions(xi);
+                let derivatives = reference_derivatives.map(|derivative| derivative / jacobian);
+                let weight = w * jacobian;
+                let force = force_function(transform_function.from_reference(xi));
+
+                for &(a, row) in &local_dofs {
+                    b_vector[row] += force * values[a] * weight;
+                    for &(c, column) in &local_dofs {
+                        stiffness_matrix[[row, column]] +=
+                            (params.mu * derivatives[c] * derivatives[a] + params.b * derivatives[c] * values[a]) * weight;
+                    }
+                }
+            }
+        }
+
+        for (boundary_condition, (node, normal)) in params.boundary_conditions.iter().zip([(0, -1.0), (mesh.len() - 1, 1.0)]) {
+            match boundary_condition {
+                BoundaryCondition::Dirichlet(value) => {
+                    stiffness_matrix.row_mut(node).fill(0.0);
+                    stiffness_matrix[[node, node]] = 1.0;
+                    b_vector[node] = *value;
+                },
+                BoundaryCondition::Neumann(flux) => {
+                    b_vector[node] += params.mu * flux * normal;
+                },
+                BoundaryCondition::Periodic => {
+                    return Err(Error::BoundaryError(
+                        "Periodic conditions are not supported by mixed-order solver".to_string()
+                    ))
+                }
+            }
+        }
+
+        Ok((stiffness_matrix, b_vector))
+    }
+
+    /// # General Information
+    ///
+    /// Evaluates a solution returned by `solve` on any point of the mesh, adding the bubble of quadratic elements to the linear interpolation of nodes.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver.
+    /// * `solution` - Coefficients of every degree of freedom, as returned by `solve`.
+    /// * `x` - Point to evaluate. Should be inside mesh.
+    ///
+    pub fn evaluate(&self, solution: &[Scalar], x: Scalar) -> Result<Scalar, Error> {
+        if solution.len() != self.b_vector.len() {
+            return Err(Error::WrongDims);
+        }
+
+        let element = self
+            .mesh
+            .windows(2)
+            .position(|nodes| nodes[0] <= x && x <= nodes[1])
+            .ok_or_else(|| Error::custom(format!("Point {} is outside of mesh", x)))?;
+
+        let xi = IntervalMap::new(self.mesh[element], self.mesh[element + 1]).to_reference(x);
+        let (values, _) = Self::shape_functions(xi);
+        let bubble = self.bubbles[element].map_or(0.0, |dof| solution[dof] * values[2]);
+
+        Ok(solution[element] * values[0] + solution[element + 1] * values[1] + bubble)
+    }
+}
+
+impl DiffEquationSolver for MixedOrderSolver1D {
+    fn assembly_time(&self) -> Duration {
+        self.assembly_time
+    }
+
+    /// # Specific implementation
+    ///
+    /// One value per node followed by one per quadratic element.
+    ///
+    fn dof_count(&self) -> usize {
+        self.b_vector.len()
+    }
+
+    /// # Specific implementation
+    ///
+    /// Bubble functions couple every quadratic element with both of its nodes, so the system is solved by gaussian elimination.
+    ///
+    fn solve(&mut self, _time_step: Scalar) -> Result<Vec<Scalar>, Error> {
+        matrix_solver::solve_by_gaussian_elimination(&self.stiffness_matrix, &self.b_vector)
+    }
+
+    /// # Specific implementation
+    ///
+    /// Problem does not depend on time, so a single solution is already the steady state.
+    ///
+    fn solve_to_steady_state(&mut self, time_step: Scalar, _tol: Scalar, _max_steps: usize) -> Result<Vec<Scalar>, Error> {
+        self.solve(time_step)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::MixedOrderSolver1D;
+    use crate::solvers::{diffusion_solver::DiffussionParams, solver_trait::DiffEquationSolver};
+
+    #[test]
+    fn linear_and_quadratic_elements() {
+        // -u_xx = 2 with u(0) = u(1) = 0 has solution u = x(1 - x)
+        let params = DiffussionParams::time_independent().b(0.0).mu(1.0).boundary_conditions(0.0, 0.0).build();
+        let mut solver = MixedOrderSolver1D::new(&params, vec![0_f64, 0.5, 1_f64], &[1, 2], |_| 2_f64, 10).unwrap();
+
+        // Three nodes and one bubble
+        assert!(solver.dof_count() == 4);
+
+        let solution = solver.solve(0_f64).unwrap();
+        assert!(solution.len() == 4);
+        assert!(solution[0].abs() < 1e-10 && solution[2].abs() < 1e-10);
+        assert!((solution[1] - 0.25).abs() < 1e-10);
+
+        // Quadratic element holds the exact parabola, linear element only interpolates nodes
+        assert!((solver.evaluate(&solution, 0.75).unwrap() - 0.1875).abs() < 1e-10);
+        assert!((solver.evaluate(&solution, 0.25).unwrap() - 0.125).abs() < 1e-10);
+        assert!(solver.evaluate(&solution, 1.5).is_err());
+    }
+}
@@ -1,16 +1,85 @@
 // Module declarations
+pub mod mixed_order;
+pub mod poisson;
 pub mod time_dependent;
 pub mod time_independent;
 
 // Internal dependencies + re-exports
-pub use time_dependent::{DiffussionParamsTimeDependent, DiffussionSolverTimeDependent};
+pub use mixed_order::MixedOrderSolver1D;
+pub use poisson::{PoissonParams1D, PoissonSolver1D};
+pub use time_dependent::{BoundaryFunction, DiffussionParamsTimeDependent, DiffussionSolverTimeDependent};
 pub use time_independent::{DiffussionParamsTimeIndependent, DiffussionSolverTimeIndependent};
 
+// Internal dependencies
+use crate::solvers::Scalar;
+use crate::solvers::basis::functions::{Differentiable1D, Function1D};
+use crate::solvers::fem::basis::single_variable::linear_basis::LinearBasis;
+use crate::Error;
+
+// External dependencies
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fs::File, io::BufReader, path::Path};
+
+
+/// # General Information
+/// 
+/// Boundary condition at one end of a 1D mesh. Diffussion params hold one per end: `[left, right]`.
+/// 
+/// # Arms
+/// 
+/// * `Dirichlet` - Fixed value of solution at the end
+/// * `Neumann` - Fixed value of derivative of solution (flux) at the end. Only supported by time-independent solver
+/// * `Periodic` - Mesh is treated as a ring: u(left) = u(right) and fluxes at both ends match. First and last node become a single degree of freedom.
+///   Has to be set on both ends and is only supported by time-dependent solver
+/// 
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BoundaryCondition {
+    Dirichlet(Scalar),
+    Neumann(Scalar),
+    Periodic,
+}
+
+impl Default for BoundaryCondition {
+    fn default() -> Self {
+        BoundaryCondition::Dirichlet(0.0)
+    }
+}
+
+/// # General Information
+///
+/// Derivative of a solution (flux) on every element. For linear elements it is constant inside an element, so it is obtained evaluating
+/// the derivative of the two basis functions supported on it at the element's midpoint.
+///
+/// # Parameters
+///
+/// * `mesh` - Vector of f64 representing a line
+/// * `solution` - Value of solution at every node of mesh
+///
+/// # Returns
+///
+/// A vector with one derivative per element (one less than nodes).
+///
+pub(crate) fn element_gradients(mesh: &Vec<Scalar>, solution: &[Scalar]) -> Result<Vec<Scalar>, Error> {
+    if solution.len() != mesh.len() {
+        return Err(Error::WrongDims);
+    }
+
+    let basis = LinearBasis::new(mesh)?;
+
+    (0..mesh.len() - 1)
+        .map(|element| -> Result<Scalar, Error> {
+            let midpoint = (mesh[element] + mesh[element + 1]) / 2.0;
+            Ok(solution[element] * basis.basis[element].differentiate()?.evaluate(midpoint)
+                + solution[element + 1] * basis.basis[element + 1].differentiate()?.evaluate(midpoint))
+        })
+        .collect()
+}
 
 /// Struct to initialize builders params for either time-dependent or time-independent diffussion solvers.
 pub struct DiffussionParams();
 
-#[derive(Default)]
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 /// # General Information
 /// 
 /// Builder for diffussion params in 1D with time-dependance
@@ -19,17 +88,21 @@ pub struct DiffussionParams();
 /// 
 /// * `mu` - Movement term
 /// * `b` - Velocity term
-/// * `boundary_conditions` - Dirichlet conditions
+/// * `boundary_conditions` - Dirichlet or periodic conditions
 /// * `initial_conditions` - Internal initial conditions
+/// * `boundary_functions` - Dirichlet values on each end as functions of time. Can not be read from a file
 /// 
 pub struct DiffussionParamsTimeDependentBuilder {
-    mu: Option<f64>,
-    b: Option<f64>,
-    boundary_conditions: Option<[f64;2]>,
-    initial_conditions: Option<Vec<f64>>,
+    mu: Option<Scalar>,
+    b: Option<Scalar>,
+    boundary_conditions: Option<[BoundaryCondition;2]>,
+    initial_conditions: Option<Vec<Scalar>>,
+    #[serde(skip)]
+    boundary_functions: Option<[BoundaryFunction;2]>,
 }
 
-#[derive(Default)]
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
 /// # General Information
 /// 
 /// Builder for diffussion params in 1D
@@ -38,12 +111,12 @@ pub struct DiffussionParamsTimeDependentBuilder {
 /// 
 /// * `mu` - Movement term
 /// * `b` - Velocity term
-/// * `boundary_conditions` - Dirichlet conditions
+/// * `boundary_conditions` - Dirichlet or Neumann conditions on each end
 /// 
 pub struct DiffussionParamsTimeIndependentBuilder {
-    mu: Option<f64>,
-    b: Option<f64>,
-    boundary_conditions: Option<[f64;2]>,
+    mu: Option<Scalar>,
+    b: Option<Scalar>,
+    boundary_conditions: Option<[BoundaryCondition;2]>,
 }
 
 
@@ -55,39 +128,82 @@ impl DiffussionParams {
     pub fn time_independent() -> DiffussionParamsTimeIndependentBuilder {
         DiffussionParamsTimeIndependentBuilder::default()
     }
+
+    /// # General Information
+    /// 
+    /// Reads params (either `DiffussionParamsTimeIndependent` or `DiffussionParamsTimeDependent`) from a json file with the same fields as the builders:
+    /// `mu`, `b`, `boundary_conditions` (e.g. `[{"Dirichlet": 0.0}, {"Neumann": 1.0}]`) and, for time-dependent params, `initial_conditions`.
+    /// Missing fields give the same message builders panic with.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `path` - Location of json file
+    /// 
+    pub fn from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T, Error> {
+        let path = path.as_ref();
+
+        if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+            return Err(Error::ExtensionNotAllowed(path.display().to_string(), "diffussion params".to_string()));
+        }
+
+        let reader = BufReader::new(File::open(path)?);
+        Ok(serde_json::from_reader(reader)?)
+    }
 }
 
 impl DiffussionParamsTimeDependentBuilder {
     /// Set b
-    pub fn b(self, b: f64) -> Self {
+    pub fn b(self, b: Scalar) -> Self {
         Self {
             b: Some(b),
             ..self
         }
     }
     /// Set mu
-    pub fn mu(self, mu: f64) -> Self {
+    pub fn mu(self, mu: Scalar) -> Self {
         Self {
             mu: Some(mu),
             ..self
         }
     }
     /// Set boundary conditions
-    pub fn boundary_conditions(self, left: f64, right: f64) -> Self {
+    pub fn boundary_conditions(self, left: Scalar, right: Scalar) -> Self {
         Self {
-            boundary_conditions: Some([left, right]),
+            boundary_conditions: Some([BoundaryCondition::Dirichlet(left), BoundaryCondition::Dirichlet(right)]),
+            boundary_functions: None,
+            ..self
+        }
+    }
+    /// Set Dirichlet conditions that change with time. Both functions receive simulated time, and are evaluated at zero to obtain initial boundary values
+    pub fn time_dependent_boundary_conditions<L, R>(self, left: L, right: R) -> Self
+    where
+        L: Fn(Scalar) -> Scalar + Send + Sync + 'static,
+        R: Fn(Scalar) -> Scalar + Send + Sync + 'static,
+    {
+        let boundary_functions = [BoundaryFunction::new(left), BoundaryFunction::new(right)];
+        Self {
+            boundary_conditions: Some(boundary_functions.clone().map(|function| BoundaryCondition::Dirichlet(function.evaluate(0.0)))),
+            boundary_functions: Some(boundary_functions),
+            ..self
+        }
+    }
+    /// Set periodic boundary conditions. Initial conditions should then be given for every node except the last one, which is the same as the first
+    pub fn periodic_boundary_conditions(self) -> Self {
+        Self {
+            boundary_conditions: Some([BoundaryCondition::Periodic;2]),
+            boundary_functions: None,
             ..self
         }
     }
     /// Set initial conditions - basic
-    pub fn initial_conditions<A: IntoIterator<Item = f64>>(self, initial_conditions: A) -> Self {
+    pub fn initial_conditions<A: IntoIterator<Item = Scalar>>(self, initial_conditions: A) -> Self {
         Self {
             initial_conditions: Some(initial_conditions.into_iter().collect()),
             ..self
         }
     }
     /// Use function 
-    pub fn initial_conditions_from_function<A: Fn(f64) -> f64, B: AsRef<str>>(_func: A, _mesh: B) -> Self {
+    pub fn initial_conditions_from_function<A: Fn(Scalar) -> Scalar, B: AsRef<str>>(_func: A, _mesh: B) -> Self {
 
         
 
@@ -95,57 +211,73 @@ impl DiffussionParamsTimeDependentBuilder {
     }
     /// Build DiffussionParams
     pub fn build(self) -> DiffussionParamsTimeDependent {
+        match self.try_build() {
+            Ok(params) => params,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Build DiffussionParams, returning an error instead of panicking when a term is missing
+    pub(crate) fn try_build(self) -> Result<DiffussionParamsTimeDependent, Error> {
         
         let mu = if let Some(mu) = self.mu {
             mu
         } else {
-            panic!("Params lack 'mu' term!");
+            return Err(Error::Config("Params lack 'mu' term!".to_string()));
         };
 
         let b = if let Some(b) = self.b {
             b
         } else {
-            panic!("Params lack 'b' term!");
+            return Err(Error::Config("Params lack 'b' term!".to_string()));
         };
 
         let boundary_conditions = if let Some(boundary) = self.boundary_conditions {
             boundary
         } else {
-            panic!("Params lack boundary conditions!");
+            return Err(Error::Config("Params lack boundary conditions!".to_string()));
         };
 
         let initial_conditions = if let Some(initial) = self.initial_conditions {
             initial
         } else {
-            panic!("Params lack initial conditions!");
+            return Err(Error::Config("Params lack initial conditions!".to_string()));
         };
         
-        DiffussionParamsTimeDependent {
+        Ok(DiffussionParamsTimeDependent {
             mu,
             boundary_conditions,
             b,
-            initial_conditions
-        }
+            initial_conditions,
+            boundary_functions: self.boundary_functions,
+        })
     }
 }
 
 impl DiffussionParamsTimeIndependentBuilder {
     /// Set mu
-    pub fn mu(self, mu: f64) -> Self {
+    pub fn mu(self, mu: Scalar) -> Self {
         Self {
             mu: Some(mu),
             ..self
         }
     }
     /// Set b
-    pub fn b(self, b: f64) -> Self {
+    pub fn b(self, b: Scalar) -> Self {
         Self {
             b: Some(b),
             ..self
         }
     }
     /// Set boundary cconditions
-    pub fn boundary_conditions(self, left: f64, right: f64) -> Self {
+    pub fn boundary_conditions(self, left: Scalar, right: Scalar) -> Self {
+        Self {
+            boundary_conditions: Some([BoundaryCondition::Dirichlet(left), BoundaryCondition::Dirichlet(right)]),
+            ..self
+        }
+    }
+    /// Set a (possibly different) kind of boundary condition on each end. E.g. a fixed value on the left and a fixed flux on the right
+    pub fn mixed_boundary_conditions(self, left: BoundaryCondition, right: BoundaryCondition) -> Self {
         Self {
             boundary_conditions: Some([left, right]),
             ..self
@@ -153,29 +285,103 @@ impl DiffussionParamsTimeIndependentBuilder {
     }
     /// Build DiffussionParams
     pub fn build(self) -> DiffussionParamsTimeIndependent {
+        match self.try_build() {
+            Ok(params) => params,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Build DiffussionParams, returning an error instead of panicking when a term is missing
+    pub(crate) fn try_build(self) -> Result<DiffussionParamsTimeIndependent, Error> {
         
         let mu = if let Some(mu) = self.mu {
             mu
         } else {
-            panic!("Params lack 'mu' term!");
+            return Err(Error::Config("Params lack 'mu' term!".to_string()));
         };
 
         let b = if let Some(b) = self.b {
             b
         } else {
-            panic!("Params lack 'b' term!");
+            return Err(Error::Config("Params lack 'b' term!".to_string()));
         };
 
         let boundary_conditions = if let Some(boundary) = self.boundary_conditions {
             boundary
         } else {
-            panic!("Params lack boundary conditions!");
+            return Err(Error::Config("Params lack boundary conditions!".to_string()));
         };
         
-        DiffussionParamsTimeIndependent {
+        Ok(DiffussionParamsTimeIndependent {
             mu,
             boundary_conditions,
             b,
-        }
+        })
+    }
+}
+
+impl TryFrom<DiffussionParamsTimeDependentBuilder> for DiffussionParamsTimeDependent {
+    type Error = Error;
+
+    fn try_from(builder: DiffussionParamsTimeDependentBuilder) -> Result<Self, Error> {
+        builder.try_build()
+    }
+}
+
+impl TryFrom<DiffussionParamsTimeIndependentBuilder> for DiffussionParamsTimeIndependent {
+    type Error = Error;
+
+    fn try_from(builder: DiffussionParamsTimeIndependentBuilder) -> Result<Self, Error> {
+        builder.try_build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BoundaryCondition, DiffussionParams, DiffussionParamsTimeDependent, DiffussionParamsTimeIndependent};
+    use crate::Error;
+
+    #[test]
+    fn params_round_trip_through_file() {
+        let directory = std::env::temp_dir().join("dzahui_params_test");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let independent = DiffussionParams::time_independent()
+            .mu(2.0)
+            .b(0.5)
+            .mixed_boundary_conditions(BoundaryCondition::Dirichlet(1.0), BoundaryCondition::Neumann(-3.0))
+            .build();
+        let dependent = DiffussionParams::time_dependent()
+            .mu(1.0)
+            .b(0.0)
+            .periodic_boundary_conditions()
+            .initial_conditions(vec![0.0, 0.5, 1.0])
+            .build();
+
+        std::fs::write(directory.join("independent.json"), serde_json::to_string(&independent).unwrap()).unwrap();
+        std::fs::write(directory.join("dependent.json"), serde_json::to_string(&dependent).unwrap()).unwrap();
+        std::fs::write(directory.join("missing.json"), r#"{"mu": 1.0, "boundary_conditions": [{"Dirichlet": 0.0}, {"Dirichlet": 1.0}]}"#).unwrap();
+
+        let read_independent: Result<DiffussionParamsTimeIndependent, Error> = DiffussionParams::from_file(directory.join("independent.json"));
+        let read_dependent: Result<DiffussionParamsTimeDependent, Error> = DiffussionParams::from_file(directory.join("dependent.json"));
+        let missing: Result<DiffussionParamsTimeIndependent, Error> = DiffussionParams::from_file(directory.join("missing.json"));
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        assert!(read_independent.unwrap() == independent);
+        assert!(read_dependent.unwrap() == dependent);
+        assert!(matches!(missing, Err(Error::Config(ref message)) if message.contains("Params lack 'b' term!")));
+    }
+
+    #[test]
+    fn time_dependent_params_move_between_threads() {
+        let params = DiffussionParams::time_dependent()
+            .mu(1.0)
+            .b(0.0)
+            .time_dependent_boundary_conditions(|t| t, |_| 0.0)
+            .initial_conditions(vec![0.0, 0.0, 0.0])
+            .build();
+
+        let left = std::thread::spawn(move || params.boundary_functions.unwrap()[0].evaluate(2.0)).join().unwrap();
+        assert!(left == 2.0);
     }
 }
@@ -0,0 +1,185 @@
+// Internal dependencies
+use crate::solvers::Scalar;
+use crate::solvers::fem::basis::single_variable::linear_basis::LinearBasis;
+use crate::solvers::basis::functions::Function1D;
+use crate::solvers::{quadrature::{gauss_legendre, interval_map::IntervalMap, DEFAULT_INTEGRATION_ITERATION}, solver_trait::DiffEquationSolver};
+use crate::Error;
+use super::{BoundaryCondition, DiffussionParamsTimeIndependent, DiffussionSolverTimeIndependent};
+
+// External dependencies
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+/// # General Information
+///
+/// Parameters needed for solving Poisson equation in 1d.
+///
+/// # Parameters
+///
+/// * `force_function` - Right-hand side "f" of the equation
+/// * `boundary_conditions` - Dirichlet values on left and right ends
+///
+pub struct PoissonParams1D {
+    pub force_function: Box<dyn Fn(Scalar) -> Scalar>,
+    pub boundary_conditions: [Scalar; 2],
+}
+
+impl PoissonParams1D {
+    /// Creates new params from a force function and Dirichlet values on both ends
+    pub fn new(force_function: Box<dyn Fn(Scalar) -> Scalar>, boundary_conditions: [Scalar; 2]) -> Self {
+        Self {
+            force_function,
+            boundary_conditions,
+        }
+    }
+}
+
+impl Debug for PoissonParams1D {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ff = &self.force_function;
+        let eval = ff(0.0);
+        let content = format!("{{ boundary_conditions: {:?},\n force_function: f(0) -> {} }}", self.boundary_conditions, eval);
+        write!(f, "{}", content)
+    }
+}
+
+#[derive(Debug)]
+/// # General Information
+///
+/// A Poisson solver abstracts the equation: "-u_xx = f" with Dirichlet conditions on both ends. It is a diffusion equation with μ = 1 and b = 0
+/// whose vector b also holds the integral of the force against every basis function.
+///
+/// # Fields
+///
+/// * `diffussion_solver` - Time-independent diffusion solver with μ = 1 and b = 0 holding the assembled system.
+///
+pub struct PoissonSolver1D {
+    pub(crate) diffussion_solver: DiffussionSolverTimeIndependent,
+}
+
+impl PoissonSolver1D {
+    /// # General Information
+    ///
+    /// Creates new instance. Stiffness matrix is assembled by diffusion solver, then the force is integrated against every interior basis function.
+    ///
+    /// # Parameters
+    ///
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `force_function` - Right-hand side "f" of the equation.
+    /// * `boundary_conditions` - Dirichlet values on left and right ends.
+    ///
+    pub fn new<F: Fn(Scalar) -> Scalar>(mesh: Vec<Scalar>, force_function: F, boundary_conditions: [Scalar; 2]) -> Result<Self, Error> {
+        Self::with_gauss_step(mesh, force_function, boundary_conditions, DEFAULT_INTEGRATION_ITERATION)
+    }
+
+    /// # General Information
+    ///
+    /// Same as `new`, with the amount of quadrature nodes chosen on a window (see `with_integration_iteration`).
+    ///
+    /// # Parameters
+    ///
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `force_function` - Right-hand side "f" of the equation.
+    /// * `boundary_conditions` - Dirichlet values on left and right ends.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    pub(crate) fn with_gauss_step<F: Fn(Scalar) -> Scalar>(mesh: Vec<Scalar>, force_function: F, boundary_conditions: [Scalar; 2], gauss_step: usize) -> Result<Self, Error> {
+
+        let params = DiffussionParamsTimeIndependent {
+            mu: 1.0,
+            b: 0.0,
+            boundary_conditions: boundary_conditions.map(BoundaryCondition::Dirichlet),
+        };
+
+        let assembly_start = Instant::now();
+        let mut diffussion_solver = DiffussionSolverTimeIndependent::new(&params, mesh, gauss_step)?;
+        let load_vector = Self::load_vector(&diffussion_solver.mesh, &force_function, gauss_step)?;
+
+        // Boundary entries keep Dirichlet values
+        let nodes = load_vector.len();
+        for (i, load) in load_vector.iter().enumerate().take(nodes - 1).skip(1) {
+            diffussion_solver.b_vector[i] += load;
+        }
+        diffussion_solver.assembly_time = assembly_start.elapsed();
+
+        Ok(Self { diffussion_solver })
+    }
+
+    /// # General Information
+    ///
+    /// Integral of force times every basis function via Gauss-Legendre. Every basis function is integrated on each of its two elements
+    /// separately, since it's only piecewise smooth.
+    ///
+    /// # Parameters
+    ///
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `force_function` - Right-hand side "f" of the equation.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    fn load_vector<F: Fn(Scalar) -> Scalar>(mesh: &Vec<Scalar>, force_function: &F, gauss_step: usize) -> Result<Vec<Scalar>, Error> {
+
+        let basis = LinearBasis::new(mesh)?;
+        let mut load_vector = vec![0.0; mesh.len()];
+
+        for element in 0..(mesh.len() - 1) {
+
+            let transform_function = IntervalMap::new(mesh[element], mesh[element + 1]);
+
+            for j in 1..=gauss_step {
+                // Obtaining arccos(node) and weight
+                let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
+                let translated_point = transform_function.from_reference(theta.cos());
+                let force = force_function(translated_point) * transform_function.jacobian() * w;
+
+                load_vector[element] += force * basis.basis[element].evaluate(translated_point);
+                load_vector[element + 1] += force * basis.basis[element + 1].evaluate(translated_point);
+            }
+        }
+
+        Ok(load_vector)
+    }
+
+    /// Derivative of a solution on every element.
+    pub fn gradient(&self, solution: &[Scalar]) -> Result<Vec<Scalar>, Error> {
+        self.diffussion_solver.gradient(solution)
+    }
+}
+
+impl DiffEquationSolver for PoissonSolver1D {
+    fn assembly_time(&self) -> Duration {
+        self.diffussion_solver.assembly_time
+    }
+
+    fn dof_count(&self) -> usize {
+        self.diffussion_solver.dof_count()
+    }
+
+    fn solve(&mut self, time_step: Scalar) -> Result<Vec<Scalar>, Error> {
+        self.diffussion_solver.solve(time_step)
+    }
+
+    /// # Specific implementation
+    ///
+    /// Problem does not depend on time, so a single solution is already the steady state.
+    ///
+    fn solve_to_steady_state(&mut self, time_step: Scalar, _tol: Scalar, _max_steps: usize) -> Result<Vec<Scalar>, Error> {
+        self.solve(time_step)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PoissonSolver1D;
+    use crate::solvers::solver_trait::DiffEquationSolver;
+
+    #[test]
+    fn constant_force_gives_parabola() {
+        let mesh: Vec<f64> = (0..=10).map(|i| i as f64 / 10.0).collect();
+        let mut solver = PoissonSolver1D::new(mesh.clone(), |_| 1_f64, [0_f64, 0_f64]).unwrap();
+        let solution = solver.solve(0_f64).unwrap();
+
+        assert!(solution.len() == mesh.len());
+        assert!(solution[0] == 0_f64 && solution[10] == 0_f64);
+        assert!(mesh.iter().zip(solution.iter()).all(|(x, u)| (u - x * (1_f64 - x) / 2_f64).abs() < 1e-10));
+    }
+}
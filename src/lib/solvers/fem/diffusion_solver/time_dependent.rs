@@ -1,15 +1,56 @@
 // internal dependencies
-use crate::solvers::fem::basis::single_variable::{
-    linear_basis::LinearBasis, polynomials_1d::FirstDegreePolynomial,
-};
+use crate::solvers::Scalar;
+use crate::solvers::fem::basis::single_variable::linear_basis::LinearBasis;
 use crate::solvers::basis::functions::{Differentiable1D, Function1D};
-use crate::solvers::{solver_trait::DiffEquationSolver, matrix_solver, utils, quadrature::gauss_legendre};
+use crate::solvers::{solver_trait::{DiffEquationSolver, SolverCheckpoint}, matrix_solver, utils, quadrature::{gauss_legendre, interval_map::IntervalMap}};
 use crate::Error;
+use super::{BoundaryCondition, DiffussionParamsTimeDependentBuilder};
 
 // External dependencies
+use serde::{Deserialize, Serialize};
 use ndarray::{Array1, Array2};
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-#[derive(Default,Debug)]
+/// # General Information
+///
+/// Dirichlet value at one end of a mesh given as a function of simulated time. Cloning it shares the same function, which may be used from any thread.
+///
+/// # Fields
+///
+/// * `0` - Function receiving simulated time
+///
+#[derive(Clone)]
+pub struct BoundaryFunction(Arc<dyn Fn(Scalar) -> Scalar + Send + Sync>);
+
+impl BoundaryFunction {
+    /// Wraps a function of time.
+    pub fn new<F: Fn(Scalar) -> Scalar + Send + Sync + 'static>(function: F) -> Self {
+        Self(Arc::new(function))
+    }
+
+    /// Value of boundary at a given time.
+    pub fn evaluate(&self, time: Scalar) -> Scalar {
+        (self.0)(time)
+    }
+}
+
+impl Debug for BoundaryFunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "f(0) -> {}", self.evaluate(0.0))
+    }
+}
+
+impl PartialEq for BoundaryFunction {
+    /// Functions can not be compared, so only copies of the same function are equal.
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[derive(Default,Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "DiffussionParamsTimeDependentBuilder")]
 ///
 /// # General Information
 /// 
@@ -19,14 +60,18 @@ use ndarray::{Array1, Array2};
 /// 
 /// * `mu` - Movement term
 /// * `b` - Velocity term
-/// * `boundary_conditions` - Dirichlet conditions
-/// * `initial_conditions` - Internal initial conditions
+/// * `boundary_conditions` - Dirichlet or periodic conditions on each end
+/// * `initial_conditions` - Internal initial conditions. With periodic conditions the first node is included too
+/// * `boundary_functions` - Dirichlet values on each end as functions of time. When given, they replace values of `boundary_conditions` after every step.
+///   Not written to files
 /// 
 pub struct DiffussionParamsTimeDependent {
-    pub mu: f64,
-    pub b: f64,
-    pub boundary_conditions: [f64;2],
-    pub(crate) initial_conditions: Vec<f64>
+    pub mu: Scalar,
+    pub b: Scalar,
+    pub boundary_conditions: [BoundaryCondition;2],
+    pub(crate) initial_conditions: Vec<Scalar>,
+    #[serde(skip)]
+    pub(crate) boundary_functions: Option<[BoundaryFunction;2]>,
 }
 
 #[derive(Debug)]
@@ -37,7 +82,7 @@ pub struct DiffussionParamsTimeDependent {
 ///
 /// # Fields
 ///
-/// * `boundary_conditions` - Boundary conditions (Only dirichlet is supported for now, Neumann is being worked on)
+/// * `boundary_conditions` - Boundary conditions (Dirichlet or periodic, Neumann is being worked on)
 /// * `stiffness_matrix` - Matrix of elements that is multiplied by time
 /// * `initial_conditions` - Every internal point needs an initial condition to advance the solution in time
 /// * `mass_matrix` - A matrix that pertains only to elements that are not multiplied by time
@@ -45,40 +90,69 @@ pub struct DiffussionParamsTimeDependent {
 /// * `state` - The state of every point at time t
 /// * `mu` - First ot two needed constants
 /// * `b` - Second of two needed constants
+/// * `assembly_time` - Time spent creating mass and stiffness matrices
+/// * `mesh` - Nodes of 1D mesh. Needed to obtain gradient of a solution
+/// * `boundary_functions` - Dirichlet values on each end as functions of time. Constant values of `boundary_conditions` are used when None
+/// * `time` - Simulated time, that is, sum of time steps given to `solve` since creation or last reset
 ///
 pub struct DiffussionSolverTimeDependent {
-    pub boundary_conditions: [f64; 2],
-    pub(crate) stiffness_matrix: Array2<f64>,
-    pub initial_conditions: Vec<f64>,
-    pub(crate) mass_matrix: Array2<f64>,
+    pub boundary_conditions: [BoundaryCondition;2],
+    pub(crate) stiffness_matrix: Array2<Scalar>,
+    pub initial_conditions: Vec<Scalar>,
+    pub(crate) mass_matrix: Array2<Scalar>,
     pub integration_step: usize,
-    pub(crate) state: Array1<f64>,
-    pub mu: f64,
-    pub b: f64,
+    pub(crate) state: Array1<Scalar>,
+    pub mu: Scalar,
+    pub b: Scalar,
+    pub(crate) assembly_time: Duration,
+    pub(crate) mesh: Vec<Scalar>,
+    pub(crate) boundary_functions: Option<[BoundaryFunction;2]>,
+    pub(crate) time: Scalar,
 }
 
 impl DiffussionSolverTimeDependent {
     /// Creates new instance checking initial conditions are the size they should be.
-    pub fn new(params: &DiffussionParamsTimeDependent, mesh: Vec<f64>, integration_step: usize) -> Result<Self,Error> {
+    pub fn new(params: &DiffussionParamsTimeDependent, mesh: Vec<Scalar>, integration_step: usize) -> Result<Self,Error> {
         
         let initial_conditions = params.initial_conditions.clone();
-        
-        if initial_conditions.len() != mesh.len() - 2 {
-            return Err(Error::WrongDims)
-        }
+        let assembly_start = Instant::now();
 
-        // obtain general initial state
-        let mut state = vec![0_f64;mesh.len()];
-        state[0] = params.boundary_conditions[0];
-        state[mesh.len() - 1] = params.boundary_conditions[1]; 
-        for i in 1..(mesh.len() - 1) {
-            state[i] = initial_conditions[i-1];
-        }
+        // obtain general initial state and matrices
+        let (state, mass_matrix, stiffness_matrix) = match params.boundary_conditions {
+            [BoundaryCondition::Dirichlet(_), BoundaryCondition::Dirichlet(_)] => {
 
-        let state = Array1::from_vec(state);
+                if initial_conditions.len() != mesh.len() - 2 {
+                    return Err(Error::WrongDims)
+                }
+
+                let state = Self::initial_state(&params.boundary_conditions, &initial_conditions);
+
+                let (mass_matrix, stiffness_matrix) = Self::gauss_legendre_integration(
+                    params.mu, params.b, &mesh, integration_step)?;
+
+                (state, mass_matrix, stiffness_matrix)
+            },
+            [BoundaryCondition::Periodic, BoundaryCondition::Periodic] => {
 
-        let (mass_matrix, stiffness_matrix) = Self::gauss_legendre_integration(
-                params.mu, params.b, &mesh, integration_step)?;
+                // First and last node are the same, and a cyclic system needs at least 3 distinct nodes
+                if initial_conditions.len() != mesh.len() - 1 || mesh.len() < 4 {
+                    return Err(Error::WrongDims)
+                }
+
+                let state = Self::initial_state(&params.boundary_conditions, &initial_conditions);
+
+                let (mass_matrix, stiffness_matrix) = Self::periodic_integration(
+                    params.mu, params.b, &mesh, integration_step)?;
+
+                (state, mass_matrix, stiffness_matrix)
+            },
+            _ => return Err(Error::BoundaryError(
+                "Time-dependent solver needs Dirichlet conditions on both ends or periodic conditions on both ends".to_string()
+            ))
+        };
+
+        let state = Array1::from_vec(state);
+        let assembly_time = assembly_start.elapsed();
 
         // obtain matrices
 
@@ -91,9 +165,58 @@ impl DiffussionSolverTimeDependent {
             state,
             mu: params.mu,
             b: params.b,
+            assembly_time,
+            mesh,
+            boundary_functions: params.boundary_functions.clone(),
+            time: 0.0,
         })
     }
 
+    /// # General Information
+    ///
+    /// State of every node at time zero. Dirichlet values are placed on both ends around initial conditions, while periodic meshes repeat
+    /// their first value on the last node. Initial conditions should already have the length checked by `new`.
+    ///
+    /// # Parameters
+    ///
+    /// * `boundary_conditions` - Conditions on left and right ends.
+    /// * `initial_conditions` - Value of solution on every degree of freedom at time zero.
+    ///
+    fn initial_state(boundary_conditions: &[BoundaryCondition; 2], initial_conditions: &[Scalar]) -> Vec<Scalar> {
+        match boundary_conditions {
+            [BoundaryCondition::Dirichlet(left), BoundaryCondition::Dirichlet(right)] => {
+                let mut state = vec![*left];
+                state.extend_from_slice(initial_conditions);
+                state.push(*right);
+                state
+            },
+            _ => {
+                let mut state = initial_conditions.to_vec();
+                state.push(initial_conditions[0]);
+                state
+            }
+        }
+    }
+
+    /// Brings state back to initial conditions and simulated time back to zero, so that solving starts over without assembling matrices again.
+    pub fn reset(&mut self) {
+        self.state = Array1::from_vec(Self::initial_state(&self.boundary_conditions, &self.initial_conditions));
+        self.time = 0.0;
+    }
+
+    /// # General Information
+    ///
+    /// Derivative of a solution (flux) on every element, computed from nodal values and element lengths through the basis derivatives.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver holding its mesh.
+    /// * `solution` - Value of solution at every node (normally obtained with `solve`).
+    ///
+    pub fn gradient(&self, solution: &[Scalar]) -> Result<Vec<Scalar>, Error> {
+        super::element_gradients(&self.mesh, solution)
+    }
+
     /// # General Information
     /// 
     /// Compĺete integration of linear basis to obtain mass matrix and stiffness matrix.
@@ -107,16 +230,16 @@ impl DiffussionSolverTimeDependent {
     /// * `mesh` - Vector of f64 representing a mesh
     /// * `gauss_step` - Amount of nodes to compute for integration.
     /// 
-    fn gauss_legendre_integration(mu: f64, b: f64, mesh: &Vec<f64>, gauss_step: usize) -> Result<(Array2<f64>,Array2<f64>),Error> {
+    fn gauss_legendre_integration(mu: Scalar, b: Scalar, mesh: &Vec<Scalar>, gauss_step: usize) -> Result<(Array2<Scalar>,Array2<Scalar>),Error> {
         
         // First generate the basis
         let linear_basis = LinearBasis::new(mesh)?;
         let basis_len = linear_basis.basis.len();
 
         // initialize matrix mass_matrix (boundaries included)
-        let mut mass_matrix = ndarray::Array::from_elem((basis_len, basis_len), 0_f64);
+        let mut mass_matrix = ndarray::Array::from_elem((basis_len, basis_len), 0.0);
         // initialize matrix stiffness_matrix (boundaries included)
-        let mut stiffness_matrix = ndarray::Array::from_elem((basis_len, basis_len), 0_f64);
+        let mut stiffness_matrix = ndarray::Array::from_elem((basis_len, basis_len), 0.0);
 
         for i in 1..(basis_len - 1) {
             // Now we calculate every integral in the equation.
@@ -129,41 +252,28 @@ impl DiffussionSolverTimeDependent {
             let derivative_phi_prev = linear_basis.basis[i-1].differentiate()?;
 
             // Transform intervals from -1,1 to [ai,bi]
-            let transform_function_prev = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i-1],
-                mesh[i],
-            );
-            let transform_function_next = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i],
-                mesh[i+1],
-            );
-            let transform_function_square =
-                FirstDegreePolynomial::transformation_from_m1_p1(
-                    mesh[i-1],
-                    mesh[i+1],
-                );
+            let transform_function_prev = IntervalMap::new(mesh[i-1], mesh[i]);
+            let transform_function_next = IntervalMap::new(mesh[i], mesh[i+1]);
+            let transform_function_square = IntervalMap::new(mesh[i-1], mesh[i+1]);
     
             // transform functions' derivatives
-            let derivative_t_prev = transform_function_prev.differentiate()?;
-            let derivative_t_next = transform_function_next.differentiate()?;
-            let derivative_t_square = transform_function_square.differentiate()?;
             
             // initialize all integral approximations
             // derivatives integral. Of the form <phi_j',phi_i'>
-            let mut integral_prev_approximation_prime = 0_f64;
-            let mut integral_next_approximation_prime = 0_f64;
-            let mut integral_square_approximation_prime = 0_f64;
+            let mut integral_prev_approximation_prime = 0.0;
+            let mut integral_next_approximation_prime = 0.0;
+            let mut integral_square_approximation_prime = 0.0;
             // half derivative integral. Of the form <phi_j,phi_i'>
-            let mut integral_prev_approximation_half = 0_f64;
-            let mut integral_next_approximation_half = 0_f64;
-            let mut integral_square_approximation_half = 0_f64;
+            let mut integral_prev_approximation_half = 0.0;
+            let mut integral_next_approximation_half = 0.0;
+            let mut integral_square_approximation_half = 0.0;
             // normal integrals. Of the form <phi_j,phi_i>
-            let mut integral_prev_approximation_mass = 0_f64;
-            let mut integral_next_approximation_mass = 0_f64;
-            let mut integral_square_approximation_mass = 0_f64;
+            let mut integral_prev_approximation_mass = 0.0;
+            let mut integral_next_approximation_mass = 0.0;
+            let mut integral_square_approximation_mass = 0.0;
             
             //integrate:
-            for j in 1..gauss_step {
+            for j in 1..=gauss_step {
                 
                 // Obtaining arccos(node) and weight
                 let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
@@ -171,51 +281,51 @@ impl DiffussionSolverTimeDependent {
     
                 // translated from -1,1
                 // x is evaluated inside phi_i function according to change of variable rule
-                let translated_point_prev = transform_function_prev.evaluate(x);
-                let translated_point_next = transform_function_next.evaluate(x);
-                let translated_point_square = transform_function_square.evaluate(x);
+                let translated_point_prev = transform_function_prev.from_reference(x);
+                let translated_point_next = transform_function_next.from_reference(x);
+                let translated_point_square = transform_function_square.from_reference(x);
     
                 // Dot product integrals
                 // dot product <phi_j,phi_(j-1)>
                 integral_prev_approximation_mass += 
                     linear_basis.basis[i].evaluate(translated_point_prev) *
-                    linear_basis.basis[i-1].evaluate(translated_point_prev) * derivative_t_prev.evaluate(x) * w;
+                    linear_basis.basis[i-1].evaluate(translated_point_prev) * transform_function_prev.jacobian() * w;
                 // dot product <phi_j,phi_j>
                 integral_square_approximation_mass +=
-                    linear_basis.basis[i].evaluate(translated_point_square).powf(2_f64) *
-                    derivative_t_square.evaluate(x) * w;
+                    linear_basis.basis[i].evaluate(translated_point_square).powf(2.0) *
+                    transform_function_square.jacobian() * w;
                 // dot product <phi_j,phi_(j+1)>
                 integral_next_approximation_mass +=
                     linear_basis.basis[i].evaluate(translated_point_next) *
-                    linear_basis.basis[i+1].evaluate(translated_point_next) * derivative_t_next.evaluate(x) * w;
+                    linear_basis.basis[i+1].evaluate(translated_point_next) * transform_function_next.jacobian() * w;
                 
                 // Derivative integrals
                 // integral <phi_j',phi_(j-1)'>
                 integral_prev_approximation_prime +=
                 derivative_phi.evaluate(translated_point_prev) *
-                derivative_phi_prev.evaluate(translated_point_prev) * derivative_t_prev.evaluate(x) * w;
+                derivative_phi_prev.evaluate(translated_point_prev) * transform_function_prev.jacobian() * w;
                 // integral <phi_j',phi_j'>
                 integral_square_approximation_prime +=
-                derivative_phi.evaluate(translated_point_square).powf(2_f64) *
-                derivative_t_square.evaluate(x) * w;
+                derivative_phi.evaluate(translated_point_square).powf(2.0) *
+                transform_function_square.jacobian() * w;
                 // integral <phi_j',phi_(j+1)'>
                 integral_next_approximation_prime +=
                 derivative_phi.evaluate(translated_point_next) *
-                derivative_phi_next.evaluate(translated_point_next) * derivative_t_next.evaluate(x) * w;
+                derivative_phi_next.evaluate(translated_point_next) * transform_function_next.jacobian() * w;
                 
                 // Half derivative integrals
                 // integral <phi_j,phi_(j-1)'>
                 integral_prev_approximation_half += 
                 linear_basis.basis[i].evaluate(translated_point_prev) *
-                derivative_phi_prev.evaluate(translated_point_prev) * derivative_t_prev.evaluate(x) * w;
+                derivative_phi_prev.evaluate(translated_point_prev) * transform_function_prev.jacobian() * w;
                 // integral <phi_j,phi_j'>
                 integral_square_approximation_half += 
                 linear_basis.basis[i].evaluate(translated_point_square) *
-                derivative_phi.evaluate(translated_point_square) * derivative_t_square.evaluate(x) * w;
+                derivative_phi.evaluate(translated_point_square) * transform_function_square.jacobian() * w;
                 // integral <phi_j,phi_(j+1)'>
                 integral_next_approximation_half += 
                 linear_basis.basis[i].evaluate(translated_point_next) *
-                derivative_phi_next.evaluate(translated_point_next) * derivative_t_next.evaluate(x) * w;
+                derivative_phi_next.evaluate(translated_point_next) * transform_function_next.jacobian() * w;
             }
 
             mass_matrix[[i,i-1]] = integral_prev_approximation_mass;
@@ -233,10 +343,10 @@ impl DiffussionSolverTimeDependent {
             b * integral_next_approximation_half;
         }
 
-        mass_matrix[[0,0]] = 1_f64;
-        mass_matrix[[basis_len-1,basis_len-1]] = 1_f64;
-        stiffness_matrix[[0,0]] = 1_f64;
-        stiffness_matrix[[basis_len-1,basis_len-1]] = 1_f64;
+        mass_matrix[[0,0]] = 1.0;
+        mass_matrix[[basis_len-1,basis_len-1]] = 1.0;
+        stiffness_matrix[[0,0]] = 1.0;
+        stiffness_matrix[[basis_len-1,basis_len-1]] = 1.0;
 
         // final result M(u_ti+1) = M(u_ti) + S(delta_t * u_ti)
         // this is the multiplication that has to be done
@@ -245,33 +355,125 @@ impl DiffussionSolverTimeDependent {
         Ok((mass_matrix,stiffness_matrix))
 
     }
+
+    /// # General Information
+    /// 
+    /// Obtains mass matrix and stiffness matrix when boundary conditions are periodic.
+    /// First and last node are collapsed into a single degree of freedom: the last node's contributions (element [n-2,n-1]) are added to the
+    /// first node's row and column, so resulting matrices are cyclic tridiagonal of size `mesh.len() - 1`.
+    /// To reuse `gauss_legendre_integration`, the mesh is extended with a copy of the last element before the first node and a copy of the first
+    /// element after the last node. Every row of an original node is then complete and only needs its columns folded over the seam.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `mu` - First of two terms to solve equation
+    /// * `b` - Second of two terms to solve equation
+    /// * `mesh` - Vector of f64 representing a mesh
+    /// * `gauss_step` - Amount of nodes to compute for integration.
+    /// 
+    fn periodic_integration(mu: Scalar, b: Scalar, mesh: &[Scalar], gauss_step: usize) -> Result<(Array2<Scalar>,Array2<Scalar>),Error> {
+
+        let mesh_len = mesh.len();
+        // degrees of freedom
+        let dof = mesh_len - 1;
+
+        let mut extended_mesh = Vec::with_capacity(mesh_len + 2);
+        extended_mesh.push(mesh[0] - (mesh[mesh_len - 1] - mesh[mesh_len - 2]));
+        extended_mesh.extend(mesh.iter());
+        extended_mesh.push(mesh[mesh_len - 1] + (mesh[1] - mesh[0]));
+
+        let (extended_mass, extended_stiffness) = Self::gauss_legendre_integration(
+            mu, b, &extended_mesh, gauss_step)?;
+
+        let mut mass_matrix = ndarray::Array::from_elem((dof, dof), 0.0);
+        let mut stiffness_matrix = ndarray::Array::from_elem((dof, dof), 0.0);
+
+        // node i is row i+1 in extended matrices; columns i, i+1 and i+2 correspond to its previous, itself and next node
+        for i in 0..dof {
+            let prev = (i + dof - 1) % dof;
+            let next = (i + 1) % dof;
+
+            mass_matrix[[i,prev]] += extended_mass[[i+1,i]];
+            mass_matrix[[i,i]] += extended_mass[[i+1,i+1]];
+            mass_matrix[[i,next]] += extended_mass[[i+1,i+2]];
+
+            stiffness_matrix[[i,prev]] += extended_stiffness[[i+1,i]];
+            stiffness_matrix[[i,i]] += extended_stiffness[[i+1,i+1]];
+            stiffness_matrix[[i,next]] += extended_stiffness[[i+1,i+2]];
+        }
+
+        Ok((mass_matrix,stiffness_matrix))
+    }
 }
 
 impl DiffEquationSolver for DiffussionSolverTimeDependent {
 
+    fn assembly_time(&self) -> Duration {
+        self.assembly_time
+    }
+
+    /// # Specific implementation
+    ///
+    /// State holds every node, including boundary ones and the repeated last node of periodic meshes.
+    ///
+    fn dof_count(&self) -> usize {
+        self.state.len()
+    }
+
+    fn checkpoint(&self) -> Option<SolverCheckpoint> {
+        Some(SolverCheckpoint {
+            state: self.state.to_vec(),
+            time: self.time,
+        })
+    }
+
+    /// # Specific implementation
+    ///
+    /// State should have one value per node, as the one given by `checkpoint`.
+    ///
+    fn restore(&mut self, checkpoint: &SolverCheckpoint) -> Result<(), Error> {
+        if checkpoint.state.len() != self.state.len() {
+            return Err(Error::WrongDims);
+        }
+        self.state = Array1::from_vec(checkpoint.state.clone());
+        self.time = checkpoint.time;
+        Ok(())
+    }
+
     /// # Specific implementation
     /// 
     /// Calculate a vector b on left-side of equation.
     /// Then solve problem Ax = b for x.
     /// 
-    fn solve(&mut self, time_step: f64) -> Result<Vec<f64>, Error> {
+    fn solve(&mut self, time_step: Scalar) -> Result<Vec<Scalar>, Error> {
+
+        if let [BoundaryCondition::Periodic, BoundaryCondition::Periodic] = self.boundary_conditions {
+            return self.solve_periodic(time_step);
+        }
 
         // let b = stiffness_matrix * self.state * time_step + mass_matrix * self.state;
         let b_first_part = utils::tridiagonal_matrix_vector_multiplication(
             &self.stiffness_matrix, &self.state, time_step)?;
 
         let b_second_part = utils::tridiagonal_matrix_vector_multiplication(
-            &self.mass_matrix, &self.state, 1_f64)?;
+            &self.mass_matrix, &self.state, 1.0)?;
 
         let b = utils::add(
             &b_first_part,
             &b_second_part)?;
 
         let mut res = matrix_solver::solve_by_thomas(&self.mass_matrix, &b)?;
-
-        // reinsert boundary values
-        res[0] = self.boundary_conditions[0];
-        res[b.len()-1] = self.boundary_conditions[1];
+        self.time += time_step;
+
+        // reinsert boundary values, evaluated at new time when they change with it
+        if let [BoundaryCondition::Dirichlet(left), BoundaryCondition::Dirichlet(right)] = self.boundary_conditions {
+            let (left, right) = match &self.boundary_functions {
+                Some([left, right]) => (left.evaluate(self.time), right.evaluate(self.time)),
+                None => (left, right),
+            };
+            res[0] = left;
+            res[b.len()-1] = right;
+        }
         
         self.state = Array1::from_vec(res.clone());
 
@@ -279,9 +481,38 @@ impl DiffEquationSolver for DiffussionSolverTimeDependent {
 
     }
 }
+
+impl DiffussionSolverTimeDependent {
+    /// Same as `solve` with cyclic matrices. Last node of state is not a degree of freedom and is copied from the first one.
+    fn solve_periodic(&mut self, time_step: Scalar) -> Result<Vec<Scalar>, Error> {
+
+        let dof = self.state.len() - 1;
+        let state = self.state.slice(ndarray::s![..dof]).to_owned();
+
+        let b_first_part = utils::cyclic_tridiagonal_matrix_vector_multiplication(
+            &self.stiffness_matrix, &state, time_step)?;
+
+        let b_second_part = utils::cyclic_tridiagonal_matrix_vector_multiplication(
+            &self.mass_matrix, &state, 1.0)?;
+
+        let b = utils::add(
+            &b_first_part,
+            &b_second_part)?;
+
+        let mut res = matrix_solver::solve_by_cyclic_thomas(&self.mass_matrix, &b)?;
+        res.push(res[0]);
+        self.time += time_step;
+
+        self.state = Array1::from_vec(res.clone());
+
+        Ok(res)
+    }
+}
 #[cfg(test)]
 mod tests {
-    use crate::solvers::{solver_trait::DiffEquationSolver, diffusion_solver::DiffussionParams};
+    use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+    use crate::{solvers::{solver_trait::DiffEquationSolver, diffusion_solver::DiffussionParams}, Error};
 
     use super::DiffussionSolverTimeDependent;
 
@@ -341,4 +572,165 @@ mod tests {
 
 
     }
+
+    #[test]
+    fn decaying_problem_reaches_steady_state() {
+
+        let conditions = DiffussionParams::time_dependent()
+            .b(0_f64)
+            .mu(1_f64)
+            .boundary_conditions(0_f64, 0_f64)
+            .initial_conditions(vec![1_f64;3]);
+
+        let mut dif_solver = DiffussionSolverTimeDependent::new(
+            &conditions.build(),
+            vec![0_f64,0.25,0.5,0.75,1_f64],
+            150)
+            .unwrap();
+
+        let solution = dif_solver.solve_to_steady_state(0.01, 1e-8, 10000).unwrap();
+
+        assert!(solution.iter().all(|u| u.abs() <= 1e-4));
+    }
+
+    #[test]
+    fn setting_stop_flag_cancels_solve() {
+
+        /// Sets a flag once `solve` has been called a given amount of times
+        #[derive(Debug)]
+        struct StopAfter {
+            solver: DiffussionSolverTimeDependent,
+            steps: usize,
+            stop: Arc<AtomicBool>,
+        }
+
+        impl DiffEquationSolver for StopAfter {
+            fn solve(&mut self, time_step: f64) -> Result<Vec<f64>, Error> {
+                self.steps -= 1;
+                if self.steps == 0 {
+                    self.stop.store(true, Ordering::Relaxed);
+                }
+                self.solver.solve(time_step)
+            }
+
+            fn dof_count(&self) -> usize {
+                self.solver.dof_count()
+            }
+        }
+
+        let conditions = DiffussionParams::time_dependent()
+            .b(0_f64)
+            .mu(1_f64)
+            .boundary_conditions(0_f64, 0_f64)
+            .initial_conditions(vec![1_f64;3]);
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut stop_after = StopAfter {
+            solver: DiffussionSolverTimeDependent::new(
+                &conditions.build(),
+                vec![0_f64,0.25,0.5,0.75,1_f64],
+                150)
+                .unwrap(),
+            steps: 5,
+            stop: Arc::clone(&stop),
+        };
+
+        let result = stop_after.solve_to_steady_state_or_cancel(0.01, 1e-8, 10000, &stop);
+
+        match result {
+            Err(Error::Cancelled(partial)) => {
+                assert!(partial.len() == 5);
+                assert!(partial[2] > 0_f64 && partial[2] < 1_f64);
+                assert!(stop_after.steps == 0);
+            },
+            _ => panic!("Solve was not cancelled"),
+        }
+    }
+
+    #[test]
+    fn periodic_advection_wraps_around() {
+
+        let nodes = 41;
+        let mesh: Vec<f64> = (0..nodes).map(|i| i as f64 / (nodes - 1) as f64).collect();
+        // bump close to the right end so that it is carried across the seam
+        let initial: Vec<f64> = mesh[..nodes - 1].iter().map(|x| (-((x - 0.85) / 0.1).powi(2)).exp()).collect();
+        let initial_mass: f64 = initial.iter().sum();
+
+        let conditions = DiffussionParams::time_dependent()
+            .b(1_f64)
+            .mu(0.01)
+            .periodic_boundary_conditions()
+            .initial_conditions(initial);
+
+        let mut dif_solver = DiffussionSolverTimeDependent::new(
+            &conditions.build(),
+            mesh,
+            150)
+            .unwrap();
+
+        let mut solution = vec![];
+        for _i in 0..300 {
+            solution = dif_solver.solve(0.001).unwrap();
+        }
+
+        // first and last node are the same point
+        assert!(solution[0] == solution[nodes - 1]);
+        // bump moved across the seam: maximum is now close to the left end
+        let max_idx = solution.iter().enumerate().fold(0, |max, (i, u)| if *u > solution[max] { i } else { max });
+        assert!(max_idx <= 8);
+        // solution is smooth across the seam: jump there is no bigger than the biggest jump elsewhere
+        let max_jump = solution[..nodes - 1].windows(2).map(|w| (w[1] - w[0]).abs()).fold(0_f64, f64::max);
+        assert!((solution[0] - solution[nodes - 2]).abs() <= max_jump);
+        // nothing leaves through the boundary
+        let mass: f64 = solution[..nodes - 1].iter().sum();
+        assert!((mass - initial_mass).abs() <= 1e-10 * initial_mass);
+    }
+
+    #[test]
+    fn left_value_follows_sine() {
+
+        let conditions = DiffussionParams::time_dependent()
+            .b(0_f64)
+            .mu(0.1)
+            .time_dependent_boundary_conditions(|t| (2.0 * std::f64::consts::PI * t).sin(), |_| 0_f64)
+            .initial_conditions(vec![0_f64;9]);
+
+        let mesh: Vec<f64> = (0..11).map(|i| i as f64 / 10.0).collect();
+        let mut dif_solver = DiffussionSolverTimeDependent::new(&conditions.build(), mesh, 150).unwrap();
+        assert!(dif_solver.state[0] == 0_f64);
+
+        for step in 1..=100 {
+            let solution = dif_solver.solve(0.01).unwrap();
+            let time = step as f64 * 0.01;
+            assert!((solution[0] - (2.0 * std::f64::consts::PI * time).sin()).abs() < 1e-9);
+            assert!(solution[10] == 0_f64);
+        }
+
+        // Heat entering from the left end reaches the interior
+        assert!(dif_solver.state.iter().skip(1).take(9).any(|u| u.abs() > 1e-3));
+
+        dif_solver.reset();
+        assert!(dif_solver.time == 0_f64);
+        assert!((dif_solver.solve(0.25).unwrap()[0] - 1_f64).abs() < 1e-9);
+    }
+
+    #[test]
+    fn reset_reproduces_trajectory() {
+
+        let conditions = DiffussionParams::time_dependent()
+            .b(1_f64)
+            .mu(0.1)
+            .boundary_conditions(0_f64, 1_f64)
+            .initial_conditions(vec![0.5_f64;9]);
+
+        let mesh: Vec<f64> = (0..11).map(|i| i as f64 / 10.0).collect();
+        let mut dif_solver = DiffussionSolverTimeDependent::new(&conditions.build(), mesh, 150).unwrap();
+
+        let first: Vec<Vec<f64>> = (0..5).map(|_| dif_solver.solve(0.01).unwrap()).collect();
+        dif_solver.reset();
+        assert!(dif_solver.state.to_vec() == [vec![0_f64], vec![0.5_f64;9], vec![1_f64]].concat());
+        let second: Vec<Vec<f64>> = (0..5).map(|_| dif_solver.solve(0.01).unwrap()).collect();
+
+        assert!(first == second);
+    }
 }
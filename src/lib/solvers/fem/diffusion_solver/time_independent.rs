@@ -1,32 +1,35 @@
 // Internal dependencies
-use crate::solvers::fem::basis::single_variable::{
-    linear_basis::LinearBasis, polynomials_1d::FirstDegreePolynomial
-};
+use crate::solvers::Scalar;
+use crate::solvers::fem::basis::single_variable::linear_basis::LinearBasis;
 use crate::solvers::basis::functions::{Differentiable1D, Function1D};
-use crate::solvers::{quadrature::gauss_legendre, matrix_solver, solver_trait::DiffEquationSolver};
+use crate::solvers::{quadrature::{gauss_legendre, interval_map::IntervalMap}, matrix_solver, solver_trait::DiffEquationSolver};
 use crate::Error;
+use super::{BoundaryCondition, DiffussionParamsTimeIndependentBuilder};
 
 // External dependencies
-use ndarray::{Array1, Array2};
+use serde::{Deserialize, Serialize};
+use ndarray::{Array1, Array2, ArrayView1};
+use std::time::{Duration, Instant};
 
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "DiffussionParamsTimeIndependentBuilder")]
 /// # General Information
 /// 
 /// Parameters needed for solving diffussion equation in 1d with time-independence.
 /// If one of it's properties is not set, it will default to zero.
-/// Boundary conditions accepted are only Dirichlet for now.
+/// Boundary conditions accepted are Dirichlet and Neumann, chosen independently on each end.
 /// 
 /// # Parameters
 /// 
 /// * `mu` - Movement term
 /// * `b` - Velocity term
-/// * `boundary_conditions` - Dirichlet conditions
+/// * `boundary_conditions` - Dirichlet or Neumann conditions on left and right ends
 /// 
 pub struct DiffussionParamsTimeIndependent {
-    pub mu: f64,
-    pub b: f64,
-    pub boundary_conditions: [f64;2],
+    pub mu: Scalar,
+    pub b: Scalar,
+    pub boundary_conditions: [BoundaryCondition;2],
 }
 
 #[derive(Debug)]
@@ -36,29 +39,35 @@ pub struct DiffussionParamsTimeIndependent {
 ///
 /// # Fields
 ///
-/// * `boundary_conditions` - Original boundary conditions (Only Dirichlet is supported for now).
+/// * `boundary_conditions` - Original boundary conditions (Dirichlet or Neumann on each end).
 /// * `stiffness_matrix` - Left-side matrix of the resulting discrete equation.
 /// * `b_vector` - Right-side vector of the resulting discrete equation.
 /// * `gauss_step` - Precision of quadrature.
 /// * `mu` - First ot two needed constants.
 /// * `b` - Second of two needed constants.
+/// * `assembly_time` - Time spent creating stiffness matrix and vector b.
+/// * `mesh` - Nodes of 1D mesh. Needed to obtain gradient of a solution.
 ///
 pub struct DiffussionSolverTimeIndependent {
-    pub boundary_conditions: [f64; 2],
-    pub(crate) stiffness_matrix: Array2<f64>,
-    pub(crate) b_vector: Array1<f64>,
+    pub boundary_conditions: [BoundaryCondition; 2],
+    pub(crate) stiffness_matrix: Array2<Scalar>,
+    pub(crate) b_vector: Array1<Scalar>,
     pub gauss_step: usize,
-    pub mu: f64,
-    pub b: f64,
+    pub mu: Scalar,
+    pub b: Scalar,
+    pub(crate) assembly_time: Duration,
+    pub(crate) mesh: Vec<Scalar>,
 }
 
 impl DiffussionSolverTimeIndependent {
     /// Creates new instance
-    pub fn new(params: &DiffussionParamsTimeIndependent, mesh: Vec<f64>, gauss_step: usize) -> Result<Self,Error> {
+    pub fn new(params: &DiffussionParamsTimeIndependent, mesh: Vec<Scalar>, gauss_step: usize) -> Result<Self,Error> {
 
+        let assembly_start = Instant::now();
         let (stiffness_matrix, b_vector) = Self::gauss_legendre_integration(
             params.boundary_conditions, 
             params.mu, params.b, &mesh, gauss_step)?;
+        let assembly_time = assembly_start.elapsed();
 
         Ok(Self {
             boundary_conditions: params.boundary_conditions,
@@ -67,6 +76,8 @@ impl DiffussionSolverTimeIndependent {
             b_vector,
             mu: params.mu,
             b: params.b,
+            assembly_time,
+            mesh,
         })
     }
 
@@ -74,7 +85,8 @@ impl DiffussionSolverTimeIndependent {
     ///
     /// First, it generates the basis for a solver from the linear basis constructor.
     /// Then the stiffnes matrix and vector b are generated based on linear basis integration via Gauss-Legendre and returned.
-    /// Note that vector and matrix will have one on their diagonals' boundaries and zero on other boundary elements to make boundary conditions permanent. 
+    /// Note that, on Dirichlet ends, vector and matrix will have one on their diagonals' boundaries and zero on other boundary elements to make boundary conditions permanent.
+    /// On Neumann ends, the row of the boundary node is integrated over its only element and the flux enters vector b through the boundary term of the weak form.
     ///
     /// # Parameters
     ///
@@ -88,120 +100,359 @@ impl DiffussionSolverTimeIndependent {
     ///
     /// A tuple with both the stiffness matrix and the vector b.
     ///
-    pub fn gauss_legendre_integration(boundary_conditions: [f64;2], mu: f64, b: f64, mesh: &Vec<f64>, gauss_step: usize) -> Result<(Array2<f64>, Array1<f64>),Error> {
+    pub fn gauss_legendre_integration(boundary_conditions: [BoundaryCondition;2], mu: Scalar, b: Scalar, mesh: &Vec<Scalar>, gauss_step: usize) -> Result<(Array2<Scalar>, Array1<Scalar>),Error> {
         
         let basis = LinearBasis::new(mesh)?;
         let basis_len = basis.basis.len();
 
         let mut stiffness_matrix =
-            ndarray::Array::from_elem((basis_len, basis_len), 0_f64);
+            ndarray::Array::from_elem((basis_len, basis_len), 0.0);
         
-        let mut b_vector = Array1::from_elem(basis_len, 0_f64);
-
-
-        for i in 1..(basis_len - 1) {
-
-            let derivative_phi = basis.basis[i].differentiate()?;
-
-            let transform_function_prev = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i - 1],
-                mesh[i],
-            );
-            let transform_function_next = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i],
-                mesh[i + 1],
-            );
-            let transform_function_square =
-                FirstDegreePolynomial::transformation_from_m1_p1(
-                    mesh[i - 1],
-                    mesh[i + 1],
-                );
-
-            let derivative_t_prev = transform_function_prev.differentiate()?;
-            let derivative_t_next = transform_function_next.differentiate()?;
-            let derivative_t_square = transform_function_square.differentiate()?;
-
-            let derivative_prev = basis.basis[i - 1].differentiate()?;
-            let derivative_next = basis.basis[i + 1].differentiate()?;
-
-            let mut integral_prev_approximation = 0_f64;
-            let mut integral_next_approximation = 0_f64;
-            let mut integral_square_approximation = 0_f64;
-
-            // integrate
-            for j in 1..gauss_step {
-                // Obtaining arccos(node) and weight
-                let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
-                let x = theta.cos();
-
-                // translated from -1,1
-                let translated_point_prev = transform_function_prev.evaluate(x);
-                let translated_point_next = transform_function_next.evaluate(x);
-                let translated_point_square = transform_function_square.evaluate(x);
-
-                integral_prev_approximation += (mu
-                    * derivative_phi.evaluate(translated_point_prev)
-                    * derivative_prev.evaluate(translated_point_prev)
-                    + b
-                        * derivative_prev.evaluate(translated_point_prev)
-                        * basis.basis[i].evaluate(translated_point_prev))
-                    * derivative_t_prev.evaluate(x)
-                    * w;
-                integral_next_approximation += (mu
-                    * derivative_phi.evaluate(translated_point_next)
-                    * derivative_next.evaluate(translated_point_next)
-                    + b
-                        * derivative_next.evaluate(translated_point_next)
-                        * basis.basis[i].evaluate(translated_point_next))
-                    * derivative_t_next.evaluate(x)
-                    * w;
-                integral_square_approximation += (mu
-                    * derivative_phi.evaluate(translated_point_square)
-                    * derivative_phi.evaluate(translated_point_square)
-                    + b
-                        * derivative_phi.evaluate(translated_point_square)
-                        * basis.basis[i].evaluate(translated_point_square))
-                    * derivative_t_square.evaluate(x)
-                    * w;
-            }
+        let b_vector = Self::boundary_load(boundary_conditions, mu, basis_len)?;
+
+
+        #[cfg(not(feature = "parallel"))]
+        let rows = (1..(basis_len - 1))
+            .map(|i| Self::interior_row(mu, b, &basis, mesh, i, gauss_step))
+            .collect::<Result<Vec<[Scalar; 3]>, Error>>()?;
+
+        #[cfg(feature = "parallel")]
+        let rows = {
+            use rayon::prelude::*;
+            (1..(basis_len - 1))
+                .into_par_iter()
+                .map(|i| Self::interior_row(mu, b, &basis, mesh, i, gauss_step))
+                .collect::<Result<Vec<[Scalar; 3]>, Error>>()?
+        };
 
+        for (i, [integral_prev_approximation, integral_square_approximation, integral_next_approximation]) in (1..(basis_len - 1)).zip(rows) {
             stiffness_matrix[[i, i]] = integral_square_approximation;
             stiffness_matrix[[i, i - 1]] = integral_prev_approximation;
             stiffness_matrix[[i, i + 1]] = integral_next_approximation;
-        
         }
 
-        // adjusting boundary conditions inside vector and matrix so that u_0 = boundary_conditions[left] and u[n] = boundary_codnitions[right]
-        // when multiplying
-        stiffness_matrix[[0,0]] = 1_f64;
-        stiffness_matrix[[basis_len - 1, basis_len - 1]] = 1_f64;
-        b_vector[0] = boundary_conditions[0];
-        b_vector[basis_len - 1] = boundary_conditions[1];
+        // adjusting boundary conditions inside vector and matrix. Every end is treated on it's own: (node, neighbor node, sign of outward normal)
+        for (boundary_condition, (node, neighbor)) in boundary_conditions.iter().zip([(0, 1), (basis_len - 1, basis_len - 2)]) {
+            match boundary_condition {
+                // u_node = value when multiplying
+                BoundaryCondition::Dirichlet(_) => {
+                    stiffness_matrix[[node, node]] = 1.0;
+                },
+                // row of boundary node is integrated over its only element, flux is already in vector b
+                BoundaryCondition::Neumann(_) => {
+                    let (integral_square_approximation, integral_neighbor_approximation) =
+                        Self::boundary_integration(mu, b, &basis, mesh, node, neighbor, gauss_step)?;
+                    stiffness_matrix[[node, node]] = integral_square_approximation;
+                    stiffness_matrix[[node, neighbor]] = integral_neighbor_approximation;
+                },
+                BoundaryCondition::Periodic => (),
+            }
+        }
 
         Ok((stiffness_matrix, b_vector))
     }
+
+    /// # General Information
+    ///
+    /// Integrates the row of an interior node, whose basis function is non-zero on the two elements around it.
+    /// Rows are independent from each other, so they may be computed concurrently when feature `parallel` is enabled.
+    ///
+    /// # Parameters
+    ///
+    /// * `mu` - Movement term.
+    /// * `b` - Velocity term.
+    /// * `basis` - Linear basis generated from mesh.
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `i` - Interior node whose row is integrated.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    /// # Returns
+    ///
+    /// Entries of row `i` on columns `i - 1`, `i` and `i + 1`.
+    ///
+    fn interior_row(mu: Scalar, b: Scalar, basis: &LinearBasis, mesh: &[Scalar], i: usize, gauss_step: usize) -> Result<[Scalar; 3], Error> {
+        let derivative_phi = basis.basis[i].differentiate()?;
+
+        let transform_function_prev = IntervalMap::new(mesh[i - 1], mesh[i]);
+        let transform_function_next = IntervalMap::new(mesh[i], mesh[i + 1]);
+        let transform_function_square = IntervalMap::new(mesh[i - 1], mesh[i + 1]);
+
+        let derivative_prev = basis.basis[i - 1].differentiate()?;
+        let derivative_next = basis.basis[i + 1].differentiate()?;
+
+        let phi = &basis.basis[i];
+        Ok([
+            Self::quadrature(Self::weak_form(mu, b, phi, &derivative_phi, &derivative_prev), transform_function_prev, gauss_step)?,
+            Self::quadrature(Self::weak_form(mu, b, phi, &derivative_phi, &derivative_phi), transform_function_square, gauss_step)?,
+            Self::quadrature(Self::weak_form(mu, b, phi, &derivative_phi, &derivative_next), transform_function_next, gauss_step)?,
+        ])
+    }
+
+    /// # General Information
+    ///
+    /// Vector b (right-hand side) of the discrete equation. Equation has no source term, so only boundary nodes are non-zero:
+    /// Dirichlet ends hold their value, while Neumann ends hold the boundary term of the weak form, "μ u'(end) n" with n the outward normal.
+    ///
+    /// # Parameters
+    ///
+    /// * `boundary_conditions` - Conditions on left and right ends.
+    /// * `mu` - Movement term.
+    /// * `nodes` - Amount of nodes of mesh.
+    ///
+    fn boundary_load(boundary_conditions: [BoundaryCondition; 2], mu: Scalar, nodes: usize) -> Result<Array1<Scalar>, Error> {
+        let mut b_vector = Array1::from_elem(nodes, 0.0);
+
+        for (boundary_condition, (node, normal)) in boundary_conditions.iter().zip([(0, -1.0), (nodes - 1, 1.0)]) {
+            b_vector[node] = match boundary_condition {
+                BoundaryCondition::Dirichlet(value) => *value,
+                BoundaryCondition::Neumann(flux) => mu * flux * normal,
+                BoundaryCondition::Periodic => {
+                    return Err(Error::BoundaryError(
+                        "Periodic conditions are not supported by time-independent solver".to_string()
+                    ))
+                }
+            };
+        }
+
+        Ok(b_vector)
+    }
+
+    /// # General Information
+    ///
+    /// Vector b (right-hand side) assembled on its own from boundary conditions, without the stiffness matrix. Useful to check boundary terms.
+    /// Loads added afterwards (like the force of a Poisson problem) are not part of it.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver holding its mesh, constants and boundary conditions.
+    ///
+    pub fn load_vector(&self) -> Result<Array1<Scalar>, Error> {
+        Self::boundary_load(self.boundary_conditions, self.mu, self.mesh.len())
+    }
+
+    /// # General Information
+    ///
+    /// Integrates the row of a boundary node, whose basis function is only non-zero in the element shared with it's neighbor.
+    ///
+    /// # Parameters
+    ///
+    /// * `mu` - Movement term.
+    /// * `b` - Velocity term.
+    /// * `basis` - Linear basis generated from mesh.
+    /// * `mesh` - Vector of f64 representing a line.
+    /// * `node` - Boundary node.
+    /// * `neighbor` - Only node sharing an element with boundary node.
+    /// * `gauss_step` - How many nodes will be calculated for a given integration.
+    ///
+    /// # Returns
+    ///
+    /// A tuple with integrals for diagonal element and element of neighbor.
+    ///
+    fn boundary_integration(mu: Scalar, b: Scalar, basis: &LinearBasis, mesh: &[Scalar], node: usize, neighbor: usize, gauss_step: usize) -> Result<(Scalar, Scalar),Error> {
+
+        let derivative_phi = basis.basis[node].differentiate()?;
+        let derivative_neighbor = basis.basis[neighbor].differentiate()?;
+
+        let transform_function = IntervalMap::new(mesh[node.min(neighbor)], mesh[node.max(neighbor)]);
+
+        let phi = &basis.basis[node];
+        Ok((
+            Self::quadrature(Self::weak_form(mu, b, phi, &derivative_phi, &derivative_phi), transform_function, gauss_step)?,
+            Self::quadrature(Self::weak_form(mu, b, phi, &derivative_phi, &derivative_neighbor), transform_function, gauss_step)?,
+        ))
+    }
+
+    /// # General Information
+    ///
+    /// Integrand of the weak form for the entry of row i and column j: "μ φ_i' φ_j' + b φ_j' φ_i".
+    ///
+    /// # Parameters
+    ///
+    /// * `mu` - Movement term.
+    /// * `b` - Velocity term.
+    /// * `phi` - Basis function of row, φ_i.
+    /// * `derivative_phi` - Derivative of basis function of row, φ_i'.
+    /// * `derivative_other` - Derivative of basis function of column, φ_j'.
+    ///
+    fn weak_form<'a, T: Function1D>(mu: Scalar, b: Scalar, phi: &'a T, derivative_phi: &'a T, derivative_other: &'a T) -> Box<dyn Function1D + 'a> {
+        derivative_phi.times(derivative_other).scale(mu).plus(derivative_other.times(phi).scale(b))
+    }
+
+    /// Integral of a function over an element with every Gauss-Legendre node of `gauss_step`.
+    fn quadrature(integrand: Box<dyn Function1D + '_>, transform_function: IntervalMap, gauss_step: usize) -> Result<Scalar, Error> {
+        let mut integral_approximation = 0.0;
+
+        for j in 1..=gauss_step {
+            // Obtaining arccos(node) and weight
+            let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
+            // translated from -1,1
+            integral_approximation += integrand.evaluate(transform_function.from_reference(theta.cos())) * transform_function.jacobian() * w;
+        }
+
+        Ok(integral_approximation)
+    }
+
+    /// # General Information
+    ///
+    /// Derivative of a solution (flux) on every element, computed from nodal values and element lengths through the basis derivatives.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver holding its mesh.
+    /// * `solution` - Value of solution at every node (normally obtained with `solve`).
+    ///
+    pub fn gradient(&self, solution: &[Scalar]) -> Result<Vec<Scalar>, Error> {
+        super::element_gradients(&self.mesh, solution)
+    }
+
+    /// # General Information
+    ///
+    /// Exact solution of "- μu_xx + bu_x = 0" with the boundary conditions of solver on the ends of its mesh. Every solution has the form
+    /// `u = A + B g(x)` with `g` an exponential of rate `b/μ` (a line when `b` vanishes), so both constants are obtained from the two conditions.
+    /// Returns NaN when conditions do not determine a single solution (Neumann or periodic on both ends).
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver holding its mesh, constants and boundary conditions.
+    /// * `x` - Point in which to evaluate solution.
+    ///
+    pub fn analytic(&self, x: Scalar) -> Scalar {
+        let (left, right) = match (self.mesh.first(), self.mesh.last()) {
+            (Some(left), Some(right)) => (*left, *right),
+            _ => return Scalar::NAN,
+        };
+        let rate = self.b / self.mu;
+
+        // Exponential is anchored on the end it decays towards, so that it never overflows
+        let g = |x: Scalar| -> (Scalar, Scalar) {
+            if (rate * (right - left)).abs() < 1e-10 {
+                (x - left, 1.0)
+            } else {
+                let anchor = if rate > 0.0 { right } else { left };
+                let value = (rate * (x - anchor)).exp();
+                (value, rate * value)
+            }
+        };
+
+        // Every condition is a row [a, b] = c of a 2x2 system on (A, B)
+        let row = |condition: BoundaryCondition, end: Scalar| -> Option<[Scalar; 3]> {
+            let (value, derivative) = g(end);
+            match condition {
+                BoundaryCondition::Dirichlet(u) => Some([1.0, value, u]),
+                BoundaryCondition::Neumann(flux) => Some([0.0, derivative, flux]),
+                BoundaryCondition::Periodic => None,
+            }
+        };
+        let (first, second) = match (row(self.boundary_conditions[0], left), row(self.boundary_conditions[1], right)) {
+            (Some(first), Some(second)) => (first, second),
+            _ => return Scalar::NAN,
+        };
+
+        let determinant = first[0] * second[1] - first[1] * second[0];
+        if determinant == 0.0 {
+            return Scalar::NAN;
+        }
+        let a = (first[2] * second[1] - first[1] * second[2]) / determinant;
+        let b = (first[0] * second[2] - first[2] * second[0]) / determinant;
+
+        a + b * g(x).0
+    }
+
+    /// # General Information
+    ///
+    /// Residual `r = b - Ax` of a given solution, where A is stiffness matrix and b is vector b. Measures how well a solution satisfies the discrete
+    /// equation: for a direct solve it should be close to machine precision.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver with it's matrix and vector already assembled.
+    /// * `solution` - A solution of the system (normally obtained with `solve`).
+    ///
+    pub fn residual(&self, solution: &[Scalar]) -> Result<Vec<Scalar>, Error> {
+
+        if solution.len() != self.b_vector.len() {
+            return Err(Error::WrongDims);
+        }
+
+        let solution = ArrayView1::from(solution);
+        let residual = &self.b_vector - &self.stiffness_matrix.dot(&solution);
+
+        Ok(residual.to_vec())
+    }
+
+    /// # General Information
+    ///
+    /// Writes sparsity pattern of assembled stiffness matrix as a png image of size n x n. Every nonzero entry is a black pixel while zeros are white.
+    /// Useful to check assembly: for linear basis a tridiagonal band is expected.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of solver with it's matrix already assembled.
+    /// * `path` - Location of the image. Must end in '.png'.
+    ///
+    pub fn write_sparsity(&self, path: &str) -> Result<(), Error> {
+
+        if !path.ends_with(".png") {
+            return Err(Error::ExtensionNotAllowed(path.to_string(), "sparsity pattern".to_string()));
+        }
+
+        let (rows, columns) = self.stiffness_matrix.dim();
+        let rows = u32::try_from(rows).map_err(|_| Error::Overflow)?;
+        let columns = u32::try_from(columns).map_err(|_| Error::Overflow)?;
+
+        let image = image::GrayImage::from_fn(columns, rows, |x, y| {
+            if self.stiffness_matrix[[y as usize, x as usize]] != 0.0 {
+                image::Luma([0_u8])
+            } else {
+                image::Luma([255_u8])
+            }
+        });
+
+        image.save(path)?;
+
+        Ok(())
+    }
 }
 
 impl DiffEquationSolver for DiffussionSolverTimeIndependent {
+    fn assembly_time(&self) -> Duration {
+        self.assembly_time
+    }
+
+    fn dof_count(&self) -> usize {
+        self.b_vector.len()
+    }
+
     /// # Specific implementation
     ///
     /// Solving starts by obtaining stiffness matrix and vector b (Ax=b).
-    /// Then both are used inside function `solve_by_thomas` to obtain the result vector.
+    /// Then both are used inside function `solve_by_thomas` to obtain the result vector. Whenever stiffness matrix is not tridiagonal
+    /// (higher order bases), `solve_by_cholesky` is used instead, or `solve_by_gaussian_elimination` when it's not symmetric positive definite.
     ///
-    fn solve(&mut self, _time_step: f64) -> Result<Vec<f64>, Error> {
+    fn solve(&mut self, _time_step: Scalar) -> Result<Vec<Scalar>, Error> {
 
-        let res = matrix_solver::solve_by_thomas(&self.stiffness_matrix, &self.b_vector)?;
+        let res = matrix_solver::solve_by_thomas_or_cholesky(&self.stiffness_matrix, &self.b_vector)?;
 
         Ok(res)
     }
+
+    /// # Specific implementation
+    ///
+    /// Problem does not depend on time, so a single solution is already the steady state.
+    ///
+    fn solve_to_steady_state(&mut self, time_step: Scalar, _tol: Scalar, _max_steps: usize) -> Result<Vec<Scalar>, Error> {
+        self.solve(time_step)
+    }
 }
 
 #[cfg(test)]
 mod test {
 
-    use crate::solvers::{matrix_solver, diffusion_solver::DiffussionParams};
+    use crate::solvers::{matrix_solver, diffusion_solver::{BoundaryCondition, DiffussionParams}, solver_trait::DiffEquationSolver};
 
     use super::DiffussionSolverTimeIndependent;
+    use crate::solvers::diffusion_solver::MixedOrderSolver1D;
+    use ndarray::{Array1, Array2};
 
     #[test]
     fn regular_mesh_matrix_3p() {
@@ -308,8 +559,8 @@ mod test {
         assert!(dif_solver.stiffness_matrix[[4, 4]] == 1_f64);
         assert!(dif_solver.stiffness_matrix[[0, 0]] == 1_f64);
 
-        assert!(dif_solver.b_vector[0] == dif_solver.boundary_conditions[0]);
-        assert!(dif_solver.b_vector[4] == dif_solver.boundary_conditions[1]);
+        assert!(BoundaryCondition::Dirichlet(dif_solver.b_vector[0]) == dif_solver.boundary_conditions[0]);
+        assert!(BoundaryCondition::Dirichlet(dif_solver.b_vector[4]) == dif_solver.boundary_conditions[1]);
     }
 
     #[test]
@@ -327,10 +578,271 @@ mod test {
         let res = matrix_solver::solve_by_thomas(&dif_solver.stiffness_matrix, &dif_solver.b_vector).unwrap();
 
         assert!(res.len() == 5);
-        assert!(res[0] == dif_solver.boundary_conditions[0]);
+        assert!(BoundaryCondition::Dirichlet(res[0]) == dif_solver.boundary_conditions[0]);
         assert!(res[1] >= 0.15 && res[1] <= 0.17);
         assert!(res[2] >= 0.36 && res[2] <= 0.38);
         assert!(res[3] >= 0.63 && res[3] <= 0.655);
-        assert!(res[4] == dif_solver.boundary_conditions[1]);
+        assert!(BoundaryCondition::Dirichlet(res[4]) == dif_solver.boundary_conditions[1]);
+    }
+
+    #[test]
+    fn residual_of_direct_solve_vanishes() {
+
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0)
+        .build();
+
+        let mut dif_solver = DiffussionSolverTimeIndependent::new(
+            &params,
+            vec![0_f64, 0.25, 0.5, 0.75, 1_f64],
+            150
+        ).unwrap();
+
+        let res = dif_solver.solve(0_f64).unwrap();
+        let residual = dif_solver.residual(&res).unwrap();
+
+        assert!(residual.len() == 5);
+        assert!(residual.iter().all(|r| r.abs() < 1e-10));
+        assert!(dif_solver.residual(&res[..4]).is_err());
+    }
+
+    #[test]
+    fn mixed_dirichlet_neumann() {
+
+        // -u'' + u' = 0 with u(0) = 1 and u'(1) = 2 has solution u = 1 - 2/e + 2/e * exp(x)
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0)
+            .mixed_boundary_conditions(BoundaryCondition::Dirichlet(1.0), BoundaryCondition::Neumann(2.0))
+            .build();
+
+        let mesh: Vec<f64> = (0..41).map(|i| i as f64 / 40.0).collect();
+        let mut dif_solver = DiffussionSolverTimeIndependent::new(
+            &params,
+            mesh.clone(),
+            150
+        ).unwrap();
+
+        let res = dif_solver.solve(0_f64).unwrap();
+        let exact = |x: f64| 1.0 - 2.0 / std::f64::consts::E + 2.0 / std::f64::consts::E * x.exp();
+
+        // Dirichlet end
+        assert!(res[0] == 1.0);
+        // Neumann end: derivative on last element
+        let derivative = (res[40] - res[39]) / (mesh[40] - mesh[39]);
+        assert!((derivative - 2.0).abs() < 0.05);
+        // Whole solution
+        assert!(res.iter().zip(mesh.iter()).all(|(u, x)| (u - exact(*x)).abs() < 1e-2));
+    }
+
+    #[test]
+    fn neumann_flux_enters_load_vector() {
+        let params = DiffussionParams::time_independent().b(1.0).mu(0.5)
+            .mixed_boundary_conditions(BoundaryCondition::Dirichlet(1.0), BoundaryCondition::Neumann(2.0))
+            .build();
+        let dif_solver = DiffussionSolverTimeIndependent::new(&params, vec![0.0, 0.25, 0.5, 0.75, 1.0], 150).unwrap();
+        let load = dif_solver.load_vector().unwrap();
+
+        // mu * flux * outward normal on the right end
+        assert!(load.to_vec() == vec![1.0, 0.0, 0.0, 0.0, 1.0]);
+        assert!(load == dif_solver.b_vector);
+
+        // Outward normal on the left end points backwards
+        let params = DiffussionParams::time_independent().b(1.0).mu(0.5)
+            .mixed_boundary_conditions(BoundaryCondition::Neumann(2.0), BoundaryCondition::Dirichlet(1.0))
+            .build();
+        let dif_solver = DiffussionSolverTimeIndependent::new(&params, vec![0.0, 0.5, 1.0], 150).unwrap();
+        assert!(dif_solver.load_vector().unwrap().to_vec() == vec![-1.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn linear_basis_has_a_dof_per_node() {
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0).build();
+
+        for nodes in [3, 4, 11] {
+            let mesh: Vec<f64> = (0..nodes).map(|i| i as f64 / (nodes - 1) as f64).collect();
+            let mut dif_solver = DiffussionSolverTimeIndependent::new(&params, mesh, 150).unwrap();
+
+            assert!(dif_solver.dof_count() == nodes);
+            assert!(dif_solver.solve(0_f64).unwrap().len() == dif_solver.dof_count());
+        }
+    }
+
+    #[test]
+    fn fem_solution_approaches_analytic() {
+
+        let mixed = DiffussionParams::time_independent().b(1.0).mu(1.0)
+            .mixed_boundary_conditions(BoundaryCondition::Dirichlet(1.0), BoundaryCondition::Neumann(2.0))
+            .build();
+        let convective = DiffussionParams::time_independent().b(20.0).mu(1.0).boundary_conditions(0.0, 1.0).build();
+        let backwards = DiffussionParams::time_independent().b(-5.0).mu(0.5).boundary_conditions(2.0, -1.0).build();
+
+        for (params, nodes, tolerance) in [(&mixed, 41, 1e-2), (&convective, 201, 1e-2), (&backwards, 101, 1e-2)] {
+            let h = 1.0 / (nodes - 1) as f64;
+            let mesh: Vec<f64> = (0..nodes).map(|i| i as f64 * h).collect();
+            let mut dif_solver = DiffussionSolverTimeIndependent::new(params, mesh.clone(), 150).unwrap();
+            let res = dif_solver.solve(0_f64).unwrap();
+
+            assert!(res.iter().zip(mesh.iter()).all(|(u, x)| (u - dif_solver.analytic(*x)).abs() < tolerance));
+        }
+
+        // Matches closed form of mixed problem
+        let exact = |x: f64| 1.0 - 2.0 / std::f64::consts::E + 2.0 / std::f64::consts::E * x.exp();
+        let dif_solver = DiffussionSolverTimeIndependent::new(&mixed, vec![0.0, 0.5, 1.0], 150).unwrap();
+        assert!([0.0, 0.3, 1.0].iter().all(|x| (dif_solver.analytic(*x) - exact(*x)).abs() < 1e-12));
+
+        // Without b solution is a line, and two fluxes leave it undetermined
+        let diffusive = DiffussionParams::time_independent().b(0.0).mu(1.0).boundary_conditions(1.0, 3.0).build();
+        let dif_solver = DiffussionSolverTimeIndependent::new(&diffusive, vec![0.0, 0.5, 1.0], 150).unwrap();
+        assert!((dif_solver.analytic(0.25) - 1.5).abs() < 1e-12);
+        let fluxes = DiffussionParams::time_independent().b(1.0).mu(1.0)
+            .mixed_boundary_conditions(BoundaryCondition::Neumann(1.0), BoundaryCondition::Neumann(1.0))
+            .build();
+        let dif_solver = DiffussionSolverTimeIndependent::new(&fluxes, vec![0.0, 0.5, 1.0], 150).unwrap();
+        assert!(dif_solver.analytic(0.5).is_nan());
+    }
+
+    #[test]
+    fn sparsity_is_tridiagonal() {
+
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0)
+        .build();
+
+        let dif_solver = DiffussionSolverTimeIndependent::new(
+            &params,
+            vec![0_f64, 0.2, 0.4, 0.6, 0.8, 1_f64],
+            150
+        ).unwrap();
+
+        let path = std::env::temp_dir().join("dzahui_sparsity_test.png");
+        let path = path.to_str().unwrap();
+        dif_solver.write_sparsity(path).unwrap();
+
+        let image = image::open(path).unwrap().into_luma8();
+        std::fs::remove_file(path).unwrap();
+
+        assert!(image.dimensions() == (6, 6));
+        for (x, y, pixel) in image.enumerate_pixels() {
+            let (i, j) = (y as i64, x as i64);
+            // boundary rows only keep their diagonal element
+            let in_band = if i == 0 || i == 5 { i == j } else { (i - j).abs() <= 1 };
+            assert!((pixel[0] == 0) == in_band);
+        }
+    }
+
+    #[test]
+    fn quadratic_elements_use_cholesky() {
+
+        let params = DiffussionParams::time_independent().b(0.0).mu(1.0).boundary_conditions(0.0, 0.0)
+        .build();
+
+        let mut dif_solver = DiffussionSolverTimeIndependent::new(
+            &params,
+            vec![0_f64, 0.5, 1_f64],
+            150
+        ).unwrap();
+
+        // -u'' = 2 on [0,1] with u(0) = u(1) = 0 using three quadratic elements (h = 1/3). Only interior nodes (x = k/6) are kept.
+        // Element stiffness is [[7,-8,1],[-8,16,-8],[1,-8,7]] / 3h, so vertices shared by two elements couple with vertices two nodes away.
+        dif_solver.stiffness_matrix = Array2::from(vec![
+            [16., -8., 0., 0., 0.],
+            [-8., 14., -8., 1., 0.],
+            [0., -8., 16., -8., 0.],
+            [0., 1., -8., 14., -8.],
+            [0., 0., 0., -8., 16.],
+        ]);
+        dif_solver.b_vector = Array1::from(vec![4./9., 2./9., 4./9., 2./9., 4./9.]);
+
+        // Quadratic elements reproduce exact solution x(1-x) at nodes
+        let exact: Vec<f64> = (1..6).map(|k| k as f64 / 6.).map(|x| x * (1. - x)).collect();
+
+        let thomas = matrix_solver::solve_by_thomas(&dif_solver.stiffness_matrix, &dif_solver.b_vector).unwrap();
+        assert!(thomas.iter().zip(exact.iter()).any(|(x, e)| (x - e).abs() > 1e-3));
+
+        let res = dif_solver.solve(0.0).unwrap();
+        assert!(res.iter().zip(exact.iter()).all(|(x, e)| (x - e).abs() < 1e-12));
+    }
+
+    #[test]
+    fn quadratic_assembly_with_advection_is_solved() {
+
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0)
+        .build();
+
+        // Bubbles of quadratic elements make matrix not tridiagonal, while advection and Dirichlet rows make it not symmetric
+        let mixed_solver = MixedOrderSolver1D::new(&params, vec![0_f64, 0.25, 0.5, 0.75, 1_f64], &[2, 2, 2, 2], |_| 0_f64, 10).unwrap();
+        assert!(!matrix_solver::is_tridiagonal(&mixed_solver.stiffness_matrix));
+        assert!(!matrix_solver::is_symmetric_positive_definite(&mixed_solver.stiffness_matrix, 1e-12));
+
+        let res = matrix_solver::solve_by_thomas_or_cholesky(&mixed_solver.stiffness_matrix, &mixed_solver.b_vector).unwrap();
+        let residual = &mixed_solver.b_vector - &mixed_solver.stiffness_matrix.dot(&Array1::from(res.clone()));
+        assert!(residual.iter().all(|r| r.abs() < 1e-12));
+
+        // Nodal values are close to exact solution (e^x - 1) / (e - 1)
+        let exact = |x: f64| (x.exp() - 1_f64) / (1_f64.exp() - 1_f64);
+        assert!(res.iter().take(5).enumerate().all(|(i, u)| (u - exact(i as f64 / 4.0)).abs() < 1e-3));
+    }
+
+    #[test]
+    fn solve_with_stats_measures_assembly_and_solve() {
+
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0)
+        .build();
+
+        let mesh: Vec<f64> = (0..100).map(|i| i as f64 / 99.0).collect();
+        let mut dif_solver = DiffussionSolverTimeIndependent::new(&params, mesh, 150).unwrap();
+
+        let (solution, stats) = dif_solver.solve_with_stats(0.0).unwrap();
+
+        assert!(solution.len() == 100);
+        assert!(stats.assembly_time > std::time::Duration::ZERO);
+        assert!(stats.solve_time > std::time::Duration::ZERO);
+        // Thomas is a direct method
+        assert!(stats.iterations.is_none());
+    }
+
+    #[test]
+    fn gradient_of_linear_solution() {
+
+        let params = DiffussionParams::time_independent().b(0.0).mu(1.0).boundary_conditions(0.0, 2.0)
+        .build();
+        let mesh = vec![0_f64, 0.1, 0.35, 0.5, 0.8, 1_f64];
+        let dif_solver = DiffussionSolverTimeIndependent::new(&params, mesh.clone(), 150).unwrap();
+
+        // u = 2x on an irregular mesh
+        let solution: Vec<f64> = mesh.iter().map(|x| 2.0 * x).collect();
+        let gradient = dif_solver.gradient(&solution).unwrap();
+
+        assert!(gradient.len() == mesh.len() - 1);
+        assert!(gradient.iter().all(|g| (g - 2.0).abs() < 1e-12));
+        assert!(dif_solver.gradient(&solution[1..]).is_err());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel_assembly_matches_serial() {
+        use crate::solvers::{basis::functions::Differentiable1D, quadrature::interval_map::IntervalMap};
+
+        let mesh: Vec<f64> = (0..=2000).map(|i| (i as f64 / 2000.0).powi(2)).collect();
+        let (stiffness_matrix, _) = DiffussionSolverTimeIndependent::gauss_legendre_integration(
+            [BoundaryCondition::Dirichlet(0.0), BoundaryCondition::Dirichlet(1.0)], 0.5, 2.0, &mesh, 10).unwrap();
+
+        // Same matrix assembled one entry after another on a single thread
+        let basis = super::LinearBasis::new(&mesh).unwrap();
+        let mut serial_matrix = Array2::from_elem((mesh.len(), mesh.len()), 0.0);
+        for i in 1..(mesh.len() - 1) {
+            let phi = &basis.basis[i];
+            let derivative_phi = phi.differentiate().unwrap();
+            for (j, start, end) in [(i - 1, i - 1, i), (i, i - 1, i + 1), (i + 1, i, i + 1)] {
+                let derivative = basis.basis[j].differentiate().unwrap();
+                serial_matrix[[i, j]] = DiffussionSolverTimeIndependent::quadrature(
+                    DiffussionSolverTimeIndependent::weak_form(0.5, 2.0, phi, &derivative_phi, &derivative),
+                    IntervalMap::new(mesh[start], mesh[end]),
+                    10,
+                ).unwrap();
+            }
+        }
+        serial_matrix[[0, 0]] = 1.0;
+        serial_matrix[[mesh.len() - 1, mesh.len() - 1]] = 1.0;
+
+        assert!(stiffness_matrix.shape() == serial_matrix.shape());
+        assert!(stiffness_matrix.iter().zip(serial_matrix.iter()).all(|(parallel, serial)| (parallel - serial).abs() < 1e-12));
     }
 }
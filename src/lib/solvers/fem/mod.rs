@@ -5,9 +5,11 @@ pub mod diffusion_solver;
 pub mod stokes_solver;
 
 // Internal dependencies + re-exports
-pub use diffusion_solver::{DiffussionParamsTimeDependent, DiffussionSolverTimeDependent, DiffussionSolverTimeIndependent, DiffussionParamsTimeIndependent};
+pub use diffusion_solver::{BoundaryCondition, DiffussionParamsTimeDependent, DiffussionSolverTimeDependent, DiffussionSolverTimeIndependent, DiffussionParamsTimeIndependent, PoissonParams1D, PoissonSolver1D};
 pub use stokes_solver::{StokesParams1D, StokesParams2D, StaticPressureSolver};
+use crate::solvers::Scalar;
 use super::solver_trait::DiffEquationSolver;
+use crate::Error;
 
 /// # General Information
 ///
@@ -19,6 +21,7 @@ use super::solver_trait::DiffEquationSolver;
 ///
 /// * `DiffussionSolverTimeIndependent` - Diffusion equation solver representation.
 /// * `DiffussionSolverTimeDependent` - Diffusion equation solver with time derivative representation.
+/// * `Poisson1DSolver` - Poisson equation in 1D solver representation.
 /// * `Stokes1DSolver` - Stokes in 1D solver representation.
 /// * `Stokes2DSolver` - Stokes in 2D solver representation.
 /// * `None` - Visuallization of mesh in simulation. No equation attached.
@@ -27,17 +30,166 @@ use super::solver_trait::DiffEquationSolver;
 pub enum Solver {
     DiffussionSolverTimeIndependent(DiffussionParamsTimeIndependent),
     DiffussionSolverTimeDependent(DiffussionParamsTimeDependent),
+    Poisson1DSolver(PoissonParams1D),
     Stokes1DSolver(StokesParams1D),
     Stokes2DSolver(StokesParams2D),
     None
 }
 
+impl Solver {
+    /// Diffusion terms (mu, b) of equation. None when equation is not a diffusion equation.
+    pub fn mu_and_b(&self) -> Option<(Scalar, Scalar)> {
+        match self {
+            Solver::DiffussionSolverTimeIndependent(params) => Some((params.mu, params.b)),
+            Solver::DiffussionSolverTimeDependent(params) => Some((params.mu, params.b)),
+            _ => None,
+        }
+    }
+
+    /// Changes diffusion terms (mu, b) of equation. A solver has to be rebuilt afterwards for them to take effect.
+    pub fn set_mu_and_b(&mut self, mu: Scalar, b: Scalar) -> Result<(), Error> {
+        match self {
+            Solver::DiffussionSolverTimeIndependent(params) => {
+                params.mu = mu;
+                params.b = b;
+            }
+            Solver::DiffussionSolverTimeDependent(params) => {
+                params.mu = mu;
+                params.b = b;
+            }
+            _ => return Err(Error::custom("Only diffusion equations have mu and b terms")),
+        }
+        Ok(())
+    }
+
+    /// # General Information
+    ///
+    /// Replaces boundary conditions of a 1D equation on the ends given, keeping the others. A solver has to be rebuilt afterwards for them
+    /// to take effect. Time-dependent boundary functions are dropped once an end is replaced.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - Equation to change
+    /// * `conditions` - New condition for left and right ends. None keeps the current one
+    ///
+    pub fn set_boundary_conditions(&mut self, conditions: [Option<BoundaryCondition>; 2]) -> Result<(), Error> {
+        if conditions.iter().all(Option::is_none) {
+            return Ok(());
+        }
+        match self {
+            Solver::DiffussionSolverTimeIndependent(params) => {
+                for (current, new) in params.boundary_conditions.iter_mut().zip(conditions) {
+                    *current = new.unwrap_or(*current);
+                }
+            }
+            Solver::DiffussionSolverTimeDependent(params) => {
+                for (current, new) in params.boundary_conditions.iter_mut().zip(conditions) {
+                    *current = new.unwrap_or(*current);
+                }
+                params.boundary_functions = None;
+            }
+            Solver::Poisson1DSolver(params) => {
+                if conditions.iter().flatten().any(|condition| !matches!(condition, BoundaryCondition::Dirichlet(_))) {
+                    return Err(Error::BoundaryError("Poisson equation only accepts Dirichlet conditions".to_string()));
+                }
+                for (current, new) in params.boundary_conditions.iter_mut().zip(conditions) {
+                    if let Some(BoundaryCondition::Dirichlet(value)) = new {
+                        *current = value;
+                    }
+                }
+            }
+            _ => return Err(Error::BoundaryError("Equation has no boundary conditions to edit".to_string())),
+        }
+        Ok(())
+    }
+
+    /// Names of scalar fields in a solution of equation. Solutions hold the values of every field at a node one after another, in this order.
+    pub fn field_names(&self) -> Vec<&'static str> {
+        match self {
+            Solver::DiffussionSolverTimeIndependent(_) | Solver::DiffussionSolverTimeDependent(_) => vec!["v_x"],
+            Solver::Poisson1DSolver(_) => vec!["u"],
+            Solver::Stokes1DSolver(_) => vec!["p"],
+            Solver::Stokes2DSolver(_) => vec!["v_x", "v_y", "p"],
+            Solver::None => vec![],
+        }
+    }
+
+    /// # General Information
+    ///
+    /// Creates a solver of the equation from its current params, assembling its matrices again. Time-dependent solvers start over from their initial conditions.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - Equation to solve
+    /// * `mesh` - Nodes of 1D mesh
+    /// * `integration_iteration` - Amount of nodes used by quadrature
+    ///
+    pub(crate) fn rebuild_solver(&self, mesh: Vec<Scalar>, integration_iteration: usize) -> Result<Box<dyn DiffEquationSolver>, Error> {
+        match self {
+            Solver::DiffussionSolverTimeIndependent(params) => {
+                let diffussion_solver = DiffussionSolverTimeIndependent::new(params, mesh, integration_iteration)?;
+                log::info!("Diffussion solver with time independence created");
+                Ok(Box::new(diffussion_solver))
+            }
+            Solver::DiffussionSolverTimeDependent(params) => {
+                let diffussion_solver = DiffussionSolverTimeDependent::new(params, mesh, integration_iteration)?;
+                log::info!("Diffussion solver with time dependence created");
+                Ok(Box::new(diffussion_solver))
+            }
+            Solver::Poisson1DSolver(params) => {
+                let poisson_solver = PoissonSolver1D::with_gauss_step(mesh, &params.force_function, params.boundary_conditions, integration_iteration)?;
+                log::info!("Poisson solver in 1D created");
+                Ok(Box::new(poisson_solver))
+            }
+            Solver::Stokes1DSolver(params) => {
+                let stokes_1d_solver = StaticPressureSolver::new(params, mesh, integration_iteration)?;
+                log::info!("Stokes solver in 1D with no time dependency created");
+                Ok(Box::new(stokes_1d_solver))
+            }
+            Solver::Stokes2DSolver(_params) => Err(Error::custom("Stokes solver in 2D is not implemented yet")),
+            Solver::None => {
+                log::info!("No solver selected. Program will display Mesh");
+                Ok(Box::new(NoSolver()))
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct NoSolver();
 
 impl DiffEquationSolver for NoSolver {
 
-    fn solve(&mut self, _time_step: f64) -> Result<Vec<f64>, crate::Error> {
+    fn solve(&mut self, _time_step: Scalar) -> Result<Vec<Scalar>, crate::Error> {
         Ok(vec![])
     }
-}
\ No newline at end of file
+
+    fn dof_count(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diffusion_solver::DiffussionParams, Solver};
+
+    #[test]
+    fn changed_mu_changes_rebuilt_solver() {
+        let params = DiffussionParams::time_independent().b(1.0).mu(1.0).boundary_conditions(0.0, 1.0)
+        .build();
+        let mut equation = Solver::DiffussionSolverTimeIndependent(params);
+        let mesh = vec![0_f64, 0.25, 0.5, 0.75, 1_f64];
+
+        let diffusive = equation.rebuild_solver(mesh.clone(), 150).unwrap().solve(0.0).unwrap();
+
+        equation.set_mu_and_b(0.1, 1.0).unwrap();
+        assert!(equation.mu_and_b() == Some((0.1, 1.0)));
+        let convective = equation.rebuild_solver(mesh, 150).unwrap().solve(0.0).unwrap();
+
+        // Boundaries stay, but with less diffusion the interior is pushed towards the left boundary value
+        assert!(convective[0] == diffusive[0] && convective[4] == diffusive[4]);
+        assert!((1..4).all(|i| convective[i] < diffusive[i]));
+
+        assert!(Solver::None.set_mu_and_b(1.0, 1.0).is_err());
+    }
+}
@@ -1,11 +1,11 @@
+use crate::solvers::Scalar;
 use std::fmt::Debug;
+use std::time::{Duration, Instant};
 
 // Internal dependencies
-use crate::solvers::fem::basis::single_variable::{
-    linear_basis::LinearBasis, polynomials_1d::FirstDegreePolynomial
-};
+use crate::solvers::fem::basis::single_variable::linear_basis::LinearBasis;
 use crate::solvers::basis::functions::{Differentiable1D,Function1D};
-use crate::solvers::{quadrature::gauss_legendre, matrix_solver, solver_trait::DiffEquationSolver};
+use crate::solvers::{quadrature::{gauss_legendre, interval_map::IntervalMap}, matrix_solver, solver_trait::DiffEquationSolver};
 use crate::Error;
 
 // External dependencies
@@ -24,17 +24,17 @@ use ndarray::{Array1, Array2};
 /// * `boundary_condition_pressure` - Pressure boundary condition
 /// 
 pub struct StokesParams1D {
-    pub rho: f64,
-    pub hydrostatic_pressure: f64,
-    pub force_function: Box<dyn Fn(f64) -> f64>,
+    pub rho: Scalar,
+    pub hydrostatic_pressure: Scalar,
+    pub force_function: Box<dyn Fn(Scalar) -> Scalar>,
 }
 
 
 impl Default for StokesParams1D {
     fn default() -> Self {
         Self {
-            rho: 0_f64,
-            hydrostatic_pressure: 0_f64,
+            rho: 0.0,
+            hydrostatic_pressure: 0.0,
             force_function: Box::new(|x| x)
         }
     }
@@ -43,7 +43,7 @@ impl Default for StokesParams1D {
 impl Debug for StokesParams1D {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ff = &self.force_function;
-        let eval = ff(0_f64);
+        let eval = ff(0.0);
         let content = format!("{{ rho: {},\nhydrostatic_pressure: {},\n force_function: f(0) -> {} }}", self.rho, self.hydrostatic_pressure,eval);
         write!(f, "{}", content)
     }
@@ -62,20 +62,23 @@ impl Debug for StokesParams1D {
 /// * `gauss_step` - Precision of quadrature.
 /// * `speed` - Constant speed.
 /// * `rho` - Constant density.
+/// * `assembly_time` - Time spent creating stiffness matrix and vector b.
 ///
 pub struct StokesSolver1D {
-    pub(crate) stiffness_matrix: Array2<f64>,
-    pub(crate) b_vector: Array1<f64>,
+    pub(crate) stiffness_matrix: Array2<Scalar>,
+    pub(crate) b_vector: Array1<Scalar>,
     pub gauss_step: usize,
-    pub hydrostatic_pressure: f64,
-    pub rho: f64,
+    pub hydrostatic_pressure: Scalar,
+    pub rho: Scalar,
+    pub(crate) assembly_time: Duration,
 }
 
 impl StokesSolver1D {
 
     /// Creates a new instance of solver from params
-    pub fn new(params: &StokesParams1D, mesh: Vec<f64>, gauss_step: usize) -> Result<Self,Error> {
+    pub fn new(params: &StokesParams1D, mesh: Vec<Scalar>, gauss_step: usize) -> Result<Self,Error> {
 
+        let assembly_start = Instant::now();
         let (stiffness_matrix, b_vector) = Self::gauss_legendre_integration(
             params.rho,
             params.hydrostatic_pressure,
@@ -83,12 +86,14 @@ impl StokesSolver1D {
             gauss_step,
             &params.force_function
         )?;
+        let assembly_time = assembly_start.elapsed();
         Ok(Self {
             stiffness_matrix,
             gauss_step,
             b_vector,
             hydrostatic_pressure: params.hydrostatic_pressure,
-            rho: params.rho
+            rho: params.rho,
+            assembly_time,
         })
 
     }
@@ -111,77 +116,63 @@ impl StokesSolver1D {
     ///
     /// A tuple with both the stiffness matrix and the vector b.
     ///
-    pub fn gauss_legendre_integration(rho: f64, hydrostatic_pressure: f64, mesh: &Vec<f64>, gauss_step: usize, function: &Box<dyn Fn(f64) -> f64>) -> Result<(Array2<f64>, Array1<f64>),Error> {
+    pub fn gauss_legendre_integration(rho: Scalar, hydrostatic_pressure: Scalar, mesh: &Vec<Scalar>, gauss_step: usize, function: &Box<dyn Fn(Scalar) -> Scalar>) -> Result<(Array2<Scalar>, Array1<Scalar>),Error> {
 
         let basis = LinearBasis::new(mesh)?;
         let basis_len = basis.basis.len();
 
         let mut stiffness_matrix =
-            ndarray::Array::from_elem((basis_len, basis_len), 0_f64);
+            ndarray::Array::from_elem((basis_len, basis_len), 0.0);
 
-        let mut b_vector = Array1::from_elem(basis_len, 0_f64);
+        let mut b_vector = Array1::from_elem(basis_len, 0.0);
 
 
         for i in 1..(basis_len - 1) {
 
             let derivative_phi = basis.basis[i].differentiate()?;
 
-            let transform_function_prev = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i - 1],
-                mesh[i],
-            );
-            let transform_function_next = FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[i],
-                mesh[i + 1],
-            );
-            let transform_function_square =
-                FirstDegreePolynomial::transformation_from_m1_p1(
-                    mesh[i - 1],
-                    mesh[i + 1],
-                );
-
-            let derivative_t_prev = transform_function_prev.differentiate()?;
-            let derivative_t_next = transform_function_next.differentiate()?;
-            let derivative_t_square = transform_function_square.differentiate()?;
+            let transform_function_prev = IntervalMap::new(mesh[i - 1], mesh[i]);
+            let transform_function_next = IntervalMap::new(mesh[i], mesh[i + 1]);
+            let transform_function_square = IntervalMap::new(mesh[i - 1], mesh[i + 1]);
 
             let derivative_prev = basis.basis[i - 1].differentiate()?;
             let derivative_next = basis.basis[i + 1].differentiate()?;
 
-            let mut integral_prev_approximation = 0_f64;
-            let mut integral_next_approximation = 0_f64;
-            let mut integral_square_approximation = 0_f64;
-            let mut b_integral_approximation = 0_f64;
+            let mut integral_prev_approximation = 0.0;
+            let mut integral_next_approximation = 0.0;
+            let mut integral_square_approximation = 0.0;
+            let mut b_integral_approximation = 0.0;
 
             // integrate
-            for j in 1..gauss_step {
+            for j in 1..=gauss_step {
                 // Obtaining arccos(node) and weight
                 let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
                 let x = theta.cos();
 
                 // translated from -1,1
-                let translated_point_prev = transform_function_prev.evaluate(x);
-                let translated_point_next = transform_function_next.evaluate(x);
-                let translated_point_square = transform_function_square.evaluate(x);
+                let translated_point_prev = transform_function_prev.from_reference(x);
+                let translated_point_next = transform_function_next.from_reference(x);
+                let translated_point_square = transform_function_square.from_reference(x);
 
                 integral_prev_approximation +=
                     basis.basis[i].evaluate(translated_point_prev)*
                     derivative_prev.evaluate(translated_point_prev)*
-                    derivative_t_prev.evaluate(x)*
+                    transform_function_prev.jacobian()*
                     w;
                 integral_next_approximation +=
                     basis.basis[i].evaluate(translated_point_next)*
                     derivative_next.evaluate(translated_point_next)*
-                    derivative_t_next.evaluate(x)*
+                    transform_function_next.jacobian()*
                     w;
                 integral_square_approximation +=
                     basis.basis[i].evaluate(translated_point_square)*
                     derivative_phi.evaluate(translated_point_square)*
-                    derivative_t_square.evaluate(x)*
+                    transform_function_square.jacobian()*
                     w;
                 b_integral_approximation += rho*
                     function(translated_point_square)*
                     basis.basis[i].evaluate(translated_point_square)*
-                    derivative_t_square.evaluate(x)*
+                    transform_function_square.jacobian()*
                     w;
             }
 
@@ -195,43 +186,38 @@ impl StokesSolver1D {
         let derivative_phi_0 = basis.basis[0].differentiate()?;
         let derivative_phi_1 = basis.basis[1].differentiate()?;
 
-        let transform_function_square_0 =
-            FirstDegreePolynomial::transformation_from_m1_p1(
-                mesh[0],
-                mesh[1],
-            );
-        let derivative_t_square_0 = transform_function_square_0.differentiate()?;
+        let transform_function_square_0 = IntervalMap::new(mesh[0], mesh[1]);
 
-        let mut integral_0_approximation = 0_f64;
-        let mut integral_0_next_approximation = 0_f64;
-        let mut b_first_integral_approximation = 0_f64;
+        let mut integral_0_approximation = 0.0;
+        let mut integral_0_next_approximation = 0.0;
+        let mut b_first_integral_approximation = 0.0;
 
 
-        for j in 1..gauss_step {
+        for j in 1..=gauss_step {
 
             // Obtaining arccos(node) and weight
             let (theta, w) = gauss_legendre::quad_pair(gauss_step, j)?;
             let x = theta.cos();
 
-            let translated_0 = transform_function_square_0.evaluate(x);
+            let translated_0 = transform_function_square_0.from_reference(x);
 
             integral_0_approximation += basis.basis[0].evaluate(translated_0) * 
                 derivative_phi_0.evaluate(translated_0) * 
-                derivative_t_square_0.evaluate(x) * w;
+                transform_function_square_0.jacobian() * w;
             
             integral_0_next_approximation += basis.basis[0].evaluate(translated_0) * 
             derivative_phi_1.evaluate(translated_0) * 
-            derivative_t_square_0.evaluate(x) * w;
+            transform_function_square_0.jacobian() * w;
 
             b_first_integral_approximation += rho * function(translated_0) *
             basis.basis[0].evaluate(translated_0) *
-            derivative_t_square_0.evaluate(x) * w;
+            transform_function_square_0.jacobian() * w;
 
         }
 
         stiffness_matrix[[0, 0]] = integral_0_approximation;
         stiffness_matrix[[0, 1]] = integral_0_next_approximation;
-        stiffness_matrix[[basis_len-1,basis_len-1]] = 1_f64;
+        stiffness_matrix[[basis_len-1,basis_len-1]] = 1.0;
         b_vector[0] = b_first_integral_approximation;
         b_vector[basis_len - 1] = hydrostatic_pressure;
 
@@ -241,17 +227,33 @@ impl StokesSolver1D {
 }
 
 impl DiffEquationSolver for StokesSolver1D {
+    fn assembly_time(&self) -> Duration {
+        self.assembly_time
+    }
+
+    fn dof_count(&self) -> usize {
+        self.b_vector.len()
+    }
+
     /// # Specific implementation
     ///
     /// Solving starts by obtaining stiffness matrix and vector b (Ax=b).
     /// Then both are used inside function `solve_by_thomas` to obtain the result vector.
     ///
-    fn solve(&mut self, _time_step: f64) -> Result<Vec<f64>, Error> {
+    fn solve(&mut self, _time_step: Scalar) -> Result<Vec<Scalar>, Error> {
 
         let res = matrix_solver::solve_by_thomas(&self.stiffness_matrix, &self.b_vector)?;
 
         Ok(res)
     }
+
+    /// # Specific implementation
+    ///
+    /// Problem does not depend on time, so a single solution is already the steady state.
+    ///
+    fn solve_to_steady_state(&mut self, time_step: Scalar, _tol: Scalar, _max_steps: usize) -> Result<Vec<Scalar>, Error> {
+        self.solve(time_step)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,8 @@
 
 // Internal dependencies
+use crate::solvers::Scalar;
+use crate::solvers::matrix_solver;
+use crate::Error;
 
 // External dependencies
 use ndarray::{Array1, Array2};
@@ -14,21 +17,21 @@ use std::fmt::Debug;
 /// Not repetead since they're the same as `StokesParams2DBuilder`
 /// 
 pub struct StokesParams2D {
-    pub boundary_conditions: Vec<[f64;2]>,
-    pub hydrostatic_pressure: f64,
-    pub force_function: Box<dyn Fn([f64;2]) -> [f64;2]>,
-    pub rho: f64,
-    pub nu: f64
+    pub boundary_conditions: Vec<[Scalar;2]>,
+    pub hydrostatic_pressure: Scalar,
+    pub force_function: Box<dyn Fn([Scalar;2]) -> [Scalar;2]>,
+    pub rho: Scalar,
+    pub nu: Scalar
 }
 
 impl Default for StokesParams2D {
     fn default() -> Self {
         Self {
             boundary_conditions: vec![],
-            hydrostatic_pressure: 0_f64,
-            force_function: Box::new(|_| [0_f64;2]),
-            nu: 0_f64,
-            rho: 0_f64
+            hydrostatic_pressure: 0.0,
+            force_function: Box::new(|_| [0.0;2]),
+            nu: 0.0,
+            rho: 0.0
         }
     }
 }
@@ -36,19 +39,104 @@ impl Default for StokesParams2D {
 impl Debug for StokesParams2D {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let ff = &self.force_function;
-        let eval = ff([0_f64,0_f64]);
+        let eval = ff([0.0,0.0]);
         let content = format!("{{ rho: {},\nhydrostatic_pressure: {},\nnu: {},\nboundary_conditions: {:?},\n force_function: f(0,0) -> {:?} }}", self.rho, self.hydrostatic_pressure,self.nu,self.boundary_conditions,eval);
         write!(f, "{}", content)
     }
 }
+/// # General Information
+///
+/// Solution of Stokes equation in 2D split by field, in the same order the writer records them (`v_x`, `v_y`, `p`).
+///
+/// # Fields
+///
+/// * `velocity_x` - First component of velocity at every node
+/// * `velocity_y` - Second component of velocity at every node
+/// * `pressure` - Pressure at every node
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct StokesFields {
+    pub velocity_x: Vec<Scalar>,
+    pub velocity_y: Vec<Scalar>,
+    pub pressure: Vec<Scalar>,
+}
+
+impl StokesFields {
+    /// # General Information
+    ///
+    /// Splits a vector of degrees of freedom holding, for every node, its velocity components followed by its pressure: `[v_x, v_y, p, v_x, v_y, p, ...]`.
+    ///
+    /// # Parameters
+    ///
+    /// * `dofs` - Solution of discrete system. Its length has to be a multiple of three
+    ///
+    pub fn from_dofs(dofs: &[Scalar]) -> Result<Self, Error> {
+        if !dofs.len().is_multiple_of(3) {
+            return Err(Error::WrongDims);
+        }
+
+        let field = |offset: usize| dofs.iter().skip(offset).step_by(3).copied().collect();
+
+        Ok(Self {
+            velocity_x: field(0),
+            velocity_y: field(1),
+            pressure: field(2),
+        })
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub struct StokesSolver2D {
-    pub(crate) stiffness_matrix: Array2<f64>,
-    pub(crate) b_vector: Array1<f64>,
-    pub boundary_conditions: Vec<f64>,
-    pub hydrostatic_pressure: f64,
+    pub(crate) stiffness_matrix: Array2<Scalar>,
+    pub(crate) b_vector: Array1<Scalar>,
+    pub boundary_conditions: Vec<Scalar>,
+    pub hydrostatic_pressure: Scalar,
     pub gauss_step: usize,
-    pub rho: f64,
-    pub nu: f64,
-}
\ No newline at end of file
+    pub rho: Scalar,
+    pub nu: Scalar,
+}
+
+impl StokesSolver2D {
+    /// # General Information
+    ///
+    /// Solves assembled system and splits the result into velocity components and pressure (see `StokesFields::from_dofs`).
+    /// Gaussian elimination is used since mixed velocity-pressure systems are not positive definite.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - An instance of solver with it's matrix and vector already assembled
+    ///
+    pub fn solve_fields(&mut self) -> Result<StokesFields, Error> {
+        let dofs = matrix_solver::solve_by_gaussian_elimination(&self.stiffness_matrix, &self.b_vector)?;
+        StokesFields::from_dofs(&dofs)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{StokesFields, StokesSolver2D};
+    use ndarray::{Array1, Array2};
+
+    #[test]
+    fn fields_split_degrees_of_freedom() {
+        let dofs = 6;
+        let mut solver = StokesSolver2D {
+            stiffness_matrix: Array2::eye(dofs),
+            b_vector: Array1::from_vec(vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]),
+            boundary_conditions: vec![],
+            hydrostatic_pressure: 0.0,
+            gauss_step: 150,
+            rho: 1.0,
+            nu: 1.0,
+        };
+
+        let fields = solver.solve_fields().unwrap();
+        assert!([&fields.velocity_x, &fields.velocity_y, &fields.pressure].iter().all(|field| field.len() == dofs / 3));
+        assert!(fields.velocity_x == vec![1.0, 4.0]);
+        assert!(fields.velocity_y == vec![2.0, 5.0]);
+        assert!(fields.pressure == vec![3.0, 6.0]);
+
+        assert!(StokesFields::from_dofs(&[1.0, 2.0]).is_err());
+    }
+}
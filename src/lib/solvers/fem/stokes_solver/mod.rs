@@ -1,9 +1,10 @@
+use crate::solvers::Scalar;
 pub mod dim1;
 pub mod dim2;
 
 pub use dim1::StokesParams1D;
 pub use dim1::StokesSolver1D;
-pub use dim2::StokesParams2D;
+pub use dim2::{StokesFields, StokesParams2D, StokesSolver2D};
 
 // Aliasing
 pub type StaticPressureSolver = StokesSolver1D;
@@ -25,9 +26,9 @@ pub struct StokesParams();
 /// * `force_function` - force applied on the fluid
 /// 
 pub struct StokesParams1DBuilder {
-    hydrostatic_pressure: Option<f64>,
-    rho: Option<f64>,
-    force_function: Option<Box<dyn Fn(f64) -> f64>>
+    hydrostatic_pressure: Option<Scalar>,
+    rho: Option<Scalar>,
+    force_function: Option<Box<dyn Fn(Scalar) -> Scalar>>
 }
 
 #[derive(Default)]
@@ -44,11 +45,11 @@ pub struct StokesParams1DBuilder {
 /// * `nu` - viscosity
 /// 
 pub struct StokesParams2DBuilder {
-    boundary_conditions: Option<Vec<[f64;2]>>,
-    hydrostatic_pressure: Option<f64>,
-    force_function: Option<Box<dyn Fn([f64;2]) -> [f64;2]>>,
-    rho: Option<f64>,
-    nu: Option<f64>
+    boundary_conditions: Option<Vec<[Scalar;2]>>,
+    hydrostatic_pressure: Option<Scalar>,
+    force_function: Option<Box<dyn Fn([Scalar;2]) -> [Scalar;2]>>,
+    rho: Option<Scalar>,
+    nu: Option<Scalar>
 }
 
 
@@ -69,21 +70,21 @@ impl StokesParams {
 
 impl StokesParams1DBuilder {
     /// Set pressure
-    pub fn hydrostatic_pressure(self, pressure_value: f64) -> Self {
+    pub fn hydrostatic_pressure(self, pressure_value: Scalar) -> Self {
         Self {
             hydrostatic_pressure: Some(pressure_value),
             ..self
         }
     }
     /// Set rho
-    pub fn density(self, rho: f64) -> Self {
+    pub fn density(self, rho: Scalar) -> Self {
         Self {
             rho: Some(rho),
             ..self
         }
     }
     /// Set force function 
-    pub fn force_function(self, func: Box<dyn Fn(f64) -> f64>) -> Self {
+    pub fn force_function(self, func: Box<dyn Fn(Scalar) -> Scalar>) -> Self {
         Self {
             force_function: Some(func),
             ..self
@@ -120,35 +121,35 @@ impl StokesParams1DBuilder {
 
 impl StokesParams2DBuilder {
     /// Set boundary conditions
-    pub fn boundary_conditions(self, boundary_conditions: Vec<[f64;2]>) -> Self {
+    pub fn boundary_conditions(self, boundary_conditions: Vec<[Scalar;2]>) -> Self {
         Self {
             boundary_conditions: Some(boundary_conditions),
             ..self
         }
     }
     /// Set pressure
-    pub fn pressure(self, pressure_value: f64) -> Self {
+    pub fn pressure(self, pressure_value: Scalar) -> Self {
         Self {
             hydrostatic_pressure: Some(pressure_value),
             ..self
         }
     }
     /// Set nu
-    pub fn kinematic_viscosity(self, viscosity_value: f64) -> Self {
+    pub fn kinematic_viscosity(self, viscosity_value: Scalar) -> Self {
         Self {
             nu: Some(viscosity_value),
             ..self
         }
     }
     /// Set density
-    pub fn density(self, density_value: f64) -> Self {
+    pub fn density(self, density_value: Scalar) -> Self {
         Self {
             rho: Some(density_value),
             ..self
         }
     }
     /// Set force function
-    pub fn force_function(self, func: Box<dyn Fn([f64;2]) -> [f64;2]>) -> Self {
+    pub fn force_function(self, func: Box<dyn Fn([Scalar;2]) -> [Scalar;2]>) -> Self {
         Self {
             force_function: Some(func),
             ..self
@@ -1,4 +1,5 @@
 // Internal dependencies
+use crate::solvers::Scalar;
 use crate::Error;
 
 // External dependencies
@@ -13,14 +14,14 @@ use ndarray::{Array1, Array2, Axis};
 /// * `b` - First vector
 /// * `v` - Second vector
 /// 
-pub fn add(b: &Array1<f64>, v: &Array1<f64>) -> Result<Array1<f64>,Error> {
+pub fn add(b: &Array1<Scalar>, v: &Array1<Scalar>) -> Result<Array1<Scalar>,Error> {
 
     if b.len() != v.len() {
         return Err(Error::WrongDims);
     }
 
     let len = b.len();
-    let mut result_vec = Array1::from_vec(vec![0_f64;len]);
+    let mut result_vec = Array1::from_vec(vec![0.0;len]);
 
     for i in 0..=(len-1) {
         result_vec[i] = b[i] + v[i];
@@ -38,7 +39,7 @@ pub fn add(b: &Array1<f64>, v: &Array1<f64>) -> Result<Array1<f64>,Error> {
 /// * `a` - a tridiagonal matrix
 /// * `b` - a vector of the same length as any axis of the matrix
 /// 
-pub fn tridiagonal_matrix_vector_multiplication(a: &Array2<f64>, b: &Array1<f64>, c: f64) -> Result<Array1<f64>,Error> {
+pub fn tridiagonal_matrix_vector_multiplication(a: &Array2<Scalar>, b: &Array1<Scalar>, c: Scalar) -> Result<Array1<Scalar>,Error> {
     
     if !a.is_square() || b.len() != a.len_of(Axis(0)) {
         return Err(Error::WrongDims);
@@ -47,7 +48,7 @@ pub fn tridiagonal_matrix_vector_multiplication(a: &Array2<f64>, b: &Array1<f64>
     // get number of operations to perform
     let len = b.len();
     // initialize result vector
-    let mut result_vec = Array1::from_elem(len,0_f64);
+    let mut result_vec = Array1::from_elem(len,0.0);
 
     for i in 1..=(len - 2) {
         
@@ -59,4 +60,30 @@ pub fn tridiagonal_matrix_vector_multiplication(a: &Array2<f64>, b: &Array1<f64>
     result_vec[len-1] = c * ( a[[len-1,len-2]]* b[len-2] + a[[len-1,len-1]] * b[len-1] );
 
     Ok(result_vec)
-}
\ No newline at end of file
+}
+
+/// # General Information
+/// 
+/// Matrix - vector multiplication for a cyclic tridiagonal system (tridiagonal plus corners `a[0][n-1]` and `a[n-1][0]`)
+/// reducing number of operations from `n^2` to `3n`
+/// 
+/// # Parameters
+/// 
+/// * `a` - a cyclic tridiagonal matrix
+/// * `b` - a vector of the same length as any axis of the matrix
+/// * `c` - a constant multiplying the result
+/// 
+pub fn cyclic_tridiagonal_matrix_vector_multiplication(a: &Array2<Scalar>, b: &Array1<Scalar>, c: Scalar) -> Result<Array1<Scalar>,Error> {
+
+    if !a.is_square() || b.len() != a.len_of(Axis(0)) || b.len() < 3 {
+        return Err(Error::WrongDims);
+    }
+
+    let len = b.len();
+    let mut result_vec = tridiagonal_matrix_vector_multiplication(a, b, c)?;
+
+    result_vec[0] += c * a[[0,len-1]] * b[len-1];
+    result_vec[len-1] += c * a[[len-1,0]] * b[0];
+
+    Ok(result_vec)
+}
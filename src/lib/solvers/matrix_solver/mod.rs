@@ -1,5 +1,6 @@
 // local dependencies
 use crate::Error;
+use super::Scalar;
 
 // External dependencies
 use ndarray::{Array1, Array2, Axis};
@@ -14,16 +15,16 @@ use ndarray::{Array1, Array2, Axis};
 /// * `matrix` - A square matrix represented by an Array2.
 /// * `b` - A vector result from matrix multiplication Ax = b represented by an Array1.
 ///
-pub fn solve_by_thomas(matrix: &Array2<f64>, b: &Array1<f64>) -> Result<Vec<f64>, Error> {
+pub fn solve_by_thomas(matrix: &Array2<Scalar>, b: &Array1<Scalar>) -> Result<Vec<Scalar>, Error> {
 
     if !matrix.is_square() || matrix.len_of(Axis(0)) != b.len() {
         return Err(Error::WrongDims)
     }
 
-    let mut solution = vec![0_f64; b.len()];
+    let mut solution = vec![0.0; b.len()];
 
-    let mut c = Array1::from_elem(b.len() - 1, 0_f64);
-    let mut d = Array1::from_elem(b.len(), 0_f64);
+    let mut c = Array1::from_elem(b.len() - 1, 0.0);
+    let mut d = Array1::from_elem(b.len(), 0.0);
     c[0] = matrix[[0, 1]] / matrix[[0, 0]];
     d[0] = b[0] / matrix[[0, 0]];
 
@@ -47,11 +48,223 @@ pub fn solve_by_thomas(matrix: &Array2<f64>, b: &Array1<f64>) -> Result<Vec<f64>
     Ok(solution)
 }
 
+/// # General Information
+///
+/// Solves a linear problem **Ax=b** where **A** is cyclic tridiagonal, that is, tridiagonal with two extra non-zero corners (`A[0][n-1]` and `A[n-1][0]`).
+/// Such matrices appear with periodic boundary conditions. Sherman-Morrison formula is used to split the problem into two tridiagonal
+/// systems that are solved via `solve_by_thomas`.
+///
+/// # Parameters
+///
+/// * `matrix` - A square cyclic tridiagonal matrix of size at least 3 represented by an Array2.
+/// * `b` - A vector result from matrix multiplication Ax = b represented by an Array1.
+///
+pub fn solve_by_cyclic_thomas(matrix: &Array2<Scalar>, b: &Array1<Scalar>) -> Result<Vec<Scalar>, Error> {
+
+    if !matrix.is_square() || matrix.len_of(Axis(0)) != b.len() || b.len() < 3 {
+        return Err(Error::WrongDims)
+    }
+
+    let len = b.len();
+    let alpha = matrix[[len - 1, 0]];
+    let beta = matrix[[0, len - 1]];
+    let gamma = -matrix[[0, 0]];
+
+    // tridiagonal part of matrix with corrected diagonal corners
+    let mut tridiagonal = matrix.clone();
+    tridiagonal[[0, len - 1]] = 0.0;
+    tridiagonal[[len - 1, 0]] = 0.0;
+    tridiagonal[[0, 0]] -= gamma;
+    tridiagonal[[len - 1, len - 1]] -= alpha * beta / gamma;
+
+    let mut u = Array1::from_elem(len, 0.0);
+    u[0] = gamma;
+    u[len - 1] = alpha;
+
+    let x = solve_by_thomas(&tridiagonal, b)?;
+    let z = solve_by_thomas(&tridiagonal, &u)?;
+
+    let factor = (x[0] + beta * x[len - 1] / gamma) / (1.0 + z[0] + beta * z[len - 1] / gamma);
+
+    Ok(x.iter().zip(z.iter()).map(|(x_i, z_i)| x_i - factor * z_i).collect())
+}
+
+/// # General Information
+///
+/// Solves a linear problem **Ax=b** where **A** is symmetric positive definite via Cholesky decomposition (**A = LL^T**),
+/// followed by forward and backward substitution. Useful whenever **A** is banded but not tridiagonal, as happens with higher order bases.
+///
+/// # Parameters
+///
+/// * `matrix` - A square symmetric positive definite matrix represented by an Array2.
+/// * `b` - A vector result from matrix multiplication Ax = b represented by an Array1.
+///
+pub fn solve_by_cholesky(matrix: &Array2<Scalar>, b: &Array1<Scalar>) -> Result<Vec<Scalar>, Error> {
+
+    if !matrix.is_square() || matrix.len_of(Axis(0)) != b.len() {
+        return Err(Error::WrongDims)
+    }
+
+    if matrix.indexed_iter().any(|((i, j), value)| (value - matrix[[j, i]]).abs() > 1e-12 * (value.abs() + matrix[[j, i]].abs())) {
+        return Err(Error::Matrix("Cholesky decomposition needs a symmetric matrix"))
+    }
+
+    let len = b.len();
+    let lower = cholesky_factor(matrix)?;
+
+    // Forward substitution (Ly = b)
+    let mut y = vec![0.0; len];
+    for i in 0..len {
+        let sum: Scalar = (0..i).map(|k| lower[[i, k]] * y[k]).sum();
+        y[i] = (b[i] - sum) / lower[[i, i]];
+    }
+
+    // Backward substitution (L^Tx = y)
+    let mut solution = vec![0.0; len];
+    for i in (0..len).rev() {
+        let sum: Scalar = (i + 1..len).map(|k| lower[[k, i]] * solution[k]).sum();
+        solution[i] = (y[i] - sum) / lower[[i, i]];
+    }
+
+    Ok(solution)
+}
+
+/// # General Information
+///
+/// Lower triangular factor **L** of **A = LL^T**. Only the lower triangle of **A** is read, so symmetry should be checked beforehand.
+/// Fails on the first pivot that is not positive, which happens if and only if a symmetric **A** is not positive definite.
+///
+/// # Parameters
+///
+/// * `matrix` - A square symmetric matrix represented by an Array2.
+///
+fn cholesky_factor(matrix: &Array2<Scalar>) -> Result<Array2<Scalar>, Error> {
+    let len = matrix.len_of(Axis(0));
+    let mut lower = Array2::from_elem((len, len), 0.0);
+
+    for j in 0..len {
+        for i in j..len {
+            let sum: Scalar = (0..j).map(|k| lower[[i, k]] * lower[[j, k]]).sum();
+
+            if i == j {
+                let pivot = matrix[[j, j]] - sum;
+                if pivot <= 0.0 {
+                    return Err(Error::Matrix("Cholesky decomposition needs a positive definite matrix"))
+                }
+                lower[[j, j]] = pivot.sqrt();
+            } else {
+                lower[[i, j]] = (matrix[[i, j]] - sum) / lower[[j, j]];
+            }
+        }
+    }
+
+    Ok(lower)
+}
+
+/// # General Information
+///
+/// Checks wether a matrix is symmetric positive definite, so that `solve_by_cholesky` can be used on it. Symmetry is checked entry by entry
+/// up to `tol`, then a Cholesky factorization is attempted: a pivot that is not positive means matrix is not positive definite.
+/// Useful to choose a solver and to catch assembly bugs.
+///
+/// # Parameters
+///
+/// * `matrix` - A matrix represented by an Array2.
+/// * `tol` - Largest difference allowed between an entry and its transposed one.
+///
+pub fn is_symmetric_positive_definite(matrix: &Array2<Scalar>, tol: Scalar) -> bool {
+    matrix.is_square()
+        && matrix.indexed_iter().all(|((i, j), value)| (value - matrix[[j, i]]).abs() <= tol)
+        && cholesky_factor(matrix).is_ok()
+}
+
+/// Checks wether every non-zero entry of a matrix lies on the main diagonal or right next to it.
+pub fn is_tridiagonal(matrix: &Array2<Scalar>) -> bool {
+    matrix
+        .indexed_iter()
+        .all(|((i, j), value)| i.abs_diff(j) <= 1 || *value == 0.0)
+}
+
+/// # General Information
+///
+/// Solves **Ax=b** with `solve_by_thomas` when **A** is tridiagonal (1D linear elements). Otherwise (higher order elements) `solve_by_cholesky`
+/// is used when **A** is symmetric positive definite and `solve_by_gaussian_elimination` when it's not, as happens with an advection term
+/// or with rows of Dirichlet nodes.
+///
+/// # Parameters
+///
+/// * `matrix` - A square matrix represented by an Array2.
+/// * `b` - A vector result from matrix multiplication Ax = b represented by an Array1.
+///
+pub fn solve_by_thomas_or_cholesky(matrix: &Array2<Scalar>, b: &Array1<Scalar>) -> Result<Vec<Scalar>, Error> {
+    if is_tridiagonal(matrix) {
+        solve_by_thomas(matrix, b)
+    } else if is_symmetric_positive_definite(matrix, 1e-12) {
+        solve_by_cholesky(matrix, b)
+    } else {
+        solve_by_gaussian_elimination(matrix, b)
+    }
+}
+
+/// # General Information
+///
+/// Solves **Ax=b** via Gaussian elimination with partial pivoting. Unlike `solve_by_cholesky` it does not need a symmetric positive
+/// definite matrix, so it can be used on saddle-point systems (like mixed velocity-pressure ones) whose diagonal has zeros.
+///
+/// # Parameters
+///
+/// * `matrix` - A square non-singular matrix represented by an Array2.
+/// * `b` - A vector result from matrix multiplication Ax = b represented by an Array1.
+///
+pub fn solve_by_gaussian_elimination(matrix: &Array2<Scalar>, b: &Array1<Scalar>) -> Result<Vec<Scalar>, Error> {
+
+    if !matrix.is_square() || matrix.len_of(Axis(0)) != b.len() {
+        return Err(Error::WrongDims)
+    }
+
+    let len = b.len();
+    let mut upper = matrix.clone();
+    let mut y = b.clone();
+
+    for j in 0..len {
+        // Row with biggest entry on column j becomes pivot row
+        let pivot_row = (j..len)
+            .max_by(|&r, &s| upper[[r, j]].abs().total_cmp(&upper[[s, j]].abs()))
+            .unwrap_or(j);
+        if upper[[pivot_row, j]] == 0.0 {
+            return Err(Error::Matrix("Gaussian elimination needs a non-singular matrix"))
+        }
+        if pivot_row != j {
+            for k in 0..len {
+                upper.swap([j, k], [pivot_row, k]);
+            }
+            y.swap(j, pivot_row);
+        }
+
+        for i in (j + 1)..len {
+            let factor = upper[[i, j]] / upper[[j, j]];
+            for k in j..len {
+                upper[[i, k]] -= factor * upper[[j, k]];
+            }
+            y[i] -= factor * y[j];
+        }
+    }
+
+    // Backward substitution (Ux = y)
+    let mut solution = vec![0.0; len];
+    for i in (0..len).rev() {
+        let sum: Scalar = (i + 1..len).map(|k| upper[[i, k]] * solution[k]).sum();
+        solution[i] = (y[i] - sum) / upper[[i, i]];
+    }
+
+    Ok(solution)
+}
+
 #[cfg(test)]
 mod test {
     use ndarray::{Array2, Array1};
 
-    use super::solve_by_thomas;
+    use super::{solve_by_thomas, solve_by_cyclic_thomas, solve_by_cholesky, solve_by_gaussian_elimination, is_tridiagonal, is_symmetric_positive_definite};
 
 
     #[test]
@@ -87,4 +300,67 @@ mod test {
 
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn solve_cyclic_4x4() {
+
+        let matrix: Array2<f64> = Array2::from(vec![[4.,1.,0.,1.],
+            [1.,4.,1.,0.],[0.,1.,4.,1.],[1.,0.,1.,4.]]);
+        let b: Array1<f64> = Array1::from(vec![6.,6.,6.,6.]);
+
+        let res = solve_by_cyclic_thomas(&matrix, &b).unwrap();
+
+        assert!(res.iter().all(|x| (x - 1_f64).abs() < 1e-12));
+
+    }
+
+    #[test]
+    fn solve_cholesky_4x4() {
+
+        let matrix: Array2<f64> = Array2::from(vec![[4.,1.,1.,0.],
+            [1.,4.,1.,1.],[1.,1.,4.,1.],[0.,1.,1.,4.]]);
+        let b: Array1<f64> = Array1::from(vec![6.,7.,7.,6.]);
+
+        assert!(!is_tridiagonal(&matrix));
+
+        let res = solve_by_cholesky(&matrix, &b).unwrap();
+
+        assert!(res.iter().all(|x| (x - 1_f64).abs() < 1e-12));
+
+        let not_symmetric: Array2<f64> = Array2::from(vec![[4.,2.],[1.,4.]]);
+        assert!(solve_by_cholesky(&not_symmetric, &Array1::from(vec![1.,1.])).is_err());
+
+    }
+
+
+    #[test]
+    fn gaussian_elimination_handles_zero_diagonal() {
+
+        // Saddle-point matrix: Cholesky and Thomas without pivoting can not be used
+        let matrix: Array2<f64> = Array2::from(vec![[2.,0.,1.],[0.,2.,1.],[1.,1.,0.]]);
+        let b: Array1<f64> = Array1::from(vec![3.,1.,1.]);
+
+        let res = solve_by_gaussian_elimination(&matrix, &b).unwrap();
+        let residual = &b - &matrix.dot(&Array1::from(res));
+        assert!(residual.iter().all(|r| r.abs() < 1e-12));
+
+        assert!(solve_by_cholesky(&matrix, &b).is_err());
+        assert!(solve_by_gaussian_elimination(&Array2::from(vec![[1.,1.],[1.,1.]]), &Array1::from(vec![1.,1.])).is_err());
+    }
+    #[test]
+    fn detects_symmetric_positive_definite() {
+
+        let spd: Array2<f64> = Array2::from(vec![[4.,1.,1.,0.],
+            [1.,4.,1.,1.],[1.,1.,4.,1.],[0.,1.,1.,4.]]);
+        assert!(is_symmetric_positive_definite(&spd, 1e-12));
+
+        // Symmetric with eigenvalues 3 and -1
+        let indefinite: Array2<f64> = Array2::from(vec![[1.,2.],[2.,1.]]);
+        assert!(!is_symmetric_positive_definite(&indefinite, 1e-12));
+
+        // Asymmetry is only tolerated up to tol
+        let almost_symmetric: Array2<f64> = Array2::from(vec![[4.,1.],[1.001,4.]]);
+        assert!(!is_symmetric_positive_definite(&almost_symmetric, 1e-6));
+        assert!(is_symmetric_positive_definite(&almost_symmetric, 1e-2));
+        assert!(!is_symmetric_positive_definite(&Array2::from_elem((2, 3), 1_f64), 1e-12));
+    }
+}
@@ -2,9 +2,32 @@
 pub mod euler;
 pub mod fem;
 pub mod matrix_solver;
+pub mod norms;
 pub mod quadrature;
 pub mod solver_trait;
 
+/// Floating point type used along numerical paths (solvers, basis functions and norms). `f64` unless feature `f32-scalar` is enabled.
+/// Mesh coordinates and tabulated quadrature nodes stay in `f64` and are converted when handed to a solver.
+#[cfg(not(feature = "f32-scalar"))]
+pub type Scalar = f64;
+/// Floating point type used along numerical paths (solvers, basis functions and norms). `f32`, since feature `f32-scalar` is enabled.
+/// Mesh coordinates and tabulated quadrature nodes stay in `f64` and are converted when handed to a solver.
+#[cfg(feature = "f32-scalar")]
+pub type Scalar = f32;
+
+/// Converts a value kept in `f64` (mesh coordinates, quadrature nodes) into `Scalar`.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn to_scalar(value: f64) -> Scalar {
+    value as Scalar
+}
+
+/// Converts a `Scalar` (normally part of a solution) into `f64`, to color a mesh or write it to files.
+#[allow(clippy::unnecessary_cast)]
+pub(crate) fn from_scalar(value: Scalar) -> f64 {
+    value as f64
+}
+
 // Re-exports
 pub use fem::Solver;
 pub use fem::*;
+pub use norms::{convergence_rate, l2_norm, linf_norm};
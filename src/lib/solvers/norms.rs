@@ -0,0 +1,73 @@
+// Internal dependencies
+use super::Scalar;
+
+/// # General Information
+///
+/// Euclidean norm of a vector: square root of the sum of its squared entries. Zero for an empty vector.
+///
+/// # Parameters
+///
+/// * `v` - Vector to measure, like a solution or the difference between two of them
+///
+pub fn l2_norm(v: &[Scalar]) -> Scalar {
+    v.iter().map(|x| x.powi(2)).sum::<Scalar>().sqrt()
+}
+
+/// # General Information
+///
+/// Maximum norm of a vector: biggest absolute value among its entries. Zero for an empty vector.
+///
+/// # Parameters
+///
+/// * `v` - Vector to measure, like a solution or the difference between two of them
+///
+pub fn linf_norm(v: &[Scalar]) -> Scalar {
+    v.iter().map(|x| x.abs()).fold(0.0, Scalar::max)
+}
+
+/// # General Information
+///
+/// Observed order of accuracy between successive refinements: `log(e_i / e_(i+1)) / log(h_i / h_(i+1))` for every pair of consecutive meshes.
+/// Linear elements should give rates close to 2 for the L2 error of a smooth solution. When slices differ in length, only meshes present in both are used.
+///
+/// # Parameters
+///
+/// * `errors` - Error (like the L2 norm of the difference with an exact solution) obtained on every mesh, from coarsest to finest
+/// * `mesh_sizes` - Size of elements of every mesh, in the same order
+///
+pub fn convergence_rate(errors: &[Scalar], mesh_sizes: &[Scalar]) -> Vec<Scalar> {
+    errors
+        .windows(2)
+        .zip(mesh_sizes.windows(2))
+        .map(|(e, h)| (e[0] / e[1]).ln() / (h[0] / h[1]).ln())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{convergence_rate, l2_norm, linf_norm};
+
+    #[test]
+    fn norms_of_known_vectors() {
+        assert!(l2_norm(&[3.0, -4.0]) == 5.0);
+        assert!(linf_norm(&[3.0, -4.0]) == 4.0);
+        assert!(l2_norm(&[1.0, 1.0, 1.0, 1.0]) == 2.0);
+        assert!(linf_norm(&[-0.5, 0.25]) == 0.5);
+        assert!(l2_norm(&[]) == 0.0 && linf_norm(&[]) == 0.0);
+    }
+
+    #[test]
+    fn rates_of_known_sequences() {
+        let mesh_sizes = [0.1, 0.05, 0.025, 0.0125];
+
+        let linear = convergence_rate(&[1.0, 0.5, 0.25, 0.125], &mesh_sizes);
+        assert!(linear.len() == 3);
+        assert!(linear.iter().all(|rate| (rate - 1.0).abs() < 1e-12));
+
+        let quadratic = convergence_rate(&[1.0, 0.25, 0.0625, 0.015625], &mesh_sizes);
+        assert!(quadratic.iter().all(|rate| (rate - 2.0).abs() < 1e-12));
+
+        assert!(convergence_rate(&[1.0, 0.25], &mesh_sizes).len() == 1);
+        assert!(convergence_rate(&[1.0], &mesh_sizes).is_empty());
+    }
+}
@@ -17,7 +17,9 @@
 ///
 use std::f64::consts::PI;
 
+use crate::solvers::{to_scalar, Scalar};
 use crate::Error;
+use super::interval_map::IntervalMap;
 
 const JZ: [f64; 20] = [
     2.40482555769577276862163187933,
@@ -5903,10 +5905,15 @@ fn gauss_legendre_quad_pair_tabulated(l: usize, k: usize) -> (f64, f64) {
 /// # Parameters
 /// 
 /// * `n` - n-th Legendre Polynomial
-/// * `k` - k-th zero of n-th Legendre Polynomial
+/// * `k` - k-th zero of n-th Legendre Polynomial, between 1 and n
 /// 
-pub fn quad_pair(n: usize, k: usize) -> Result<(f64, f64),Error> {
-    match k < n {
+pub fn quad_pair(n: usize, k: usize) -> Result<(Scalar, Scalar),Error> {
+    quad_pair_f64(n, k).map(|(theta, w)| (to_scalar(theta), to_scalar(w)))
+}
+
+/// Same as `quad_pair`, in the precision nodes are tabulated and calculated with.
+fn quad_pair_f64(n: usize, k: usize) -> Result<(f64, f64),Error> {
+    match k >= 1 && k <= n {
         true => {
             if n < 101 {
                 Ok(gauss_legendre_quad_pair_tabulated(n, k - 1))
@@ -5921,18 +5928,43 @@ pub fn quad_pair(n: usize, k: usize) -> Result<(f64, f64),Error> {
             }
         }
         false => {
-            Err(Error::Integration(String::from("Misuse of quad_pair function: k should be smaller than n")))
+            Err(Error::Integration(String::from("Misuse of quad_pair function: k should be between 1 and n")))
         }
     }
 }
 
+/// # General Information
+/// 
+/// Integrates `f` over `[a,b]` with 1, 2, ..., `max_n` Gauss-Legendre nodes and returns every pair (nodes, value).
+/// Useful to choose `integration_iteration`: once values stop changing, adding nodes is only wasted time.
+/// 
+/// # Parameters
+/// 
+/// * `f` - Function to integrate
+/// * `a` - Start of interval
+/// * `b` - End of interval
+/// * `max_n` - Maximum amount of nodes to use
+/// 
+pub fn gauss_legendre_convergence<F: Fn(Scalar) -> Scalar>(f: F, a: Scalar, b: Scalar, max_n: usize) -> Result<Vec<(usize, Scalar)>, Error> {
+    let map = IntervalMap::new(a, b);
+
+    (1..=max_n)
+        .map(|n| -> Result<(usize, Scalar), Error> {
+            let mut sum = 0.0;
+            for k in 1..=n {
+                let (theta, w) = quad_pair(n, k)?;
+                sum += w * f(map.from_reference(theta.cos()));
+            }
+            Ok((n, sum * map.jacobian()))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
 
     use super::PI;
     use super::*;
-    use crate::solvers::fem::basis::single_variable::{polynomials_1d::FirstDegreePolynomial};
-    use crate::solvers::basis::functions::{Differentiable1D, Function1D};
 
     #[test]
     fn integrate_exp_tabulate() {
@@ -5988,17 +6020,36 @@ mod test {
 
     #[test]
     fn integrate_cosine_0_pi() {
-        let transform = FirstDegreePolynomial::transformation_from_m1_p1(0_f64, PI);
-        let deriv_t = transform.differentiate().unwrap();
+        let transform = IntervalMap::new(0_f64, PI);
 
         let mut sum = 0_f64;
         for i in 1..510 {
             let (theta, w) = quad_pair(510, i).unwrap();
-            let x = transform.evaluate(theta.cos());
-            sum += w * x.cos() * deriv_t.evaluate(0_f64);
+            let x = transform.from_reference(theta.cos());
+            sum += w * x.cos() * transform.jacobian();
         }
 
         let error = sum.abs();
         assert!(error <= 0.0001);
     }
+
+    #[test]
+    fn convergence_of_smooth_function() {
+        let exact = 1_f64.exp() - 1_f64;
+        let values = gauss_legendre_convergence(|x| x.exp(), 0_f64, 1_f64, 8).unwrap();
+
+        assert!(values.len() == 8);
+        assert!(values.iter().map(|(n, _)| *n).eq(1..=8));
+
+        let errors: Vec<f64> = values.iter().map(|(_, value)| (value - exact).abs()).collect();
+        // Error decreases with every node until machine precision is reached
+        for pair in errors.windows(2) {
+            assert!(pair[1] < pair[0] || pair[1] < 1e-14);
+        }
+        assert!(errors[7] < 1e-14);
+
+        // n nodes integrate polynomials of degree 2n - 1 exactly
+        let values = gauss_legendre_convergence(|x| x.powi(5), -1_f64, 2_f64, 3).unwrap();
+        assert!((values[2].1 - 63_f64 / 6_f64).abs() < 1e-12);
+    }
 }
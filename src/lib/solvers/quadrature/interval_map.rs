@@ -0,0 +1,56 @@
+use crate::solvers::Scalar;
+/// # General Information
+///
+/// Affine change of variable between the reference interval [-1,1] (where Gauss-Legendre nodes live) and any interval [a,b].
+/// Every quadrature-based assembly should use it instead of building the transformation by hand.
+///
+/// # Fields
+///
+/// * `a` - Start of interval
+/// * `b` - End of interval
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IntervalMap {
+    pub a: Scalar,
+    pub b: Scalar,
+}
+
+impl IntervalMap {
+    /// Creates a map between [-1,1] and [a,b].
+    pub fn new(a: Scalar, b: Scalar) -> Self {
+        Self { a, b }
+    }
+
+    /// Maps a point of [a,b] to [-1,1].
+    pub fn to_reference(&self, x: Scalar) -> Scalar {
+        (2.0 * x - self.a - self.b) / (self.b - self.a)
+    }
+
+    /// Maps a point of [-1,1] to [a,b].
+    pub fn from_reference(&self, xi: Scalar) -> Scalar {
+        (self.b - self.a) / 2.0 * xi + (self.a + self.b) / 2.0
+    }
+
+    /// Derivative of `from_reference`. Constant, since the map is affine.
+    pub fn jacobian(&self) -> Scalar {
+        (self.b - self.a) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::IntervalMap;
+
+    #[test]
+    fn round_trip_and_jacobian() {
+        let map = IntervalMap::new(0.5, 2.0);
+
+        for xi in [-1.0, -0.3, 0.0, 0.7, 1.0] {
+            assert!((map.to_reference(map.from_reference(xi)) - xi).abs() < 1e-14);
+        }
+        assert!(map.from_reference(-1.0) == 0.5);
+        assert!(map.from_reference(1.0) == 2.0);
+        assert!(map.to_reference(1.25) == 0.0);
+        assert!(map.jacobian() == 0.75);
+    }
+}
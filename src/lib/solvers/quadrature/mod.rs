@@ -1,2 +1,7 @@
 // Module definition
-pub mod gauss_legendre;
\ No newline at end of file
+pub mod gauss_legendre;
+pub mod interval_map;
+pub mod triangle;
+
+/// Amount of Gauss-Legendre nodes used for every integral when none is given.
+pub(crate) const DEFAULT_INTEGRATION_ITERATION: usize = 150;
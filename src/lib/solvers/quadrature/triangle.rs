@@ -0,0 +1,78 @@
+/// # General information
+///
+/// Symmetric Gauss quadrature rules over triangles. Points are given in barycentric coordinates (l1, l2, l3), so that any triangle with vertices
+/// v1, v2, v3 is integrated evaluating the integrand at l1 * v1 + l2 * v2 + l3 * v3.
+/// Weights add up to 1/2 (area of reference triangle (0,0), (1,0), (0,1)). To integrate over any other triangle, multiply the result by
+/// twice its area (determinant of the affine transformation).
+///
+/// Rules are taken from **Strang, G., Fix, G. (1973). An Analysis of the Finite Element Method. Prentice-Hall.**
+///
+use crate::Error;
+use crate::solvers::Scalar;
+
+/// # General Information
+///
+/// Obtains barycentric points and weights of a symmetric rule over the reference triangle.
+///
+/// # Parameters
+///
+/// * `order` - Maximum degree of polynomials integrated exactly. Only 1 (centroid), 2 (three points) and 3 (four points) are available.
+///
+pub fn triangle_quadrature(order: usize) -> Result<(Vec<[Scalar; 3]>, Vec<Scalar>), Error> {
+    match order {
+        1 => Ok((vec![[1. / 3., 1. / 3., 1. / 3.]], vec![0.5])),
+        2 => Ok((
+            vec![
+                [2. / 3., 1. / 6., 1. / 6.],
+                [1. / 6., 2. / 3., 1. / 6.],
+                [1. / 6., 1. / 6., 2. / 3.],
+            ],
+            vec![1. / 6.; 3],
+        )),
+        3 => Ok((
+            vec![
+                [1. / 3., 1. / 3., 1. / 3.],
+                [0.6, 0.2, 0.2],
+                [0.2, 0.6, 0.2],
+                [0.2, 0.2, 0.6],
+            ],
+            vec![-27. / 96., 25. / 96., 25. / 96., 25. / 96.],
+        )),
+        _ => Err(Error::Integration(format!(
+            "Triangle quadrature of order {} is not available. Use 1, 2 or 3",
+            order
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::triangle_quadrature;
+
+    /// Integrates over reference triangle (0,0), (1,0), (0,1) where x = l2 and y = l3
+    fn integrate<F: Fn(f64, f64) -> f64>(order: usize, f: F) -> f64 {
+        let (points, weights) = triangle_quadrature(order).unwrap();
+        points
+            .iter()
+            .zip(weights.iter())
+            .map(|(point, w)| f(point[1], point[2]) * w)
+            .sum()
+    }
+
+    #[test]
+    fn constant_and_linear_are_exact() {
+        for order in 1..=3 {
+            assert!((integrate(order, |_, _| 1.0) - 0.5).abs() < 1e-14);
+            assert!((integrate(order, |x, _| x) - 1. / 6.).abs() < 1e-14);
+            assert!((integrate(order, |_, y| y) - 1. / 6.).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn higher_degrees_are_exact() {
+        assert!((integrate(2, |x, _| x * x) - 1. / 12.).abs() < 1e-14);
+        assert!((integrate(2, |x, y| x * y) - 1. / 24.).abs() < 1e-14);
+        assert!((integrate(3, |x, _| x * x * x) - 1. / 20.).abs() < 1e-14);
+        assert!(triangle_quadrature(4).is_err());
+    }
+}
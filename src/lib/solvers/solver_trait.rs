@@ -1,8 +1,45 @@
 // Local dependencies
+use super::{norms::l2_norm, Scalar};
 use crate::Error;
 
 // External dependencies
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// # General Information
+///
+/// Measurements of a single call to `solve`, obtained via `solve_with_stats`. Useful for benchmarking.
+///
+/// # Fields
+///
+/// * `assembly_time` - Time spent creating matrices and vectors of the discrete problem (normally at creation of solver)
+/// * `solve_time` - Time spent inside `solve`
+/// * `iterations` - Iterations needed by an iterative method. None for direct methods
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SolveStats {
+    pub assembly_time: Duration,
+    pub solve_time: Duration,
+    pub iterations: Option<usize>,
+}
+
+/// # General Information
+///
+/// Internal state a solver needs to continue solving from where it was left, instead of starting over from its initial conditions.
+/// Matrices are not included, since they are assembled again from params and mesh.
+///
+/// # Fields
+///
+/// * `state` - Value of solution at every node after last step
+/// * `time` - Simulated time reached by solver
+///
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SolverCheckpoint {
+    pub state: Vec<Scalar>,
+    pub time: Scalar,
+}
 
 /// # General Information
 ///
@@ -20,5 +57,125 @@ pub trait DiffEquationSolver: Debug {
     /// * `&self` - An instance of an ODE/PDE solver.
     /// * `time_step` - Optional for time independent methods, but important for others to move forward the solution.
     ///
-    fn solve(&mut self, time_step: f64) -> Result<Vec<f64>, Error>;
-}
\ No newline at end of file
+    fn solve(&mut self, time_step: Scalar) -> Result<Vec<Scalar>, Error>;
+
+    /// # General Information
+    ///
+    /// Amount of values returned by every call to `solve`. For linear bases on a 1D mesh this is one value per node, boundary nodes included
+    /// (their values are set by boundary conditions rather than solved for). Solvers with several fields per node count every field.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - An instance of an ODE/PDE solver.
+    ///
+    fn dof_count(&self) -> usize;
+
+    /// Internal state needed to continue solving later. None by default, for solvers whose solution does not depend on previous steps.
+    fn checkpoint(&self) -> Option<SolverCheckpoint> {
+        None
+    }
+
+    /// # General Information
+    ///
+    /// Continues from a checkpoint obtained with `checkpoint` from a solver built with the same params and mesh. The next call to `solve`
+    /// gives the same result it would have given on the original solver. Fails by default, since only solvers with internal state can be restored.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - An instance of an ODE/PDE solver.
+    /// * `checkpoint` - State to continue from.
+    ///
+    fn restore(&mut self, _checkpoint: &SolverCheckpoint) -> Result<(), Error> {
+        Err(Error::custom("Solver has no internal state to restore"))
+    }
+
+    /// Time spent assembling the discrete problem. Solvers that assemble matrices should override it. Zero by default.
+    fn assembly_time(&self) -> Duration {
+        Duration::ZERO
+    }
+
+    /// # General Information
+    ///
+    /// Calls `solve` once and returns its result along with the time it took and the time assembly took.
+    /// Iterative methods should override this method to report their iteration count.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - An instance of an ODE/PDE solver.
+    /// * `time_step` - Step used to move forward the solution.
+    ///
+    fn solve_with_stats(&mut self, time_step: Scalar) -> Result<(Vec<Scalar>, SolveStats), Error> {
+        let start = Instant::now();
+        let solution = self.solve(time_step)?;
+        let solve_time = start.elapsed();
+
+        Ok((solution, SolveStats {
+            assembly_time: self.assembly_time(),
+            solve_time,
+            iterations: None,
+        }))
+    }
+
+    /// # General Information
+    ///
+    /// Advances the solution until it stops changing, that is, until the euclidean norm of the difference between two consecutive solutions
+    /// is smaller than `tol`. Time-independent solvers only need a single call to `solve` and should override this method.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - An instance of an ODE/PDE solver.
+    /// * `time_step` - Step used to move forward the solution.
+    /// * `tol` - Tolerance for the norm of the change between two consecutive solutions.
+    /// * `max_steps` - Maximum amount of calls to `solve` before returning `Error::Convergence`.
+    ///
+    fn solve_to_steady_state(&mut self, time_step: Scalar, tol: Scalar, max_steps: usize) -> Result<Vec<Scalar>, Error> {
+        self.solve_to_steady_state_or_cancel(time_step, tol, max_steps, &AtomicBool::new(false))
+    }
+
+    /// # General Information
+    ///
+    /// Same as `solve_to_steady_state`, but `stop` is checked before every step. Whenever it is set (normally from another thread through an
+    /// `Arc<AtomicBool>`), the loop ends and `Error::Cancelled` is returned with the last solution obtained.
+    ///
+    /// # Parameters
+    ///
+    /// * `&mut self` - An instance of an ODE/PDE solver.
+    /// * `time_step` - Step used to move forward the solution.
+    /// * `tol` - Tolerance for the norm of the change between two consecutive solutions.
+    /// * `max_steps` - Maximum amount of calls to `solve` before returning `Error::Convergence`.
+    /// * `stop` - Flag that cancels the solve once set.
+    ///
+    fn solve_to_steady_state_or_cancel(&mut self, time_step: Scalar, tol: Scalar, max_steps: usize, stop: &AtomicBool) -> Result<Vec<Scalar>, Error> {
+        if stop.load(Ordering::Relaxed) {
+            return Err(Error::Cancelled(vec![]));
+        }
+
+        let mut previous = self.solve(time_step)?;
+
+        for _ in 1..max_steps {
+            if stop.load(Ordering::Relaxed) {
+                return Err(Error::Cancelled(previous));
+            }
+
+            let current = self.solve(time_step)?;
+
+            if current.len() != previous.len() {
+                return Err(Error::WrongDims);
+            }
+
+            let difference: Vec<Scalar> = current.iter().zip(previous.iter()).map(|(c, p)| c - p).collect();
+            let change = l2_norm(&difference);
+
+            if change < tol {
+                return Ok(current);
+            }
+
+            previous = current;
+        }
+
+        Err(Error::Convergence(format!(
+            "steady state not reached after {} steps with tolerance {}",
+            max_steps, tol
+        )))
+    }
+}
@@ -1,12 +1,124 @@
 use crate::Error;
 
+use serde::ser::{Serialize, SerializeMap, Serializer};
 use std::fs::{self, File};
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::Receiver;
 use std::thread;
 use std::time::Instant;
 
+/// # General Information
+///
+/// Kind of file values are written to. Chosen from the extension of the file prefix (or of the write location when it names a file).
+///
+/// # Arms
+///
+/// * `Csv` - Header with variable names followed by a line of comma-separated values per node. Used when no extension is given
+/// * `Json` - Array with an object per node, holding a value per variable
+/// * `Vtk` - VTK unstructured grid (.vtu) with a vertex per node and a point data array per variable. Element quantities become cells with cell data.
+///   Points are placed at node coordinates when writer is given them, and on the x axis at the index of every node otherwise
+/// * `Raw` - Values separated by spaces, a line per node and no header. Used for unknown extensions
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WriterFormat {
+    Csv,
+    Json,
+    Vtk,
+    Raw,
+}
+
+impl WriterFormat {
+    /// Format matching the extension of a file name. Names without extension are written as CSV, while unknown extensions fall back to raw with a warning.
+    pub(crate) fn from_name<A: AsRef<Path>>(name: A) -> Self {
+        match name.as_ref().extension().and_then(|extension| extension.to_str()) {
+            None => WriterFormat::Csv,
+            Some(extension) => match extension.to_lowercase().as_str() {
+                "csv" => WriterFormat::Csv,
+                "json" => WriterFormat::Json,
+                "vtu" | "vtk" => WriterFormat::Vtk,
+                other => {
+                    log::warn!("Unknown output extension '{}', values will be written as raw text", other);
+                    WriterFormat::Raw
+                }
+            },
+        }
+    }
+
+    /// Extension given to files written with this format.
+    fn extension(&self) -> &'static str {
+        match self {
+            WriterFormat::Csv => "csv",
+            WriterFormat::Json => "json",
+            WriterFormat::Vtk => "vtu",
+            WriterFormat::Raw => "txt",
+        }
+    }
+}
+
+/// # General Information
+///
+/// Quantities defined on every element of a mesh rather than on every node, like fluxes (derivatives of a solution are constant on linear elements).
+///
+/// # Fields
+///
+/// * `elements` - Indices of nodes of every element
+/// * `names` - Name of every quantity
+/// * `values` - Value of every quantity on every element, one element after another
+///
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ElementData {
+    pub(crate) elements: Vec<Vec<usize>>,
+    pub(crate) names: Vec<&'static str>,
+    pub(crate) values: Vec<f64>,
+}
+
+impl ElementData {
+    /// Creates element data. Errors when values do not hold every quantity on every element.
+    pub(crate) fn new(elements: Vec<Vec<usize>>, names: Vec<&'static str>, values: Vec<f64>) -> Result<Self, Error> {
+        if elements.len() * names.len() != values.len() {
+            return Err(Error::WrongDims);
+        }
+
+        Ok(Self { elements, names, values })
+    }
+
+    /// Every element along with its index, its nodes and the value of every quantity on it.
+    fn elements(&self) -> impl Iterator<Item = (usize, &[usize], &[f64])> {
+        self.elements
+            .iter()
+            .zip(self.values.chunks(self.names.len().max(1)))
+            .enumerate()
+            .map(|(element, (nodes, values))| (element, nodes.as_slice(), values))
+    }
+
+    /// VTK cell type of an element with a given amount of nodes: vertex, line, triangle, tetrahedron or polygon otherwise.
+    fn vtk_cell_type(nodes: usize) -> u8 {
+        match nodes {
+            1 => 1,
+            2 => 3,
+            3 => 5,
+            4 => 10,
+            _ => 7,
+        }
+    }
+}
+
+/// # General Information
+///
+/// Everything written at once: values on nodes and, optionally, quantities on elements. Both files share their id.
+///
+/// # Fields
+///
+/// * `nodes` - Values of every variable at every node, one node after another
+/// * `elements` - Quantities defined on elements (like fluxes). None when only nodes are written
+///
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Output {
+    pub(crate) nodes: Vec<f64>,
+    pub(crate) elements: Option<ElementData>,
+}
+
 /// # General Information
 /// 
 /// Writes solution of equation to a given file.
@@ -15,16 +127,24 @@ use std::time::Instant;
 /// 
 /// # Fields
 /// 
-/// * `receiver` - A sync_channel receiver to obtain vector values to write to file 
+/// * `receiver` - A sync_channel receiver to obtain values on nodes (and on elements, if any) to write to files
 /// * `write_path` - A directory to write files in
 /// * `variable_names` - Chosen by a given equation. Normally a vector like ['x','y','z'] or similar
-/// * `file_prefix` - To identify files from a single simulation
+/// * `file_prefix` - To identify files from a single simulation. Extension is removed, since it only chooses `format`
+/// * `format` - Kind of file written
+/// * `precision` - Decimal places written per value. Default float formatting when None
+/// * `stride` - Only every k-th node is written, in a line starting with its index. Every node is written without index when None
+/// * `coordinates` - Point of every node, used to place VTK points. Nodes are placed on the x axis at their index when empty
 /// 
 pub(crate) struct Writer {
-    pub(crate) receiver: Receiver<Vec<f64>>,
+    pub(crate) receiver: Receiver<Output>,
     write_path: PathBuf,
     variable_names: Vec<&'static str>,
-    file_prefix: String
+    file_prefix: String,
+    format: WriterFormat,
+    precision: Option<usize>,
+    stride: Option<usize>,
+    coordinates: Vec<[f64; 3]>,
 }
 
 impl Writer {
@@ -33,19 +153,21 @@ impl Writer {
     /// 
     /// Creates a new instance of writer (And there should be only one, but it is not enforced).
     /// Can be told to erase previous files on a directory.
-    /// Will complain if directory is not present
+    /// Will complain if directory is not present.
+    /// Format is inferred from the extension of `file_prefix`. When `write_path` does not exist but names a file (like "out.vtu"), its
+    /// parent directory is used instead and its name becomes the prefix (and chooses the format) unless `file_prefix` already has an extension.
     /// 
     /// # Parameters
     /// 
     /// * `receiver` - A receiver to obtain the solution to an equation
     /// * `write_path` - The path in which files are created and written to
-    /// * `file_prefix` - Prefix for all files of a given simulation
+    /// * `file_prefix` - Prefix for all files of a given simulation. Its extension (if any) chooses the format
     /// * `variable_names` - A vector with all variables of a problem. Chosen by the equation struct in dzahui window. Also determines how many elements
     /// from solution vector are taken per line
     /// * `erase_prev_dir` - Option to erase every file inside dir given. Will not erase nested directories
     /// 
     pub(crate) fn new<A, B, C>(
-        receiver: Receiver<Vec<f64>>,
+        receiver: Receiver<Output>,
         write_path: B,
         file_prefix: A,
         variable_names: C,
@@ -55,7 +177,21 @@ impl Writer {
         B: AsRef<str>,
         C: IntoIterator<Item = &'static str> {
 
-        let write_path = PathBuf::from(write_path.as_ref().to_string());
+        let mut write_path = PathBuf::from(write_path.as_ref().to_string());
+        let mut file_prefix = PathBuf::from(file_prefix.as_ref());
+
+        // A location naming a file is split into its directory and a prefix
+        if !write_path.exists() && write_path.extension().is_some() {
+            if file_prefix.extension().is_none() {
+                let name = write_path.file_name().map(PathBuf::from).unwrap_or_default();
+                file_prefix = PathBuf::from(format!("{}{}", file_prefix.display(), name.display()));
+            }
+            write_path = match write_path.parent() {
+                Some(parent) if parent != Path::new("") => parent.to_path_buf(),
+                _ => PathBuf::from("."),
+            };
+        }
+        let format = WriterFormat::from_name(&file_prefix);
 
         if !write_path.as_path().exists() {
             return Err(Error::NotFound("Path for creating files and writing values not found"))
@@ -81,10 +217,46 @@ impl Writer {
             receiver,
             write_path,
             variable_names: variable_names.into_iter().collect(),
-            file_prefix: file_prefix.as_ref().to_string()
+            file_prefix: file_prefix.with_extension("").to_string_lossy().to_string(),
+            format,
+            precision: None,
+            stride: None,
+            coordinates: vec![],
         })
     }
 
+    /// Changes amount of decimal places written per value. None keeps default float formatting.
+    pub(crate) fn with_precision(self, precision: Option<usize>) -> Self {
+        Self {
+            precision,
+            ..self
+        }
+    }
+
+    /// Writes only every k-th node, preceded by its index. None writes every node without index.
+    pub(crate) fn with_stride(self, stride: Option<usize>) -> Self {
+        Self {
+            stride,
+            ..self
+        }
+    }
+
+    /// Places VTK points at the coordinates of every node instead of at their index.
+    pub(crate) fn with_coordinates(self, coordinates: Vec<[f64; 3]>) -> Self {
+        Self {
+            coordinates,
+            ..self
+        }
+    }
+
+    /// VTK point of a node: its coordinates when known, or its index on the x axis.
+    fn point(&self, node: usize) -> String {
+        match self.coordinates.get(node) {
+            Some([x, y, z]) => format!("{} {} {}", x, y, z),
+            None => format!("{} 0 0", node),
+        }
+    }
+
     /// # General Information
     /// 
     /// Writes once to a file created inside. Will create a file for every call.
@@ -98,21 +270,247 @@ impl Writer {
     /// 
     pub(crate) fn write(&self, id: f64, vals: Vec<f64>) -> Result<(),Error> {
 
-        //Create file name
-        let mut file_name = self.file_prefix.clone();
-        file_name.push_str(id.to_string().as_str());
-        file_name.push_str(".csv");
-        
-        // Create file path
+        // Create file
+        let mut file = File::create(self.file_path(id))?;
+        file.write_all(self.contents(&vals)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Path of file written for a given id: prefix, id and the extension of format inside `write_path`.
+    fn file_path(&self, id: f64) -> PathBuf {
         let mut file_path = self.write_path.clone();
-        file_path.push(file_name);
+        file_path.push(format!("{}{}.{}", self.file_prefix, id, self.format.extension()));
+        file_path
+    }
 
-        // Create file
-        let mut file = File::create(file_path)?;
+    /// # General Information
+    ///
+    /// Writes quantities defined on elements to a file created inside, named like node files with "elements" before the id.
+    /// Stride is ignored, so every element is written along with its index.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - A reference to itself to use `write_path`, `file_prefix` and `format`
+    /// * `id` - A unique id for a file
+    /// * `data` - Elements and the value of every quantity on them
+    ///
+    pub(crate) fn write_elements(&self, id: f64, data: &ElementData) -> Result<(), Error> {
+        let mut file = File::create(self.element_file_path(id))?;
+        file.write_all(self.element_contents(data)?.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Path of file of element quantities written for a given id.
+    fn element_file_path(&self, id: f64) -> PathBuf {
+        let mut file_path = self.write_path.clone();
+        file_path.push(format!("{}elements{}.{}", self.file_prefix, id, self.format.extension()));
+        file_path
+    }
+
+    /// Text of a file of element quantities in the format of writer. Nodes of every element are written next to its index.
+    fn element_contents(&self, data: &ElementData) -> Result<String, Error> {
+        let join = |values: &[usize], separator: &str| values.iter().map(|node| node.to_string()).collect::<Vec<String>>().join(separator);
+        let values = |values: &[f64]| values.iter().map(|e| format_value(*e, self.precision)).collect::<Vec<String>>();
+
+        Ok(match self.format {
+            WriterFormat::Csv => {
+                let header = ["element", "nodes"].iter().chain(data.names.iter()).copied().collect::<Vec<&str>>().join(",");
+                data.elements().fold(format!("{}\n", header), |mut contents, (element, nodes, point)| {
+                    let line = [element.to_string(), join(nodes, " ")].into_iter().chain(values(point)).collect::<Vec<String>>();
+                    contents.push_str(&line.join(","));
+                    contents.push('\n');
+                    contents
+                })
+            }
+            WriterFormat::Json => json_array(data.elements().map(|(element, nodes, point)| JsonRecord {
+                index: Some(("element", element)),
+                nodes: Some(nodes),
+                names: &data.names,
+                values: point,
+                precision: self.precision,
+            }))?,
+            WriterFormat::Vtk => self.vtk_element_contents(data),
+            WriterFormat::Raw => data.elements().fold(String::new(), |mut contents, (element, _, point)| {
+                let line = [element.to_string()].into_iter().chain(values(point)).collect::<Vec<String>>();
+                contents.push_str(&line.join(" "));
+                contents.push('\n');
+                contents
+            }),
+        })
+    }
+
+    /// # General Information
+    ///
+    /// VTK unstructured grid whose cells are the elements, with a cell data array per quantity. As with node values, points are placed
+    /// at node coordinates when known, and on the x axis at their index otherwise.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - A reference to itself to use `precision` and `coordinates`
+    /// * `data` - Elements and the value of every quantity on them
+    ///
+    fn vtk_element_contents(&self, data: &ElementData) -> String {
+        let join = |values: Vec<String>| values.join(" ");
+        let nodes = data.elements.iter().flatten().max().map_or(0, |node| node + 1);
+
+        let points = join((0..nodes).map(|node| self.point(node)).collect());
+        let connectivity = join(data.elements.iter().flatten().map(|node| node.to_string()).collect());
+        let offsets = join(
+            data.elements
+                .iter()
+                .scan(0, |offset, element| {
+                    *offset += element.len();
+                    Some(offset.to_string())
+                })
+                .collect(),
+        );
+        let types = join(data.elements.iter().map(|element| ElementData::vtk_cell_type(element.len()).to_string()).collect());
+        let arrays: Vec<String> = data
+            .names
+            .iter()
+            .enumerate()
+            .map(|(quantity, name)| {
+                let values = join(data.elements().map(|(_, _, point)| format_value(point[quantity], self.precision)).collect());
+                format!("        <DataArray type=\"Float64\" Name=\"{}\" format=\"ascii\">{}</DataArray>", name, values)
+            })
+            .collect();
+
+        let mut lines = vec![
+            "<?xml version=\"1.0\"?>".to_string(),
+            "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">".to_string(),
+            "  <UnstructuredGrid>".to_string(),
+            format!("    <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">", nodes, data.elements.len()),
+            "      <Points>".to_string(),
+            format!("        <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">{}</DataArray>", points),
+            "      </Points>".to_string(),
+            "      <Cells>".to_string(),
+            format!("        <DataArray type=\"Int64\" Name=\"connectivity\" format=\"ascii\">{}</DataArray>", connectivity),
+            format!("        <DataArray type=\"Int64\" Name=\"offsets\" format=\"ascii\">{}</DataArray>", offsets),
+            format!("        <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">{}</DataArray>", types),
+            "      </Cells>".to_string(),
+            "      <CellData>".to_string(),
+        ];
+        lines.extend(arrays);
+        lines.extend(["      </CellData>", "    </Piece>", "  </UnstructuredGrid>", "</VTKFile>", ""].map(String::from));
+
+        lines.join("\n")
+    }
+
+    /// # General Information
+    ///
+    /// Text of a file in the format of writer. When a stride is given, only every k-th node is kept along with its index.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - A reference to itself to use `format`, `variable_names`, `precision` and `stride`
+    /// * `vals` - Values of every variable at every node, one node after another
+    ///
+    fn contents(&self, vals: &[f64]) -> Result<String, Error> {
+        Ok(match self.format {
+            WriterFormat::Csv => self.csv_contents(vals),
+            WriterFormat::Json => self.json_contents(vals)?,
+            WriterFormat::Vtk => self.vtk_contents(vals),
+            WriterFormat::Raw => self.raw_contents(vals),
+        })
+    }
+
+    /// Nodes written, along with their index and the value of every variable on them.
+    fn nodes<'a>(&self, vals: &'a [f64]) -> impl Iterator<Item = (usize, &'a [f64])> {
+        vals.chunks(self.variable_names.len().max(1)).enumerate().step_by(self.stride.unwrap_or(1))
+    }
+
+    /// Values separated by spaces, a line per node. Index of node goes first when a stride is given.
+    fn raw_contents(&self, vals: &[f64]) -> String {
+        self.nodes(vals).fold(String::new(), |mut contents, (node, point)| {
+            let mut line: Vec<String> = point.iter().map(|e| format_value(*e, self.precision)).collect();
+            if self.stride.is_some() {
+                line.insert(0, node.to_string());
+            }
+            contents.push_str(&line.join(" "));
+            contents.push('\n');
+            contents
+        })
+    }
+
+    /// Array with an object per node. Values that are not finite are written as null, since JSON has no way to represent them.
+    fn json_contents(&self, vals: &[f64]) -> Result<String, Error> {
+        json_array(self.nodes(vals).map(|(node, point)| JsonRecord {
+            index: self.stride.map(|_| ("node", node)),
+            nodes: None,
+            names: &self.variable_names,
+            values: point,
+            precision: self.precision,
+        }))
+    }
+
+    /// # General Information
+    ///
+    /// VTK unstructured grid with a vertex cell per node and a point data array per variable. Every point is placed at the coordinates of its
+    /// node when writer was given them (see `with_coordinates`). Otherwise it is placed on the x axis at its index, and values have to be
+    /// matched with mesh coordinates through it.
+    ///
+    /// # Parameters
+    ///
+    /// * `&self` - A reference to itself to use `variable_names`, `precision`, `stride` and `coordinates`
+    /// * `vals` - Values of every variable at every node, one node after another
+    ///
+    fn vtk_contents(&self, vals: &[f64]) -> String {
+        let nodes: Vec<(usize, &[f64])> = self.nodes(vals).collect();
+        let join = |values: Vec<String>| values.join(" ");
+
+        let points = join(nodes.iter().map(|(node, _)| self.point(*node)).collect());
+        let connectivity = join((0..nodes.len()).map(|i| i.to_string()).collect());
+        let offsets = join((1..=nodes.len()).map(|i| i.to_string()).collect());
+        let types = join(vec!["1".to_string(); nodes.len()]);
+        let arrays: Vec<String> = self
+            .variable_names
+            .iter()
+            .enumerate()
+            .map(|(variable, name)| {
+                let values = join(nodes.iter().map(|(_, point)| format_value(point.get(variable).copied().unwrap_or(f64::NAN), self.precision)).collect());
+                format!("        <DataArray type=\"Float64\" Name=\"{}\" format=\"ascii\">{}</DataArray>", name, values)
+            })
+            .collect();
+
+        let mut lines = vec![
+            "<?xml version=\"1.0\"?>".to_string(),
+            "<VTKFile type=\"UnstructuredGrid\" version=\"0.1\" byte_order=\"LittleEndian\">".to_string(),
+            "  <UnstructuredGrid>".to_string(),
+            format!("    <Piece NumberOfPoints=\"{}\" NumberOfCells=\"{}\">", nodes.len(), nodes.len()),
+            "      <Points>".to_string(),
+            format!("        <DataArray type=\"Float64\" NumberOfComponents=\"3\" format=\"ascii\">{}</DataArray>", points),
+            "      </Points>".to_string(),
+            "      <Cells>".to_string(),
+            format!("        <DataArray type=\"Int64\" Name=\"connectivity\" format=\"ascii\">{}</DataArray>", connectivity),
+            format!("        <DataArray type=\"Int64\" Name=\"offsets\" format=\"ascii\">{}</DataArray>", offsets),
+            format!("        <DataArray type=\"UInt8\" Name=\"types\" format=\"ascii\">{}</DataArray>", types),
+            "      </Cells>".to_string(),
+            "      <PointData>".to_string(),
+        ];
+        lines.extend(arrays);
+        lines.extend(["      </PointData>", "    </Piece>", "  </UnstructuredGrid>", "</VTKFile>", ""].map(String::from));
+
+        lines.join("\n")
+    }
+
+    /// # General Information
+    /// 
+    /// CSV text of a file: a header with variable names followed by a line per node. When a stride is given, only every k-th node is kept and
+    /// every line starts with the index of its node.
+    /// 
+    /// # Parameters
+    /// 
+    /// * `&self` - A reference to itself to use `variable_names`, `precision` and `stride`
+    /// * `vals` - Values of every variable at every node, one node after another
+    /// 
+    fn csv_contents(&self, vals: &[f64]) -> String {
 
         // Write varaibles
         let variables_len = self.variable_names.len();
-        let mut header = self.variable_names.iter().fold(String::from(""), |mut prev, cur| {
+        let first_column = if self.stride.is_some() { vec!["node"] } else { vec![] };
+        let mut header = first_column.iter().chain(self.variable_names.iter()).fold(String::from(""), |mut prev, cur| {
             prev.push_str(cur);
             prev.push(',');
             prev
@@ -122,14 +520,19 @@ impl Writer {
         header.pop();
         header.push('\n');
 
-        file.write(header.as_bytes())?;
+        let mut contents = header;
 
         // Write values
-        for point in vals.chunks(variables_len) {
+        for (node, point) in vals.chunks(variables_len).enumerate().step_by(self.stride.unwrap_or(1)) {
             let mut line = String::new();
+
+            if self.stride.is_some() {
+                line.push_str(node.to_string().as_str());
+                line.push(',');
+            }
             
             for e in point {
-                line.push_str(e.to_string().as_str());
+                line.push_str(format_value(*e, self.precision).as_str());
                 line.push(',');
             }
 
@@ -138,21 +541,76 @@ impl Writer {
             // Add line jump
             line.push('\n');
 
-            file.write(line.as_bytes())?;
+            contents.push_str(&line);
 
         }
 
-        Ok(())
+        contents
+    }
+}
+
+/// Formats a value with a given amount of decimal places, or with default float formatting when `precision` is None.
+pub(crate) fn format_value(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(decimals) => format!("{:.*}", decimals, value),
+        None => value.to_string(),
     }
 }
 
+/// # General Information
+///
+/// A node or element written as a JSON object: its index (if any), its nodes (if any) and then the value of every variable, in that order.
+///
+/// # Fields
+///
+/// * `index` - Key and index of record. None when index is not written
+/// * `nodes` - Nodes of an element. None for nodes
+/// * `names` - Name of every variable
+/// * `values` - Value of every variable
+/// * `precision` - Decimal places kept per value. Values are kept as they are when None
+///
+struct JsonRecord<'a> {
+    index: Option<(&'static str, usize)>,
+    nodes: Option<&'a [usize]>,
+    names: &'a [&'static str],
+    values: &'a [f64],
+    precision: Option<usize>,
+}
+
+impl Serialize for JsonRecord<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some((key, index)) = self.index {
+            map.serialize_entry(key, &index)?;
+        }
+        if let Some(nodes) = self.nodes {
+            map.serialize_entry("nodes", nodes)?;
+        }
+        for (name, value) in self.names.iter().zip(self.values) {
+            // Rounded through its text, so that only requested decimals are written. Non-finite values become null
+            let value = format_value(*value, self.precision).parse::<f64>().unwrap_or(*value);
+            map.serialize_entry(name, &value)?;
+        }
+        map.end()
+    }
+}
+
+/// Array of JSON records, one per line.
+fn json_array<'a>(records: impl Iterator<Item = JsonRecord<'a>>) -> Result<String, Error> {
+    let records = records.map(|record| serde_json::to_string(&record)).collect::<Result<Vec<String>, _>>()?;
+    Ok(format!("[{}]\n", records.join(",\n")))
+}
+
 pub(crate) fn spawn(writer: Writer, timer: Instant) {
     thread::spawn(move || {
         loop {
             if let Ok(vals) = writer.receiver.recv() {
                 
                 let time = timer.elapsed().as_secs_f64();
-                let res = writer.write(time, vals);
+                let res = writer.write(time, vals.nodes).and_then(|()| match &vals.elements {
+                    Some(elements) => writer.write_elements(time, elements),
+                    None => Ok(()),
+                });
                 // Send result back to main thread
                 match res {
                     Ok(()) => log::info!("Data has been saved"),
@@ -164,4 +622,121 @@ pub(crate) fn spawn(writer: Writer, timer: Instant) {
             }
         }
     });
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_value, ElementData, Writer, WriterFormat};
+    use std::path::Path;
+    use std::sync::mpsc;
+
+    #[test]
+    fn formats_to_requested_decimals() {
+        assert!(format_value(1.23456789, Some(2)) == "1.23");
+        assert!(format_value(-0.000123456, Some(4)) == "-0.0001");
+        assert!(format_value(2.0, Some(3)) == "2.000");
+        assert!(format_value(0.1, None) == "0.1");
+    }
+
+    #[test]
+    fn stride_keeps_every_kth_node_with_its_index() {
+        let (_tx, rx) = mpsc::sync_channel(1);
+        let writer = Writer::new(rx, "./", "", ["u"], false).unwrap().with_stride(Some(10));
+        let solution: Vec<f64> = (0..100).map(|i| i as f64 / 2.0).collect();
+
+        let contents = writer.contents(&solution).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert!(lines[0] == "node,u");
+        assert!(lines.len() == 11);
+        for (record, line) in lines[1..].iter().enumerate() {
+            let node = 10 * record;
+            assert!(*line == format!("{},{}", node, node as f64 / 2.0));
+        }
+
+        // Without stride every node is written and no index is added
+        let (_tx, rx) = mpsc::sync_channel(1);
+        let writer = Writer::new(rx, "./", "", ["u"], false).unwrap();
+        assert!(writer.contents(&solution).unwrap().lines().count() == 101);
+    }
+
+    #[test]
+    fn extension_selects_format() {
+        let writer_for = |location: &str, prefix: &str| {
+            let (_tx, rx) = mpsc::sync_channel(1);
+            Writer::new(rx, location, prefix, ["u"], false).unwrap()
+        };
+
+        let csv = writer_for("./", "run.csv");
+        assert!(csv.format == WriterFormat::Csv);
+        assert!(csv.file_path(1.5) == Path::new("./run1.5.csv"));
+        assert!(csv.contents(&[1.0, 2.0]).unwrap() == "u\n1\n2\n");
+
+        let json = writer_for("./", "run.json");
+        assert!(json.format == WriterFormat::Json);
+        assert!(json.contents(&[1.0, f64::NAN]).unwrap() == "[{\"u\":1.0},\n{\"u\":null}]\n");
+
+        // Location naming a file is split into directory and prefix
+        let vtk = writer_for("./out.vtu", "");
+        assert!(vtk.format == WriterFormat::Vtk);
+        assert!(vtk.file_path(2.0) == Path::new("./out2.vtu"));
+        let contents = vtk.contents(&[1.0, 2.0]).unwrap();
+        assert!(contents.contains("NumberOfPoints=\"2\""));
+        assert!(contents.contains("Name=\"u\" format=\"ascii\">1 2</DataArray>"));
+
+        // No extension keeps CSV, unknown ones are written raw
+        assert!(writer_for("./", "run").format == WriterFormat::Csv);
+        let raw = writer_for("./", "run.dat");
+        assert!(raw.format == WriterFormat::Raw);
+        assert!(raw.file_path(3.0) == Path::new("./run3.txt"));
+    }
+
+    #[test]
+    fn element_quantities_are_written_per_element() {
+        let writer_for = |prefix: &str| {
+            let (_tx, rx) = mpsc::sync_channel(1);
+            Writer::new(rx, "./", prefix, ["u"], false).unwrap().with_stride(Some(2))
+        };
+        // Flux of a solution on a line of four nodes
+        let flux = ElementData::new(vec![vec![0, 1], vec![1, 2], vec![2, 3]], vec!["flux"], vec![1.0, 2.0, 3.0]).unwrap();
+        assert!(ElementData::new(vec![vec![0, 1]], vec!["flux"], vec![1.0, 2.0]).is_err());
+
+        let csv = writer_for("run");
+        assert!(csv.element_file_path(1.0) == Path::new("./runelements1.csv"));
+        assert!(csv.element_contents(&flux).unwrap() == "element,nodes,flux\n0,0 1,1\n1,1 2,2\n2,2 3,3\n");
+
+        let json = writer_for("run.json").element_contents(&flux).unwrap();
+        assert!(json.lines().count() == 3);
+        assert!(json.starts_with("[{\"element\":0,\"nodes\":[0,1],\"flux\":1.0},"));
+
+        let vtk = writer_for("run.vtu").element_contents(&flux).unwrap();
+        assert!(vtk.contains("NumberOfPoints=\"4\" NumberOfCells=\"3\""));
+        assert!(vtk.contains("Name=\"offsets\" format=\"ascii\">2 4 6</DataArray>"));
+        assert!(vtk.contains("Name=\"types\" format=\"ascii\">3 3 3</DataArray>"));
+        assert!(vtk.contains("<CellData>\n        <DataArray type=\"Float64\" Name=\"flux\" format=\"ascii\">1 2 3</DataArray>\n      </CellData>"));
+        assert!(!vtk.contains("PointData"));
+    }
+
+    #[test]
+    fn json_is_escaped_and_rounded() {
+        let (_tx, rx) = mpsc::sync_channel(1);
+        let writer = Writer::new(rx, "./", "run.json", ["say \"u\"\n"], false).unwrap().with_precision(Some(2)).with_stride(Some(1));
+
+        let contents = writer.contents(&[1.23456, f64::INFINITY]).unwrap();
+        assert!(contents == "[{\"node\":0,\"say \\\"u\\\"\\n\":1.23},\n{\"node\":1,\"say \\\"u\\\"\\n\":null}]\n");
+        let parsed: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert!(parsed[0]["say \"u\"\n"] == 1.23);
+    }
+
+    #[test]
+    fn vtk_points_follow_given_coordinates() {
+        let (_tx, rx) = mpsc::sync_channel(1);
+        let writer = Writer::new(rx, "./", "run.vtu", ["u"], false).unwrap().with_coordinates(vec![[0.5, 1.0, 0.0], [2.0, -1.0, 3.0]]);
+
+        assert!(writer.contents(&[1.0, 2.0]).unwrap().contains("format=\"ascii\">0.5 1 0 2 -1 3</DataArray>"));
+        let flux = ElementData::new(vec![vec![0, 1]], vec!["flux"], vec![1.0]).unwrap();
+        assert!(writer.element_contents(&flux).unwrap().contains("format=\"ascii\">0.5 1 0 2 -1 3</DataArray>"));
+
+        // Nodes without coordinates are placed at their index
+        assert!(writer.contents(&[1.0, 2.0, 3.0]).unwrap().contains("format=\"ascii\">0.5 1 0 2 -1 3 2 0 0</DataArray>"));
+    }
+}
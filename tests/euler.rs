@@ -1,4 +1,7 @@
-use dzahui::EulerSolver;
+use dzahui::solvers::basis::functions::Function1D;
+use dzahui::solvers::basis::single_variable::polynomials_1d::FirstDegreePolynomial;
+use dzahui::solvers::{l2_norm, linf_norm};
+use dzahui::{EulerSolver, Scalar, SystemEulerSolver};
 
 #[test]
 fn first_order_ode() {
@@ -51,3 +54,81 @@ fn radioactive_decay_radium_ode() {
     assert_eq!(quantity >= 490.0, true);
     assert_eq!(quantity <= 510.0, true);
 }
+
+#[test]
+fn explicit_time_gives_same_result() {
+    // y' = t - y
+    let bundled_solver = EulerSolver::new(|val: &[f64; 2]| val[1] - val[0]);
+    let explicit_solver = EulerSolver::new_with_time(|t: f64, state: &[f64]| t - state[0]);
+
+    let step: f64 = 0.01;
+    let mut bundled = [1.0, 0.0];
+    let mut explicit = [1.0, 0.0];
+    while bundled[1] <= 2.0 {
+        bundled = bundled_solver.do_step(bundled, step);
+        explicit = explicit_solver.do_step(explicit, step);
+    }
+
+    assert_eq!(bundled, explicit);
+}
+
+#[test]
+fn lotka_volterra_system() {
+    // Prey grows and is eaten, predators starve and feed on prey
+    let (alpha, beta, gamma, delta) = (1.1, 0.4, 0.4, 0.1);
+    let new_solver = SystemEulerSolver::new(|_t: f64, state: &[f64]| {
+        vec![
+            alpha * state[0] - beta * state[0] * state[1],
+            delta * state[0] * state[1] - gamma * state[1],
+        ]
+    });
+
+    let step: f64 = 0.001;
+    let mut time: f64 = 0.0;
+    let mut state = vec![10.0, 10.0];
+    let mut prey = vec![];
+    while time <= 30.0 {
+        state = new_solver.do_step(state, time, step);
+        time += step;
+        assert_eq!(state.iter().all(|population| *population > 0.0), true);
+        prey.push(state[0]);
+    }
+
+    // Prey population goes up and down more than once
+    let turns = prey
+        .windows(3)
+        .filter(|w| (w[1] - w[0]) * (w[2] - w[1]) < 0.0)
+        .count();
+    assert_eq!(turns >= 4, true);
+}
+
+#[test]
+fn integrated_decay_goes_to_zero() {
+    // y' = -y
+    let new_solver = EulerSolver::new(|val: &[f64; 2]| -val[0]);
+
+    let trajectory = new_solver.integrate([1.0, 0.0], 0.01, 1000).unwrap();
+
+    assert!(trajectory.len() == 1001);
+    assert!(trajectory[0] == [1.0, 0.0]);
+    assert!(trajectory.windows(2).all(|w| w[1][0] < w[0][0] && w[1][0] > 0.0));
+    // y(10) = e^-10
+    let [last, time] = trajectory[1000];
+    assert!((time - 10.0).abs() < 1e-9);
+    assert!(last < 1e-4);
+    // Same states as stepping by hand
+    assert!(trajectory[1] == new_solver.do_step([1.0, 0.0], 0.01));
+}
+
+#[test]
+fn basis_function_drives_solver() {
+    // Derivative given by a first degree polynomial y' = 1 - y, with solution 1 - exp(-t)
+    let derivative = FirstDegreePolynomial::new(-1.0, 1.0);
+    let new_solver = EulerSolver::new(|val: &[Scalar; 2]| derivative.evaluate(val[0]));
+
+    let trajectory = new_solver.integrate([0.0, 0.0], 0.001, 1000).unwrap();
+    let [pos, time]: [Scalar; 2] = trajectory[trajectory.len() - 1];
+    let expected: Scalar = 1.0 - (-time).exp();
+    assert!((pos - expected).abs() < 1e-3);
+    assert!(l2_norm(&[pos - expected]) == linf_norm(&[pos - expected]));
+}
@@ -0,0 +1,16 @@
+use dzahui::{Mesh, MeshBuilder};
+
+// Results of every method are checked by unit tests inside mesh module. Here it is only checked they can be reached from outside crate
+#[test]
+fn public_mesh_api_is_reachable() {
+    let square = Mesh::builder("./assets/unit_square.obj").build_mesh_2d().unwrap();
+    let solution: Vec<f64> = square.node_coordinates().iter().map(|[x, y, _]| x + y).collect();
+    assert!(square.evaluate_2d(&solution, [0.25, 0.5]).is_ok());
+    assert!(square.element_sizes().unwrap().len() == 2);
+    assert!(square.min_element_size().unwrap() <= square.max_element_size().unwrap());
+    assert!(square.decimate(0.5).is_ok());
+
+    let coarse = MeshBuilder::mesh_1d_from_mapping(0.0, 1.0, 3, |t| t).unwrap().build_mesh_1d(None).unwrap();
+    let refined = coarse.refine_where(&[0.0, 1.0, 0.0], |element, _| element == 0).unwrap();
+    assert!(refined.interpolate_solution_onto(&[0.0, 1.0, 0.0], &coarse).unwrap().len() == refined.node_coordinates().len());
+}